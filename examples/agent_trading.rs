@@ -0,0 +1,26 @@
+//! Approve a fresh agent (API wallet) with the master key, then trade through it.
+//!
+//! The master key in `PRIVATE_KEY` only signs the approval; orders are signed by the agent.
+
+use std::str::FromStr;
+
+use alloy::signers::local::PrivateKeySigner;
+use hl_rs::{BaseUrl, ExchangeClient, NoOp};
+
+#[tokio::main]
+async fn main() {
+    dotenv::dotenv().unwrap();
+
+    let private_key = std::env::var("PRIVATE_KEY").unwrap();
+    let master = PrivateKeySigner::from_str(&private_key).unwrap();
+    println!("master: {}", master.address());
+
+    let client = ExchangeClient::new(BaseUrl::Testnet);
+    let (agent_client, agent) = client.approve_agent(&master, Some("hl-rs")).await.unwrap();
+    // The agent key can trade for the master account: keep it in a secret store to reuse the
+    // agent, never in logs.
+    println!("agent: {}", agent.address());
+
+    let result = agent_client.send_action(NoOp::default()).await.unwrap();
+    println!("noop as agent: {:?}", result);
+}
@@ -12,20 +12,20 @@
 //!
 //! # Optional
 //!
-//! - `ASSET_INDEX` — asset id (default: `11338` spot on testnet, same as `place_order` example).
+//! - `COIN` — coin name resolved through [`hl_rs::AssetRegistry`] (default: `USDH/USDC` spot on
+//!   testnet, same as `place_order` example).
 //! - `BUILDER_F` — builder fee in tenths of a basis point (default: `10` → 1 bp). Must stay
 //!   under the approved `MAX_FEE_RATE` cap.
 //! - `MAX_FEE_RATE` — decimal string for `ApproveBuilderFee::max_fee_rate` (default `0.001`).
 
 use std::str::FromStr;
 
-use alloy::primitives::{Address, address};
+use alloy::primitives::address;
 use alloy::signers::local::PrivateKeySigner;
 use hl_rs::{
-    ApproveBuilderFee, BaseUrl, BatchOrder, BuilderInfo, ExchangeClient, LimitOrderType, OrderType,
-    OrderWire, Tif,
+    ApproveBuilderFee, AssetRegistry, BaseUrl, BatchOrder, BuilderInfo, ExchangeClient, InfoClient,
+    OrderPrecision, OrderWire, Tif,
 };
-use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 
 #[tokio::main]
@@ -36,10 +36,14 @@ async fn main() {
     let wallet = PrivateKeySigner::from_str(&private_key).expect("invalid PRIVATE_KEY");
     let builder = address!("0x0ef4B52b87ddcB520009cCb57cfe83B8c36b3955");
 
-    let asset: u32 = std::env::var("ASSET_INDEX")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(10_000 + 1338);
+    let info = InfoClient::builder(BaseUrl::Testnet)
+        .build()
+        .expect("info client");
+    let registry = AssetRegistry::load(&info)
+        .await
+        .expect("load asset registry");
+    let coin = std::env::var("COIN").unwrap_or_else(|_| "USDH/USDC".to_string());
+    let asset = registry.asset(&coin).expect("unknown COIN");
 
     let builder_f: u32 = 10;
     let max_fee_rate= dec!(10);
@@ -60,15 +64,9 @@ async fn main() {
 
     // Hyperliquid has no separate “market” wire type: use an IOC limit with a price far
     // through the book so it takes liquidity immediately (here, a small buy capped at 10).
-    let order = OrderWire {
-        asset,
-        is_buy: true,
-        limit_px: dec!(10),
-        size: dec!(1),
-        reduce_only: false,
-        order_type: OrderType::Limit(LimitOrderType { tif: Tif::Ioc }),
-        client_order_id: None,
-    };
+    let precision = OrderPrecision::from(registry.info(&coin).expect("unknown COIN"));
+    let order = OrderWire::limit_checked(&precision, asset, true, dec!(10), dec!(1), Tif::Ioc)
+        .expect("order off the tick/lot grid");
 
     let builder_info = BuilderInfo {
         b: builder.to_string().to_lowercase(),
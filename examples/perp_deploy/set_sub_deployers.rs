@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use alloy::primitives::{address, Address};
+use alloy::primitives::address;
 use alloy::signers::local::PrivateKeySigner;
 use hl_rs::{BaseUrl, ExchangeClient, SetSubDeployers, SubDeployerVariant};
 
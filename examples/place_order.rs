@@ -1,11 +1,15 @@
-//! Place a limit order on the perpetuals exchange.
+//! Place a limit order.
 //!
-//! Uses ETH (asset index 4 on testnet) by default. Set ASSET_INDEX in .env to override.
+//! Resolves the asset id from `COIN` in .env (default: `USDH/USDC` spot on testnet), using
+//! the same names as info and ws responses (`"ETH"`, `"PURR/USDC"`, `"@107"`, `"mydex:BTC"`).
 
 use std::str::FromStr;
 
 use alloy::signers::local::PrivateKeySigner;
-use hl_rs::{BaseUrl, BatchOrder, ExchangeClient, LimitOrderType, OrderType, OrderWire, Tif};
+use hl_rs::{
+    AssetRegistry, BaseUrl, BatchOrder, ExchangeClient, InfoClient, OrderPrecision, OrderWire,
+    RoundingMode, Tif,
+};
 use rust_decimal_macros::dec;
 
 #[tokio::main]
@@ -14,18 +18,18 @@ async fn main() {
 
     let url = BaseUrl::Testnet;
 
-    // Add 10_000 to market index for spot
-    let asset: u32 = 10_000 + 1338; // USDH/USDC Spot Testnet
-
-    let order = OrderWire {
-        asset,
-        is_buy: false,
-        limit_px: dec!(1),
-        size: dec!(11.0),
-        reduce_only: false,
-        order_type: OrderType::Limit(LimitOrderType { tif: Tif::Gtc }),
-        client_order_id: None,
-    };
+    let info = InfoClient::builder(url.clone()).build().unwrap();
+    let registry = AssetRegistry::load(&info).await.unwrap();
+
+    let coin = std::env::var("COIN").unwrap_or_else(|_| "USDH/USDC".to_string());
+    let asset = registry.asset(&coin).unwrap();
+    println!("{coin} -> asset {asset}");
+
+    // Round onto the asset's tick/lot grid; sells round up so the order never crosses further.
+    let precision = OrderPrecision::from(registry.info(&coin).unwrap());
+    let order = OrderWire::limit(asset, false, dec!(1), dec!(11.0), Tif::Gtc)
+        .rounded(&precision, RoundingMode::Passive)
+        .unwrap();
 
     let action = BatchOrder::new(vec![order]);
 
@@ -5,7 +5,7 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::{parse_macro_input, DeriveInput, Fields, LitStr};
 
-use crate::{ensure_struct_fields, parse_action_attrs, ActionAttrs};
+use crate::{ensure_struct_fields, parse_action_attrs, response_type_tokens, ActionAttrs};
 
 fn has_nonce_field(fields: &syn::FieldsNamed) -> bool {
     fields
@@ -18,6 +18,7 @@ fn build_l1_action_impl(
     ident: &syn::Ident,
     action_type_lit: &syn::LitStr,
     payload_key_lit: &syn::LitStr,
+    response_type: &TokenStream2,
 ) -> TokenStream2 {
     quote! {
         impl crate::actions::L1Action for #ident {
@@ -29,6 +30,8 @@ fn build_l1_action_impl(
             const ACTION_TYPE: &'static str = <Self as crate::actions::L1Action>::ACTION_TYPE;
             const PAYLOAD_KEY: &'static str = <Self as crate::actions::L1Action>::PAYLOAD_KEY;
 
+            type Response = #response_type;
+
             fn signing_hash(
                 &self,
                 meta: &crate::actions::SigningMeta,
@@ -109,6 +112,7 @@ pub(crate) fn derive_l1_action(input: TokenStream) -> TokenStream {
     let ActionAttrs {
         action_type_override,
         payload_key_override,
+        response_type,
         ..
     } = match parse_action_attrs(&input.attrs) {
         Ok(parsed) => parsed,
@@ -142,5 +146,7 @@ pub(crate) fn derive_l1_action(input: TokenStream) -> TokenStream {
     let payload_key_value = payload_key_override.unwrap_or_else(|| action_type_value.clone());
     let payload_key_lit = LitStr::new(&payload_key_value, ident.span());
 
-    build_l1_action_impl(ident, &action_type_lit, &payload_key_lit).into()
+    let response_type = response_type_tokens(response_type.as_ref());
+
+    build_l1_action_impl(ident, &action_type_lit, &payload_key_lit, &response_type).into()
 }
@@ -12,12 +12,14 @@ struct ActionAttrs {
     action_type_override: Option<String>,
     payload_key_override: Option<String>,
     types_preimage: Option<String>,
+    response_type: Option<syn::Type>,
 }
 pub(crate) fn parse_action_attrs(attrs: &[Attribute]) -> Result<ActionAttrs, syn::Error> {
     let mut parsed = ActionAttrs {
         action_type_override: None,
         payload_key_override: None,
         types_preimage: None,
+        response_type: None,
     };
 
     for attr in attrs {
@@ -40,6 +42,13 @@ pub(crate) fn parse_action_attrs(attrs: &[Attribute]) -> Result<ActionAttrs, syn
                         "types" => {
                             parsed.types_preimage = Some(extract_lit_str(&name_value.value)?);
                         }
+                        "response" => {
+                            let ty = extract_lit_str(&name_value.value)?;
+                            parsed.response_type = Some(
+                                syn::parse_str(&ty)
+                                    .map_err(|e| syn::Error::new(name_value.value.span(), e))?,
+                            );
+                        }
                         _ => {
                             return Err(syn::Error::new(
                                 name_value.span(),
@@ -54,6 +63,14 @@ pub(crate) fn parse_action_attrs(attrs: &[Attribute]) -> Result<ActionAttrs, syn
     Ok(parsed)
 }
 
+/// Response type for `Action::Response`; `crate::ExchangeResponse` unless overridden.
+pub(crate) fn response_type_tokens(response_type: Option<&syn::Type>) -> proc_macro2::TokenStream {
+    match response_type {
+        Some(ty) => quote::quote! { #ty },
+        None => quote::quote! { crate::ExchangeResponse },
+    }
+}
+
 fn extract_lit_str(expr_lit: &Expr) -> Result<String, syn::Error> {
     let Expr::Lit(expr_lit) = expr_lit else {
         return Err(syn::Error::new(expr_lit.span(), "must be a string literal"));
@@ -7,7 +7,7 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::{parse_macro_input, DeriveInput, LitStr};
 
-use crate::{ensure_named_fields, parse_action_attrs, ActionAttrs};
+use crate::{ensure_named_fields, parse_action_attrs, response_type_tokens, ActionAttrs};
 
 /// Field metadata for code generation.
 struct FieldInfo {
@@ -265,6 +265,7 @@ fn build_user_signed_action_impl(
     struct_hash_tokens: &[TokenStream2],
     multisig_hash_tokens: &[TokenStream2],
     uses_time: bool,
+    response_type: &TokenStream2,
 ) -> TokenStream2 {
     quote! {
         impl crate::actions::UserSignedAction for #ident {
@@ -285,6 +286,8 @@ fn build_user_signed_action_impl(
             const ACTION_TYPE: &'static str = <Self as crate::actions::UserSignedAction>::ACTION_TYPE;
             const PAYLOAD_KEY: &'static str = <Self as crate::actions::UserSignedAction>::ACTION_TYPE;
 
+            type Response = #response_type;
+
             fn is_user_signed() -> bool {
                 true
             }
@@ -357,6 +360,7 @@ pub(crate) fn derive_user_signed_action(input: TokenStream) -> TokenStream {
     let ActionAttrs {
         action_type_override,
         types_preimage,
+        response_type,
         ..
     } = match parse_action_attrs(&input.attrs) {
         Ok(parsed) => parsed,
@@ -424,6 +428,7 @@ pub(crate) fn derive_user_signed_action(input: TokenStream) -> TokenStream {
         &struct_hash_tokens,
         &multisig_hash_tokens,
         uses_time,
+        &response_type_tokens(response_type.as_ref()),
     )
     .into()
 }
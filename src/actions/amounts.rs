@@ -0,0 +1,179 @@
+//! Integer amounts used by action wire fields.
+
+use std::fmt;
+
+use alloy::dyn_abi::DynSolType;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    abi_value::{AbiResult, ToAbiValue},
+    Error,
+};
+
+/// USD amount in millionths (`1_000_000` is $1), the unit of the integer `usd` fields of vault
+/// and sub-account actions. Python computes these with `float_to_usd_int`.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct MicroUsd(pub i64);
+
+impl MicroUsd {
+    /// Decimal places of one micro-USD.
+    pub const DECIMALS: u32 = 6;
+
+    /// Exact conversion from dollars. Fails if `usd` has more than 6 decimal places or
+    /// does not fit in an `i64`.
+    pub fn from_decimal(usd: Decimal) -> Result<Self, Error> {
+        scale(usd, Self::DECIMALS)?
+            .to_i64()
+            .map(Self)
+            .ok_or_else(|| out_of_range(usd))
+    }
+
+    /// Amount in dollars.
+    pub fn to_decimal(self) -> Decimal {
+        Decimal::new(self.0, Self::DECIMALS).normalize()
+    }
+}
+
+impl From<i64> for MicroUsd {
+    fn from(micros: i64) -> Self {
+        Self(micros)
+    }
+}
+
+impl TryFrom<Decimal> for MicroUsd {
+    type Error = Error;
+
+    fn try_from(usd: Decimal) -> Result<Self, Error> {
+        Self::from_decimal(usd)
+    }
+}
+
+impl From<MicroUsd> for Decimal {
+    fn from(amount: MicroUsd) -> Self {
+        amount.to_decimal()
+    }
+}
+
+impl fmt::Display for MicroUsd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_decimal().fmt(f)
+    }
+}
+
+/// Token amount in the token's smallest unit, as the `wei` fields of staking actions take it.
+///
+/// The unit depends on the token's `weiDecimals` from spot meta; HYPE has 8 (see
+/// [`Wei::from_hype`]).
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Wei(pub u64);
+
+impl Wei {
+    /// `weiDecimals` of HYPE.
+    pub const HYPE_DECIMALS: u32 = 8;
+
+    /// Exact conversion from a token amount with `wei_decimals` decimals. Fails if `amount`
+    /// is negative, has more decimal places than the token, or does not fit in a `u64`.
+    pub fn from_decimal(amount: Decimal, wei_decimals: u32) -> Result<Self, Error> {
+        scale(amount, wei_decimals)?
+            .to_u64()
+            .map(Self)
+            .ok_or_else(|| out_of_range(amount))
+    }
+
+    /// Token amount for a token with `wei_decimals` decimals.
+    pub fn to_decimal(self, wei_decimals: u32) -> Decimal {
+        Decimal::from_i128_with_scale(self.0.into(), wei_decimals).normalize()
+    }
+
+    /// Exact conversion from HYPE.
+    pub fn from_hype(hype: Decimal) -> Result<Self, Error> {
+        Self::from_decimal(hype, Self::HYPE_DECIMALS)
+    }
+
+    /// Amount in HYPE.
+    pub fn to_hype(self) -> Decimal {
+        self.to_decimal(Self::HYPE_DECIMALS)
+    }
+}
+
+impl From<u64> for Wei {
+    fn from(wei: u64) -> Self {
+        Self(wei)
+    }
+}
+
+impl fmt::Display for Wei {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ToAbiValue for Wei {
+    fn to_abi_value(&self, abi_type: &DynSolType) -> AbiResult {
+        self.0.to_abi_value(abi_type)
+    }
+}
+
+/// `amount * 10^decimals`, refusing to round.
+fn scale(amount: Decimal, decimals: u32) -> Result<Decimal, Error> {
+    let scaled = Decimal::from(10i64.pow(decimals))
+        .checked_mul(amount)
+        .ok_or_else(|| out_of_range(amount))?;
+    if !scaled.fract().is_zero() {
+        return Err(Error::InvalidAmount(format!(
+            "{amount} has more than {decimals} decimal places"
+        )));
+    }
+    Ok(scaled)
+}
+
+fn out_of_range(amount: Decimal) -> Error {
+    Error::InvalidAmount(format!("{amount} is out of range"))
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn micro_usd_converts_exactly() {
+        assert_eq!(
+            MicroUsd::from_decimal(dec!(1.5)).unwrap(),
+            MicroUsd(1_500_000)
+        );
+        assert_eq!(
+            MicroUsd::from_decimal(dec!(-0.000001)).unwrap(),
+            MicroUsd(-1)
+        );
+        assert_eq!(MicroUsd(2_500_000).to_decimal(), dec!(2.5));
+        assert_eq!(MicroUsd(10_000).to_string(), "0.01");
+        assert!(MicroUsd::from_decimal(dec!(0.0000001)).is_err());
+        assert!(MicroUsd::from_decimal(Decimal::MAX).is_err());
+        assert_eq!(serde_json::to_value(MicroUsd(5)).unwrap(), 5);
+    }
+
+    #[test]
+    fn wei_converts_with_token_decimals() {
+        assert_eq!(Wei::from_hype(dec!(1)).unwrap(), Wei(100_000_000));
+        assert_eq!(Wei::from_hype(dec!(0.5)).unwrap(), Wei(50_000_000));
+        assert_eq!(
+            Wei::from_decimal(dec!(2.5), 18).unwrap(),
+            Wei(2_500_000_000_000_000_000)
+        );
+        assert_eq!(Wei(12_345_000_000).to_hype(), dec!(123.45));
+        assert_eq!(Wei(1).to_decimal(18), dec!(0.000000000000000001));
+        assert!(Wei::from_hype(dec!(0.000000001)).is_err());
+        assert!(Wei::from_hype(dec!(-1)).is_err());
+        assert!(Wei::from_decimal(dec!(1000), 18).is_err());
+        assert_eq!(serde_json::to_value(Wei(7)).unwrap(), 7);
+    }
+}
@@ -0,0 +1,169 @@
+//! Client order ids.
+
+use std::{fmt, str::FromStr};
+
+use alloy::primitives::{keccak256, B128};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Error;
+
+/// Client order id: 16 bytes, written as `0x` followed by 32 hex digits.
+///
+/// Always serialized as that string, in JSON and in the msgpack the L1 signing hash is taken
+/// over, so orders carrying one hash like the Python SDK's.
+///
+/// ```
+/// use hl_rs::Cloid;
+///
+/// let cloid: Cloid = "0x00000000000000000000000000000001".parse().unwrap();
+/// assert_eq!(cloid, Cloid::from(1u128));
+/// assert!("0x01".parse::<Cloid>().is_err());
+/// // The same strategy and sequence always give the same id.
+/// assert_eq!(Cloid::from_strategy("grid-eth", 7), Cloid::from_strategy("grid-eth", 7));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cloid(B128);
+
+impl Cloid {
+    pub fn new(bytes: B128) -> Self {
+        Self(bytes)
+    }
+
+    /// Random id.
+    pub fn random() -> Self {
+        Self(B128::from(uuid::Uuid::new_v4().into_bytes()))
+    }
+
+    /// Id derived from a strategy name and a sequence number: the first 16 bytes of
+    /// `keccak256(strategy_id || sequence as big-endian u64)`. Lets a restarted strategy
+    /// recompute the ids of the orders it placed.
+    pub fn from_strategy(strategy_id: &str, sequence: u64) -> Self {
+        let mut preimage = Vec::with_capacity(strategy_id.len() + 8);
+        preimage.extend_from_slice(strategy_id.as_bytes());
+        preimage.extend_from_slice(&sequence.to_be_bytes());
+        Self(B128::from_slice(&keccak256(preimage)[..16]))
+    }
+
+    pub fn as_b128(&self) -> &B128 {
+        &self.0
+    }
+
+    pub fn to_u128(self) -> u128 {
+        u128::from_be_bytes(self.0 .0)
+    }
+}
+
+impl From<B128> for Cloid {
+    fn from(bytes: B128) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<[u8; 16]> for Cloid {
+    fn from(bytes: [u8; 16]) -> Self {
+        Self(B128::from(bytes))
+    }
+}
+
+impl From<u128> for Cloid {
+    fn from(value: u128) -> Self {
+        Self::from(value.to_be_bytes())
+    }
+}
+
+impl From<Cloid> for B128 {
+    fn from(cloid: Cloid) -> Self {
+        cloid.0
+    }
+}
+
+impl FromStr for Cloid {
+    type Err = Error;
+
+    /// Parse `0x` followed by exactly 32 hex digits.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let invalid = || Error::InvalidCloid(format!("`{s}` is not 0x followed by 32 hex digits"));
+        let hex = s.strip_prefix("0x").ok_or_else(invalid)?;
+        if hex.len() != 32 {
+            return Err(invalid());
+        }
+        hex.parse::<B128>().map(Self).map_err(|_| invalid())
+    }
+}
+
+impl fmt::Display for Cloid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}", self.0)
+    }
+}
+
+impl Serialize for Cloid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // `B128` itself becomes raw bytes in non-human-readable formats like msgpack.
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cloid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_displays_the_wire_form() {
+        let text = "0x0123456789abcdef0123456789abcdef";
+        let cloid: Cloid = text.parse().unwrap();
+        assert_eq!(cloid.to_string(), text);
+        assert_eq!(cloid.to_u128(), 0x0123456789abcdef0123456789abcdef);
+        assert_eq!(
+            "0x0123456789ABCDEF0123456789ABCDEF"
+                .parse::<Cloid>()
+                .unwrap(),
+            cloid
+        );
+
+        for bad in [
+            "0123456789abcdef0123456789abcdef",
+            "0x0123456789abcdef0123456789abcde",
+            "0x0123456789abcdef0123456789abcdef00",
+            "0x0123456789abcdef0123456789abcdeg",
+            "test-cloid-12345",
+        ] {
+            assert!(
+                matches!(bad.parse::<Cloid>(), Err(Error::InvalidCloid(_))),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn serializes_as_a_string_in_json_and_msgpack() {
+        let cloid = Cloid::from(1u128);
+        let text = "0x00000000000000000000000000000001";
+        assert_eq!(serde_json::to_value(cloid).unwrap(), text);
+        assert_eq!(
+            rmp_serde::to_vec_named(&cloid).unwrap(),
+            rmp_serde::to_vec_named(text).unwrap()
+        );
+        assert_eq!(
+            rmp_serde::from_slice::<Cloid>(&rmp_serde::to_vec_named(text).unwrap()).unwrap(),
+            cloid
+        );
+        assert!(serde_json::from_str::<Cloid>("\"0x01\"").is_err());
+    }
+
+    #[test]
+    fn generated_ids_differ_unless_derived_from_the_same_input() {
+        assert_ne!(Cloid::random(), Cloid::random());
+        let first = Cloid::from_strategy("grid-eth", 1);
+        assert_eq!(first, Cloid::from_strategy("grid-eth", 1));
+        assert_ne!(first, Cloid::from_strategy("grid-eth", 2));
+        assert_ne!(first, Cloid::from_strategy("grid-btc", 1));
+    }
+}
@@ -2,15 +2,12 @@
 // Prepared and Signed Action Types
 // ============================================================================
 
-use alloy::{
-    primitives::{Address, B256},
-    signers::local::PrivateKeySigner,
-};
+use alloy::primitives::{Address, B256};
 use alloy_signer::{Signature, SignerSync};
 
 use crate::{
     actions::{signing::SigningMeta, traits::Action, ActionKind},
-    Error, SigningChain,
+    nonce, ActionSigner, Error, SigningChain,
 };
 use serde_json;
 
@@ -25,14 +22,18 @@ pub struct PreparedAction<A: Action> {
 }
 
 impl<A: Action> PreparedAction<A> {
-    /// Prepare an action for signing
+    /// Prepare an action for signing. Actions without a nonce get one from the process-wide
+    /// [`nonce::shared`] source, which [`nonce::set_shared`] replaces.
     pub fn new(
         action: A,
         signing_chain: &SigningChain,
         vault_address: Option<Address>,
         expires_after: Option<u64>,
     ) -> Result<Self, Error> {
-        let nonce = action.nonce().unwrap_or_else(current_timestamp_ms);
+        let nonce = match action.nonce() {
+            Some(nonce) => nonce,
+            None => nonce::shared().next_nonce()?,
+        };
         let action = action.with_nonce(nonce);
 
         let meta = SigningMeta {
@@ -58,20 +59,22 @@ impl<A: Action> PreparedAction<A> {
         self.signing_hash
     }
 
-    /// Sign with a local wallet
-    pub fn sign(self, wallet: &PrivateKeySigner) -> Result<SignedAction<A>, Error> {
+    /// Sign with a synchronous signer, e.g. a local wallet
+    pub fn sign<S: SignerSync + ?Sized>(self, wallet: &S) -> Result<SignedAction<A>, Error> {
         let signature = wallet
             .sign_hash_sync(&self.signing_hash)
             .map_err(|e| Error::SignatureFailure(e.to_string()))?;
 
-        Ok(SignedAction {
-            action: self.action,
-            nonce: self.nonce,
-            vault_address: self.vault_address,
-            expires_after: self.expires_after,
-            signature,
-            signing_chain: Some(self.signing_chain),
-        })
+        Ok(self.with_signature(signature))
+    }
+
+    /// Sign with any [`ActionSigner`], e.g. a remote or hardware-backed signer
+    pub async fn sign_with<S: ActionSigner + ?Sized>(
+        self,
+        signer: &S,
+    ) -> Result<SignedAction<A>, Error> {
+        let signature = signer.sign_hash(&self.signing_hash).await?;
+        Ok(self.with_signature(signature))
     }
 
     /// Attach an externally-provided signature
@@ -114,6 +117,25 @@ impl<T: Action> SignedAction<T> {
     pub fn extract_action_kind(&self) -> ActionKind {
         self.action.extract_action_kind()
     }
+
+    /// Address that signed this action, recomputing its signing hash under `signing_chain`.
+    ///
+    /// This is the address the exchange sees; when it is not a known user or approved agent,
+    /// the exchange answers "User or API Wallet ... does not exist".
+    pub fn recover_signer(&self, signing_chain: &SigningChain) -> Result<Address, Error> {
+        let hash = self.action.signing_hash(&SigningMeta {
+            nonce: self.nonce,
+            vault_address: self.vault_address,
+            expires_after: self.expires_after,
+            signing_chain,
+        })?;
+        recover_address(&self.signature, hash)
+    }
+
+    /// Check that this action was signed by `expected` under `signing_chain`.
+    pub fn verify(&self, expected: Address, signing_chain: &SigningChain) -> Result<(), Error> {
+        check_signer(expected, self.recover_signer(signing_chain)?)
+    }
 }
 
 /// Fully signed action envelope, deserialized without a concrete action type.
@@ -135,12 +157,121 @@ impl SignedActionKind {
     pub fn from_json(json: &str) -> Result<Self, Error> {
         serde_json::from_str(json).map_err(|e| Error::JsonParse(e.to_string()))
     }
+
+    /// Address that signed this action, recomputing its signing hash under `signing_chain`.
+    ///
+    /// Errors for [`ActionKind::Unknown`] actions.
+    pub fn recover_signer(&self, signing_chain: &SigningChain) -> Result<Address, Error> {
+        let hash = self.action.signing_hash(&SigningMeta {
+            nonce: self.nonce,
+            vault_address: self.vault_address,
+            expires_after: self.expires_after,
+            signing_chain,
+        })?;
+        recover_address(&self.signature, hash)
+    }
+
+    /// Check that this action was signed by `expected` under `signing_chain`.
+    pub fn verify(&self, expected: Address, signing_chain: &SigningChain) -> Result<(), Error> {
+        check_signer(expected, self.recover_signer(signing_chain)?)
+    }
+}
+
+fn recover_address(signature: &Signature, hash: B256) -> Result<Address, Error> {
+    signature
+        .recover_address_from_prehash(&hash)
+        .map_err(|e| Error::SignatureFailure(e.to_string()))
+}
+
+fn check_signer(expected: Address, recovered: Address) -> Result<(), Error> {
+    if recovered != expected {
+        return Err(Error::SignerMismatch {
+            expected,
+            recovered,
+        });
+    }
+    Ok(())
 }
 
-/// Get current timestamp in milliseconds
-pub fn current_timestamp_ms() -> u64 {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
+#[cfg(test)]
+mod tests {
+    use alloy::signers::local::PrivateKeySigner;
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::actions::{ToggleBigBlocks, UsdSend};
+
+    fn wallet() -> PrivateKeySigner {
+        PrivateKeySigner::from_bytes(&B256::repeat_byte(0x42)).unwrap()
+    }
+
+    fn to_json<T: Action + serde::Serialize>(signed: &SignedAction<T>) -> String {
+        serde_json::to_string(signed).unwrap()
+    }
+
+    #[test]
+    fn recovers_l1_signer_from_json() {
+        let wallet = wallet();
+        let signed = PreparedAction::new(
+            ToggleBigBlocks::enable().with_nonce(1_700_000_000_000),
+            &SigningChain::Testnet,
+            Some(Address::repeat_byte(0x11)),
+            Some(1_700_000_060_000),
+        )
         .unwrap()
-        .as_millis() as u64
+        .sign(&wallet)
+        .unwrap();
+
+        let parsed = SignedActionKind::from_json(&to_json(&signed)).unwrap();
+        assert_eq!(
+            parsed.recover_signer(&SigningChain::Testnet).unwrap(),
+            wallet.address()
+        );
+        parsed
+            .verify(wallet.address(), &SigningChain::Testnet)
+            .unwrap();
+
+        // The L1 source differs per chain, so the wrong chain recovers someone else.
+        let err = parsed
+            .verify(wallet.address(), &SigningChain::Mainnet)
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::SignerMismatch { expected, .. } if expected == wallet.address())
+        );
+    }
+
+    #[test]
+    fn recovers_user_signed_signer() {
+        let wallet = wallet();
+        let action = UsdSend::new(Address::repeat_byte(0x33), dec!(1.5));
+        let signed = PreparedAction::new(action, &SigningChain::Mainnet, None, None)
+            .unwrap()
+            .sign(&wallet)
+            .unwrap();
+
+        assert_eq!(
+            signed.recover_signer(&SigningChain::Mainnet).unwrap(),
+            wallet.address()
+        );
+
+        let parsed = SignedAction::<UsdSend>::from_json(&to_json(&signed)).unwrap();
+        parsed
+            .verify(wallet.address(), &SigningChain::Mainnet)
+            .unwrap();
+        assert!(matches!(
+            parsed.verify(Address::ZERO, &SigningChain::Mainnet),
+            Err(Error::SignerMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn unknown_actions_cannot_be_recovered() {
+        let json = r#"{
+            "action": {"type": "somethingNew", "x": 1},
+            "nonce": 1,
+            "signature": {"r": "0x1", "s": "0x2", "v": 27}
+        }"#;
+        let parsed = SignedActionKind::from_json(json).unwrap();
+        assert!(parsed.recover_signer(&SigningChain::Testnet).is_err());
+    }
 }
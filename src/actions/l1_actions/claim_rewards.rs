@@ -0,0 +1,15 @@
+use hl_rs_derive::L1Action;
+use serde::{Deserialize, Serialize};
+
+/// Claim accrued staking rewards into the staking balance.
+#[derive(Serialize, Deserialize, Debug, Clone, L1Action, Default)]
+pub struct ClaimRewards {
+    #[serde(skip_serializing)]
+    pub nonce: Option<u64>,
+}
+
+impl ClaimRewards {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 
 /// Create a new sub-account.
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
+#[action(response = "alloy::primitives::Address")]
 #[serde(rename_all = "camelCase")]
 pub struct CreateSubAccount {
     /// Name for the sub-account
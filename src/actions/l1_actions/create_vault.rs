@@ -0,0 +1,37 @@
+use hl_rs_derive::L1Action;
+use serde::{Deserialize, Serialize};
+
+use crate::actions::MicroUsd;
+
+/// Create a vault led by the signer, seeded with `initial_usd` from their perp balance.
+///
+/// The exchange requires at least $100 of initial deposit. Unlike most L1 actions, the
+/// nonce is also part of the action body. Responds with the new vault's address.
+#[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
+#[serde(rename_all = "camelCase")]
+#[action(response = "alloy::primitives::Address")]
+pub struct CreateVault {
+    /// Vault name
+    pub name: String,
+    /// Vault description
+    pub description: String,
+    /// Initial deposit in micro-USD
+    pub initial_usd: MicroUsd,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+}
+
+impl CreateVault {
+    pub fn new(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        initial_usd: MicroUsd,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            initial_usd,
+            nonce: None,
+        }
+    }
+}
@@ -28,5 +28,17 @@ pub use sub_account_spot_transfer::SubAccountSpotTransfer;
 mod vault_transfer;
 pub use vault_transfer::VaultTransfer;
 
+mod create_vault;
+pub use create_vault::CreateVault;
+
+mod vault_modify;
+pub use vault_modify::VaultModify;
+
+mod vault_distribute;
+pub use vault_distribute::VaultDistribute;
+
+mod claim_rewards;
+pub use claim_rewards::ClaimRewards;
+
 mod agent_enable_dex_abstraction;
 pub use agent_enable_dex_abstraction::AgentEnableDexAbstraction;
@@ -6,7 +6,11 @@ use serde::{Deserialize, Serialize};
 /// Margin tables define leverage tiers with lower bounds and max leverage.
 /// Max 3 tiers per table.
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "perpDeploy", payload_key = "insertMarginTable")]
+#[action(
+    action_type = "perpDeploy",
+    payload_key = "insertMarginTable",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct InsertMarginTable {
     pub dex: String,
@@ -74,7 +74,11 @@ use serde::{Deserialize, Serialize, Serializer};
 /// assert!(action.asset_request.only_isolated);
 /// ```
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "perpDeploy", payload_key = "registerAsset")]
+#[action(
+    action_type = "perpDeploy",
+    payload_key = "registerAsset",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct RegisterAsset {
     /// Maximum gas for the operation (optional).
@@ -4,7 +4,11 @@ use hl_rs_derive::L1Action;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug, Clone, L1Action)]
-#[action(action_type = "perpDeploy", payload_key = "setFeeRecipient")]
+#[action(
+    action_type = "perpDeploy",
+    payload_key = "setFeeRecipient",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct SetFeeRecipient {
     pub dex: String,
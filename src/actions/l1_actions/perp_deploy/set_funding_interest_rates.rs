@@ -39,7 +39,11 @@ use rust_decimal::Decimal;
 /// let action = SetFundingInterestRates::from_target_apy("mydex", "BTC", 5.0);
 /// ```
 #[derive(Debug, Clone, L1Action)]
-#[action(action_type = "perpDeploy", payload_key = "setFundingInterestRates")]
+#[action(
+    action_type = "perpDeploy",
+    payload_key = "setFundingInterestRates",
+    response = "crate::SetGlobalResponse"
+)]
 pub struct SetFundingInterestRates {
     /// Vec of (asset, interest_rate) tuples.
     /// Interest rates must be between -0.01 and 0.01.
@@ -6,7 +6,11 @@ use hl_rs_derive::L1Action;
 /// Multipliers must be in the range [0, 10].
 /// The tuples are sorted by asset name during serialization.
 #[derive(Debug, Clone, L1Action)]
-#[action(action_type = "perpDeploy", payload_key = "setFundingMultipliers")]
+#[action(
+    action_type = "perpDeploy",
+    payload_key = "setFundingMultipliers",
+    response = "crate::SetGlobalResponse"
+)]
 pub struct SetFundingMultipliers {
     /// Vec of (asset, multiplier) tuples
     pub multipliers: Vec<(String, String)>,
@@ -6,7 +6,11 @@ use hl_rs_derive::L1Action;
 /// Growth mode status can only be changed once every 30 days.
 /// The tuples are sorted by asset name during serialization.
 #[derive(Debug, Clone, L1Action)]
-#[action(action_type = "perpDeploy", payload_key = "setGrowthModes")]
+#[action(
+    action_type = "perpDeploy",
+    payload_key = "setGrowthModes",
+    response = "crate::SetGlobalResponse"
+)]
 pub struct SetGrowthModes {
     /// Vec of (asset, is_growth_mode_enabled) tuples
     pub modes: Vec<(String, bool)>,
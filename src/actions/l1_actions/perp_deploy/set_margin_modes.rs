@@ -38,7 +38,11 @@ pub enum MarginMode {
 /// assert_eq!(action.modes[1].0, "mydex:ETH");
 /// ```
 #[derive(Debug, Clone, L1Action)]
-#[action(action_type = "perpDeploy", payload_key = "setMarginModes")]
+#[action(
+    action_type = "perpDeploy",
+    payload_key = "setMarginModes",
+    response = "crate::SetGlobalResponse"
+)]
 pub struct SetMarginModes {
     /// Vec of (asset, margin_mode) tuples
     pub modes: Vec<(String, MarginMode)>,
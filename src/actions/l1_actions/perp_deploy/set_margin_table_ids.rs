@@ -6,7 +6,11 @@ use hl_rs_derive::L1Action;
 /// References margin tables previously inserted via InsertMarginTable.
 /// The tuples are sorted by asset name during serialization.
 #[derive(Debug, Clone, L1Action)]
-#[action(action_type = "perpDeploy", payload_key = "setMarginTableIds")]
+#[action(
+    action_type = "perpDeploy",
+    payload_key = "setMarginTableIds",
+    response = "crate::SetGlobalResponse"
+)]
 pub struct SetMarginTableIds {
     /// Vec of (asset, margin_table_id) tuples
     pub ids: Vec<(String, i64)>,
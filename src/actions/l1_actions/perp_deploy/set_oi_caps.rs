@@ -44,7 +44,11 @@ use hl_rs_derive::L1Action;
 /// assert_eq!(action.caps[0], ("mydex:SOL".to_string(), 2_000_000));
 /// ```
 #[derive(Debug, Clone, L1Action, Default)]
-#[action(action_type = "perpDeploy", payload_key = "setOpenInterestCaps")]
+#[action(
+    action_type = "perpDeploy",
+    payload_key = "setOpenInterestCaps",
+    response = "crate::SetGlobalResponse"
+)]
 pub struct SetOpenInterestCaps {
     /// List of (coin, cap) tuples. Coin format is "dex:SYMBOL".
     pub caps: Vec<(String, u64)>,
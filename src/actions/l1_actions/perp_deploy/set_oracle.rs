@@ -3,7 +3,11 @@ use serde::ser::{SerializeStruct, Serializer};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "perpDeploy", payload_key = "setOracle")]
+#[action(
+    action_type = "perpDeploy",
+    payload_key = "setOracle",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct SetOracle {
     pub dex: String,
@@ -55,7 +55,11 @@ impl std::error::Error for SetPerpAnnotationBuildError {}
 /// assert_eq!(action.keywords, vec!["perp".to_string(), "crypto".to_string()]);
 /// ```
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "perpDeploy", payload_key = "setPerpAnnotation")]
+#[action(
+    action_type = "perpDeploy",
+    payload_key = "setPerpAnnotation",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct SetPerpAnnotation {
     /// The coin identifier in the format "dex:SYMBOL" (e.g., "mydex:BTC").
@@ -62,7 +62,11 @@ use serde::{Deserialize, Serialize};
 /// assert!(!action.sub_deployers[0].allowed);
 /// ```
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "perpDeploy", payload_key = "setSubDeployers")]
+#[action(
+    action_type = "perpDeploy",
+    payload_key = "setSubDeployers",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct SetSubDeployers {
     /// The DEX name (lowercase).
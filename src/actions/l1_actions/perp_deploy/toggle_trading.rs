@@ -30,7 +30,11 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(action.coin, "mydex:ETH");
 /// ```
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "perpDeploy", payload_key = "haltTrading")]
+#[action(
+    action_type = "perpDeploy",
+    payload_key = "haltTrading",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct ToggleTrading {
     /// The coin identifier in the format "dex:SYMBOL" (e.g., "mydex:BTC").
@@ -2,7 +2,11 @@ use hl_rs_derive::L1Action;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "spotDeploy", payload_key = "enableFreezePrivilege")]
+#[action(
+    action_type = "spotDeploy",
+    payload_key = "enableFreezePrivilege",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct EnableFreezePrivilege {
     pub token: u32,
@@ -2,7 +2,11 @@ use hl_rs_derive::L1Action;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "spotDeploy", payload_key = "enableQuoteToken")]
+#[action(
+    action_type = "spotDeploy",
+    payload_key = "enableQuoteToken",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct EnableQuoteToken {
     pub token: u32,
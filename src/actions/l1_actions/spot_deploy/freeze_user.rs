@@ -2,7 +2,11 @@ use hl_rs_derive::L1Action;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "spotDeploy", payload_key = "freezeUser")]
+#[action(
+    action_type = "spotDeploy",
+    payload_key = "freezeUser",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct FreezeUser {
     pub token: u32,
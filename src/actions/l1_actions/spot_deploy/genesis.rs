@@ -3,7 +3,11 @@ use serde::{Deserialize, Serialize};
 
 /// Genesis configuration for a token.
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "spotDeploy", payload_key = "genesis")]
+#[action(
+    action_type = "spotDeploy",
+    payload_key = "genesis",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct Genesis {
     /// Token index
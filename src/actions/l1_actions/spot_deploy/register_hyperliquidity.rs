@@ -4,7 +4,11 @@ use serde::{Deserialize, Serialize};
 
 /// Register hyperliquidity for a spot market.
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "spotDeploy", payload_key = "registerHyperliquidity")]
+#[action(
+    action_type = "spotDeploy",
+    payload_key = "registerHyperliquidity",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct RegisterHyperliquidity {
     /// Spot market index
@@ -3,7 +3,11 @@ use serde::{Deserialize, Serialize};
 
 /// Register a spot market pair.
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "spotDeploy", payload_key = "registerSpot")]
+#[action(
+    action_type = "spotDeploy",
+    payload_key = "registerSpot",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct RegisterSpot {
     /// Token pair [base_token, quote_token]
@@ -15,7 +15,11 @@ pub struct TokenSpec {
 
 /// Register a new token.
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "spotDeploy", payload_key = "registerToken2")]
+#[action(
+    action_type = "spotDeploy",
+    payload_key = "registerToken2",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct RegisterToken {
     /// Token specification
@@ -5,7 +5,11 @@ use serde::{Deserialize, Serialize};
 ///
 /// See [HyperCore ↔ HyperEVM transfers](https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/hyperevm/hypercore-less-than-greater-than-hyperevm-transfers).
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "spotDeploy", payload_key = "requestEvmContract")]
+#[action(
+    action_type = "spotDeploy",
+    payload_key = "requestEvmContract",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct RequestEvmContract {
     pub token: u32,
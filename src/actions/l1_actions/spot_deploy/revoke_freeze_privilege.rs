@@ -2,7 +2,11 @@ use hl_rs_derive::L1Action;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "spotDeploy", payload_key = "revokeFreezePrivilege")]
+#[action(
+    action_type = "spotDeploy",
+    payload_key = "revokeFreezePrivilege",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct RevokeFreezePrivilege {
     pub token: u32,
@@ -3,7 +3,11 @@ use serde::{Deserialize, Serialize};
 
 /// Set the deployer's trading fee share for a token.
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "spotDeploy", payload_key = "setDeployerTradingFeeShare")]
+#[action(
+    action_type = "spotDeploy",
+    payload_key = "setDeployerTradingFeeShare",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct SetDeployerFees {
     /// Token index
@@ -3,7 +3,11 @@ use serde::{Deserialize, Serialize};
 
 /// User genesis allocation for token distribution.
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "spotDeploy", payload_key = "userGenesis")]
+#[action(
+    action_type = "spotDeploy",
+    payload_key = "userGenesis",
+    response = "crate::SetGlobalResponse"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct UserGenesis {
     /// Token index
@@ -2,7 +2,7 @@ use alloy::primitives::Address;
 use hl_rs_derive::L1Action;
 use serde::{Deserialize, Serialize};
 
-use crate::actions::serialization::ser_lowercase;
+use crate::actions::{serialization::ser_lowercase, MicroUsd};
 
 /// Transfer USD between main account and sub-account.
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
@@ -13,15 +13,15 @@ pub struct SubAccountTransfer {
     pub sub_account_user: Address,
     /// True to deposit to sub-account, false to withdraw
     pub is_deposit: bool,
-    /// USD amount (raw integer, not decimal)
-    pub usd: i64,
+    /// USD amount in micro-USD
+    pub usd: MicroUsd,
     #[serde(skip_serializing)]
     pub nonce: Option<u64>,
 }
 
 impl SubAccountTransfer {
     /// Deposit USD to a sub-account
-    pub fn deposit(sub_account_user: Address, usd: i64) -> Self {
+    pub fn deposit(sub_account_user: Address, usd: MicroUsd) -> Self {
         Self {
             sub_account_user,
             is_deposit: true,
@@ -31,7 +31,7 @@ impl SubAccountTransfer {
     }
 
     /// Withdraw USD from a sub-account
-    pub fn withdraw(sub_account_user: Address, usd: i64) -> Self {
+    pub fn withdraw(sub_account_user: Address, usd: MicroUsd) -> Self {
         Self {
             sub_account_user,
             is_deposit: false,
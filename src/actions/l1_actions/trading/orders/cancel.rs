@@ -16,7 +16,7 @@ pub struct CancelWire {
 /// - `payload_key = "cancels"` incorrectly nests as `"cancels": { "cancels": [...] }` → HTTP 422.
 /// - Default derive uses `type: "batchCancel"`; override with `action_type = "cancel"`.
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "cancel")]
+#[action(action_type = "cancel", response = "crate::CancelStatuses")]
 pub struct BatchCancel {
     /// Cancel requests
     pub cancels: Vec<CancelWire>,
@@ -1,19 +1,22 @@
 use hl_rs_derive::L1Action;
 use serde::{Deserialize, Serialize};
 
+use crate::Cloid;
+
 /// A single cancel request by client order ID.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CancelByCloidWire {
     /// Asset index
     pub asset: u32,
     /// Client order ID
-    pub cloid: String,
+    pub cloid: Cloid,
 }
 
 /// Batch cancel orders by client order ID action.
 ///
 /// Flattened like Python: `{ "type": "cancelByCloid", "cancels": [ ... ] }` (no double `cancels`).
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
+#[action(response = "crate::CancelStatuses")]
 pub struct CancelByCloid {
     /// Cancel requests
     pub cancels: Vec<CancelByCloidWire>,
@@ -29,7 +32,7 @@ impl CancelByCloid {
         }
     }
 
-    pub fn single(asset: u32, cloid: impl Into<String>) -> Self {
+    pub fn single(asset: u32, cloid: impl Into<Cloid>) -> Self {
         Self::new(vec![CancelByCloidWire {
             asset,
             cloid: cloid.into(),
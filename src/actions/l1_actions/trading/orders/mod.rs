@@ -7,8 +7,14 @@ pub use cancel::*;
 mod cancel_by_cloid;
 pub use cancel_by_cloid::*;
 
+mod precision;
+pub use precision::*;
+
 mod modify;
 pub use modify::*;
 
 mod schedule_cancel;
 pub use schedule_cancel::*;
+
+mod twap;
+pub use twap::*;
@@ -21,6 +21,7 @@ pub struct ModifyWire {
 /// a different payload key nests the payload as `"modifies": { "modifies": [...] }`, which Hyperliquid
 /// rejects with HTTP 422 (`Failed to deserialize the JSON body into the target type`).
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
+#[action(response = "crate::ExchangeDataStatuses")]
 pub struct BatchModify {
     /// Modify requests
     pub modifies: Vec<ModifyWire>,
@@ -6,8 +6,11 @@ use serde::{
 };
 use std::fmt;
 
+use super::{OrderPrecision, RoundingMode};
+use crate::Cloid;
+
 /// Serialize Decimal as normalized string for HL wire format (matches Python float_to_wire).
-fn serialize_decimal_wire<S>(d: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+pub(super) fn serialize_decimal_wire<S>(d: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -16,7 +19,7 @@ where
 }
 
 /// Deserialize Decimal from string.
-fn deserialize_decimal_wire<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+pub(super) fn deserialize_decimal_wire<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -165,7 +168,109 @@ pub struct OrderWire {
     pub order_type: OrderType,
     /// Client order ID (wire `c`)
     #[serde(rename = "c", skip_serializing_if = "Option::is_none")]
-    pub client_order_id: Option<String>,
+    pub client_order_id: Option<Cloid>,
+}
+
+impl OrderWire {
+    /// Limit order with the given time-in-force.
+    pub fn limit(asset: u32, is_buy: bool, limit_px: Decimal, size: Decimal, tif: Tif) -> Self {
+        Self {
+            asset,
+            is_buy,
+            limit_px,
+            size,
+            reduce_only: false,
+            order_type: OrderType::Limit(LimitOrderType { tif }),
+            client_order_id: None,
+        }
+    }
+
+    /// Take-profit or stop-loss trigger order. `limit_px` caps the fill price once triggered.
+    pub fn trigger(
+        asset: u32,
+        is_buy: bool,
+        limit_px: Decimal,
+        size: Decimal,
+        trigger_px: Decimal,
+        is_market: bool,
+        tpsl: TpSl,
+    ) -> Self {
+        Self {
+            asset,
+            is_buy,
+            limit_px,
+            size,
+            reduce_only: false,
+            order_type: OrderType::Trigger(TriggerOrderType {
+                trigger_px,
+                is_market,
+                tpsl,
+            }),
+            client_order_id: None,
+        }
+    }
+
+    /// [`limit`](Self::limit) for an asset with the given precision, e.g. from
+    /// [`AssetRegistry::info`](crate::AssetRegistry::info). A price or size off the asset's
+    /// tick and lot grid is rejected here with
+    /// [`Error::InvalidPrecision`](crate::Error::InvalidPrecision) rather than by the
+    /// exchange; [`rounded`](Self::rounded) fixes them up instead.
+    pub fn limit_checked(
+        precision: &OrderPrecision,
+        asset: u32,
+        is_buy: bool,
+        limit_px: Decimal,
+        size: Decimal,
+        tif: Tif,
+    ) -> crate::Result<Self> {
+        Self::limit(asset, is_buy, limit_px, size, tif).rounded(precision, RoundingMode::Strict)
+    }
+
+    /// [`trigger`](Self::trigger) for an asset with the given precision, validated like
+    /// [`limit_checked`](Self::limit_checked).
+    pub fn trigger_checked(
+        precision: &OrderPrecision,
+        asset: u32,
+        is_buy: bool,
+        limit_px: Decimal,
+        size: Decimal,
+        trigger: TriggerOrderType,
+    ) -> crate::Result<Self> {
+        let TriggerOrderType {
+            trigger_px,
+            is_market,
+            tpsl,
+        } = trigger;
+        Self::trigger(asset, is_buy, limit_px, size, trigger_px, is_market, tpsl)
+            .rounded(precision, RoundingMode::Strict)
+    }
+
+    pub fn with_reduce_only(mut self, reduce_only: bool) -> Self {
+        self.reduce_only = reduce_only;
+        self
+    }
+
+    pub fn with_client_order_id(mut self, client_order_id: impl Into<Cloid>) -> Self {
+        self.client_order_id = Some(client_order_id.into());
+        self
+    }
+
+    /// Round the limit price, trigger price and size onto the asset's tick and lot grid.
+    ///
+    /// With [`RoundingMode::Strict`] nothing is changed and invalid values are rejected with
+    /// [`Error::InvalidPrecision`](crate::Error::InvalidPrecision) before they reach the exchange.
+    pub fn rounded(
+        mut self,
+        precision: &OrderPrecision,
+        mode: RoundingMode,
+    ) -> crate::Result<Self> {
+        self.limit_px = precision.round_price(self.limit_px, self.is_buy, mode)?;
+        self.size = precision.round_size(self.size, mode)?;
+        if let OrderType::Trigger(trigger) = &mut self.order_type {
+            trigger.trigger_px = precision.round_price(trigger.trigger_px, self.is_buy, mode)?;
+        }
+        Ok(self)
+    }
 }
 
 /// Builder info for order attribution.
@@ -196,7 +301,11 @@ pub enum Grouping {
 ///
 /// Key order for hashing: type (from wrapper), orders, grouping, builder.
 #[derive(Deserialize, Debug, Clone, L1Action)]
-#[action(action_type = "order", payload_key = "order")]
+#[action(
+    action_type = "order",
+    payload_key = "order",
+    response = "crate::ExchangeDataStatuses"
+)]
 #[serde(rename_all = "camelCase")]
 pub struct BatchOrder {
     /// Order wires
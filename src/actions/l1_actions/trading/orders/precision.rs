@@ -0,0 +1,367 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::{
+    info::{AssetInfo, AssetKind},
+    prelude::{Error, Result},
+    types::{AssetMeta, TokenInfo},
+};
+
+/// Maximum significant figures allowed in a non-integer price.
+pub const MAX_PRICE_SIG_FIGS: u32 = 5;
+/// Maximum price decimals for perps, before subtracting `sz_decimals`.
+pub const MAX_PERP_PRICE_DECIMALS: u32 = 6;
+/// Maximum price decimals for spot, before subtracting `sz_decimals`.
+pub const MAX_SPOT_PRICE_DECIMALS: u32 = 8;
+
+/// How to bring a price or size onto the exchange's grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    /// Round to the nearest valid value (midpoint away from zero).
+    #[default]
+    Nearest,
+    /// Round away from the book: buys round down, sells round up. Sizes round toward zero.
+    Passive,
+    /// Reject values that are not already valid.
+    Strict,
+}
+
+/// Tick and lot size rules for one asset.
+///
+/// Prices may have at most 5 significant figures and at most `6 - sz_decimals` (perps) or
+/// `8 - sz_decimals` (spot) decimals. Integer prices are always accepted regardless of
+/// significant figures. Sizes may have at most `sz_decimals` decimals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrderPrecision {
+    pub kind: AssetKind,
+    pub sz_decimals: u32,
+}
+
+impl OrderPrecision {
+    pub fn perp(sz_decimals: u32) -> Self {
+        Self {
+            kind: AssetKind::Perp,
+            sz_decimals,
+        }
+    }
+
+    pub fn spot(sz_decimals: u32) -> Self {
+        Self {
+            kind: AssetKind::Spot,
+            sz_decimals,
+        }
+    }
+
+    /// Maximum number of decimals a price may carry.
+    pub fn max_price_decimals(&self) -> u32 {
+        let max = match self.kind {
+            AssetKind::Perp => MAX_PERP_PRICE_DECIMALS,
+            AssetKind::Spot => MAX_SPOT_PRICE_DECIMALS,
+        };
+        max.saturating_sub(self.sz_decimals)
+    }
+
+    /// Round a limit or trigger price. `is_buy` only matters for [`RoundingMode::Passive`].
+    pub fn round_price(&self, px: Decimal, is_buy: bool, mode: RoundingMode) -> Result<Decimal> {
+        if px <= Decimal::ZERO {
+            return Err(Error::InvalidPrecision(format!(
+                "price must be positive, got {px}"
+            )));
+        }
+        let px = px.normalize();
+        if px.fract().is_zero() {
+            return Ok(px);
+        }
+
+        let dp = self.price_decimals_for(px);
+        let strategy = match mode {
+            RoundingMode::Nearest => RoundingStrategy::MidpointAwayFromZero,
+            RoundingMode::Passive if is_buy => RoundingStrategy::ToNegativeInfinity,
+            RoundingMode::Passive => RoundingStrategy::ToPositiveInfinity,
+            RoundingMode::Strict => {
+                self.validate_price(px)?;
+                return Ok(px);
+            }
+        };
+        let rounded = px.round_dp_with_strategy(dp, strategy).normalize();
+        if rounded.is_zero() {
+            return Err(Error::InvalidPrecision(format!(
+                "price {px} rounds to zero with {dp} decimals"
+            )));
+        }
+        Ok(rounded)
+    }
+
+    /// Round an order size to `sz_decimals`. [`RoundingMode::Passive`] never rounds up.
+    pub fn round_size(&self, sz: Decimal, mode: RoundingMode) -> Result<Decimal> {
+        if sz <= Decimal::ZERO {
+            return Err(Error::InvalidPrecision(format!(
+                "size must be positive, got {sz}"
+            )));
+        }
+        let strategy = match mode {
+            RoundingMode::Nearest => RoundingStrategy::MidpointAwayFromZero,
+            RoundingMode::Passive => RoundingStrategy::ToZero,
+            RoundingMode::Strict => {
+                self.validate_size(sz)?;
+                return Ok(sz.normalize());
+            }
+        };
+        let rounded = sz
+            .round_dp_with_strategy(self.sz_decimals, strategy)
+            .normalize();
+        if rounded.is_zero() {
+            return Err(Error::InvalidPrecision(format!(
+                "size {sz} rounds to zero with {} decimals",
+                self.sz_decimals
+            )));
+        }
+        Ok(rounded)
+    }
+
+    /// Check a price against the significant-figure and max-decimals rules.
+    pub fn validate_price(&self, px: Decimal) -> Result<()> {
+        let px = px.normalize();
+        if px <= Decimal::ZERO {
+            return Err(Error::InvalidPrecision(format!(
+                "price must be positive, got {px}"
+            )));
+        }
+        if px.fract().is_zero() {
+            return Ok(());
+        }
+        let sig_figs = px.mantissa().unsigned_abs().to_string().len() as u32;
+        if sig_figs > MAX_PRICE_SIG_FIGS {
+            return Err(Error::InvalidPrecision(format!(
+                "price {px} has {sig_figs} significant figures, max is {MAX_PRICE_SIG_FIGS}"
+            )));
+        }
+        let max_decimals = self.max_price_decimals();
+        if px.scale() > max_decimals {
+            return Err(Error::InvalidPrecision(format!(
+                "price {px} has {} decimals, max is {max_decimals}",
+                px.scale()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check a size against `sz_decimals`.
+    pub fn validate_size(&self, sz: Decimal) -> Result<()> {
+        let sz = sz.normalize();
+        if sz <= Decimal::ZERO {
+            return Err(Error::InvalidPrecision(format!(
+                "size must be positive, got {sz}"
+            )));
+        }
+        if sz.scale() > self.sz_decimals {
+            return Err(Error::InvalidPrecision(format!(
+                "size {sz} has {} decimals, max is {}",
+                sz.scale(),
+                self.sz_decimals
+            )));
+        }
+        Ok(())
+    }
+
+    /// Decimals to keep for `px`: the tighter of the 5-sig-fig limit and the max-decimals limit.
+    fn price_decimals_for(&self, px: Decimal) -> u32 {
+        let integer_digits = px.trunc().mantissa().unsigned_abs().to_string().len() as i64;
+        let sig_fig_decimals = if px >= Decimal::ONE {
+            MAX_PRICE_SIG_FIGS as i64 - integer_digits
+        } else {
+            // Leading zeros after the decimal point don't count as significant.
+            let leading_zeros = px.scale() as i64 - px.mantissa().to_string().len() as i64;
+            MAX_PRICE_SIG_FIGS as i64 + leading_zeros
+        };
+        sig_fig_decimals.clamp(0, self.max_price_decimals() as i64) as u32
+    }
+}
+
+impl From<&AssetInfo> for OrderPrecision {
+    fn from(info: &AssetInfo) -> Self {
+        Self {
+            kind: info.kind,
+            sz_decimals: info.sz_decimals,
+        }
+    }
+}
+
+/// Precision for a perp from its universe entry.
+impl From<&AssetMeta> for OrderPrecision {
+    fn from(meta: &AssetMeta) -> Self {
+        Self::perp(meta.sz_decimals)
+    }
+}
+
+/// Precision for a spot pair from its base token.
+impl From<&TokenInfo> for OrderPrecision {
+    fn from(token: &TokenInfo) -> Self {
+        Self::spot(token.sz_decimals as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn max_price_decimals_differ_for_perp_and_spot() {
+        assert_eq!(OrderPrecision::perp(4).max_price_decimals(), 2);
+        assert_eq!(OrderPrecision::spot(4).max_price_decimals(), 4);
+        assert_eq!(OrderPrecision::perp(7).max_price_decimals(), 0);
+    }
+
+    #[test]
+    fn nearest_rounds_to_five_sig_figs() {
+        let btc = OrderPrecision::perp(5);
+        assert_eq!(
+            btc.round_price(dec!(1234.567), true, RoundingMode::Nearest)
+                .unwrap(),
+            dec!(1234.6)
+        );
+        // Integer prices are always valid, even above 5 significant figures.
+        assert_eq!(
+            btc.round_price(dec!(113377), true, RoundingMode::Nearest)
+                .unwrap(),
+            dec!(113377)
+        );
+        assert_eq!(
+            btc.round_price(dec!(113377.4), true, RoundingMode::Nearest)
+                .unwrap(),
+            dec!(113377)
+        );
+    }
+
+    #[test]
+    fn nearest_respects_max_decimals() {
+        // Perp with szDecimals 2 allows 4 decimals: 0.0123456 -> 0.0123.
+        let perp = OrderPrecision::perp(2);
+        assert_eq!(
+            perp.round_price(dec!(0.0123456), true, RoundingMode::Nearest)
+                .unwrap(),
+            dec!(0.0123)
+        );
+        // Spot with the same szDecimals allows 6 decimals: 5 sig figs win.
+        let spot = OrderPrecision::spot(2);
+        assert_eq!(
+            spot.round_price(dec!(0.0123456), true, RoundingMode::Nearest)
+                .unwrap(),
+            dec!(0.012346)
+        );
+    }
+
+    #[test]
+    fn passive_rounds_away_from_the_book() {
+        let eth = OrderPrecision::perp(4);
+        assert_eq!(
+            eth.round_price(dec!(3151.27), true, RoundingMode::Passive)
+                .unwrap(),
+            dec!(3151.2)
+        );
+        assert_eq!(
+            eth.round_price(dec!(3151.21), false, RoundingMode::Passive)
+                .unwrap(),
+            dec!(3151.3)
+        );
+        assert_eq!(
+            eth.round_size(dec!(0.12349), RoundingMode::Passive)
+                .unwrap(),
+            dec!(0.1234)
+        );
+    }
+
+    #[test]
+    fn strict_rejects_invalid_values() {
+        let eth = OrderPrecision::perp(4);
+        assert_eq!(
+            eth.round_price(dec!(3151.2), true, RoundingMode::Strict)
+                .unwrap(),
+            dec!(3151.2)
+        );
+        assert!(matches!(
+            eth.round_price(dec!(3151.25), true, RoundingMode::Strict),
+            Err(Error::InvalidPrecision(_))
+        ));
+        assert!(matches!(
+            eth.round_price(dec!(0.001), true, RoundingMode::Strict),
+            Err(Error::InvalidPrecision(_))
+        ));
+        assert!(matches!(
+            eth.round_size(dec!(0.12345), RoundingMode::Strict),
+            Err(Error::InvalidPrecision(_))
+        ));
+    }
+
+    #[test]
+    fn sizes_round_to_sz_decimals() {
+        let purr = OrderPrecision::spot(0);
+        assert_eq!(
+            purr.round_size(dec!(11.5), RoundingMode::Nearest).unwrap(),
+            dec!(12)
+        );
+        assert!(matches!(
+            purr.round_size(dec!(0.4), RoundingMode::Passive),
+            Err(Error::InvalidPrecision(_))
+        ));
+    }
+
+    #[test]
+    fn order_wire_rounds_price_trigger_and_size() {
+        use crate::actions::{OrderType, OrderWire, TpSl};
+
+        let eth = OrderPrecision::perp(4);
+        let order = OrderWire::trigger(
+            4,
+            false,
+            dec!(3000.04),
+            dec!(0.123456),
+            dec!(3100.06),
+            true,
+            TpSl::Sl,
+        )
+        .rounded(&eth, RoundingMode::Nearest)
+        .unwrap();
+
+        assert_eq!(order.limit_px, dec!(3000));
+        assert_eq!(order.size, dec!(0.1235));
+        let OrderType::Trigger(trigger) = order.order_type else {
+            panic!("expected trigger order");
+        };
+        assert_eq!(trigger.trigger_px, dec!(3100.1));
+    }
+
+    #[test]
+    fn checked_constructors_reject_off_grid_orders() {
+        use crate::actions::{OrderWire, Tif, TpSl, TriggerOrderType};
+
+        let eth = OrderPrecision::perp(4);
+        let order =
+            OrderWire::limit_checked(&eth, 4, true, dec!(3000.5), dec!(0.1234), Tif::Gtc).unwrap();
+        assert_eq!(order.limit_px, dec!(3000.5));
+        assert_eq!(order.size, dec!(0.1234));
+        assert!(matches!(
+            OrderWire::limit_checked(&eth, 4, true, dec!(3000.55), dec!(0.1), Tif::Gtc),
+            Err(Error::InvalidPrecision(_))
+        ));
+        assert!(matches!(
+            OrderWire::limit_checked(&eth, 4, true, dec!(3000), dec!(0.12345), Tif::Gtc),
+            Err(Error::InvalidPrecision(_))
+        ));
+
+        let trigger = |trigger_px| TriggerOrderType {
+            trigger_px,
+            is_market: true,
+            tpsl: TpSl::Sl,
+        };
+        let stop = |trigger_px| {
+            OrderWire::trigger_checked(&eth, 4, false, dec!(2900), dec!(1), trigger(trigger_px))
+        };
+        assert!(stop(dec!(2950)).is_ok());
+        assert!(matches!(
+            stop(dec!(2950.01)),
+            Err(Error::InvalidPrecision(_))
+        ));
+    }
+}
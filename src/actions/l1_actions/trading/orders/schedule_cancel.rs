@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
 #[serde(rename_all = "camelCase")]
 pub struct ScheduleCancel {
-    /// UTC milliseconds timestamp for cancellation (optional - if None, cancels immediately)
+    /// UTC milliseconds timestamp for cancellation (optional - if None, removes the scheduled cancel)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time: Option<u64>,
     #[serde(skip_serializing)]
@@ -24,7 +24,7 @@ impl ScheduleCancel {
         }
     }
 
-    /// Cancel all orders immediately.
+    /// Remove the scheduled cancel, if any.
     pub fn now() -> Self {
         Self {
             time: None,
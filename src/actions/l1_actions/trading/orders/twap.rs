@@ -0,0 +1,87 @@
+use hl_rs_derive::L1Action;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::order::{deserialize_decimal_wire, serialize_decimal_wire};
+
+/// Native TWAP order: the exchange slices `size` into suborders over `minutes`.
+///
+/// Wire shape: `{ "type": "twapOrder", "twap": { "a", "b", "s", "r", "m", "t" } }`.
+#[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
+#[action(payload_key = "twap", response = "crate::TwapOrderResponse")]
+pub struct TwapOrder {
+    /// Asset index (wire `a`)
+    #[serde(rename = "a")]
+    pub asset: u32,
+    /// Buy side (wire `b`)
+    #[serde(rename = "b")]
+    pub is_buy: bool,
+    /// Total size (wire `s`)
+    #[serde(
+        rename = "s",
+        serialize_with = "serialize_decimal_wire",
+        deserialize_with = "deserialize_decimal_wire"
+    )]
+    pub size: Decimal,
+    /// Reduce only (wire `r`)
+    #[serde(rename = "r")]
+    pub reduce_only: bool,
+    /// Duration in minutes, 5 to 1440 (wire `m`)
+    #[serde(rename = "m")]
+    pub minutes: u32,
+    /// Randomize suborder timing (wire `t`)
+    #[serde(rename = "t")]
+    pub randomize: bool,
+    #[serde(skip_serializing)]
+    pub nonce: Option<u64>,
+}
+
+impl TwapOrder {
+    pub fn new(asset: u32, is_buy: bool, size: Decimal, minutes: u32) -> Self {
+        Self {
+            asset,
+            is_buy,
+            size,
+            reduce_only: false,
+            minutes,
+            randomize: false,
+            nonce: None,
+        }
+    }
+
+    pub fn with_reduce_only(mut self, reduce_only: bool) -> Self {
+        self.reduce_only = reduce_only;
+        self
+    }
+
+    pub fn with_randomize(mut self, randomize: bool) -> Self {
+        self.randomize = randomize;
+        self
+    }
+}
+
+/// Cancel a running TWAP order.
+///
+/// Wire shape: `{ "type": "twapCancel", "a", "t" }`.
+#[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
+#[action(response = "crate::TwapCancelResponse")]
+pub struct TwapCancel {
+    /// Asset index (wire `a`)
+    #[serde(rename = "a")]
+    pub asset: u32,
+    /// TWAP id returned by [`TwapOrder`] (wire `t`)
+    #[serde(rename = "t")]
+    pub twap_id: u64,
+    #[serde(skip_serializing)]
+    pub nonce: Option<u64>,
+}
+
+impl TwapCancel {
+    pub fn new(asset: u32, twap_id: u64) -> Self {
+        Self {
+            asset,
+            twap_id,
+            nonce: None,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use alloy::primitives::Address;
+use hl_rs_derive::L1Action;
+use serde::{Deserialize, Serialize};
+
+use crate::actions::{serialization::ser_lowercase, MicroUsd};
+
+/// Distribute USD from a vault the signer leads to its followers, pro rata.
+///
+/// At most the vault's `maxDistributable` (see `InfoClient::vault_details`) can be paid out.
+#[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultDistribute {
+    /// Vault address
+    #[serde(serialize_with = "ser_lowercase")]
+    pub vault_address: Address,
+    /// USD amount in micro-USD
+    pub usd: MicroUsd,
+    #[serde(skip_serializing)]
+    pub nonce: Option<u64>,
+}
+
+impl VaultDistribute {
+    pub fn new(vault_address: Address, usd: MicroUsd) -> Self {
+        Self {
+            vault_address,
+            usd,
+            nonce: None,
+        }
+    }
+}
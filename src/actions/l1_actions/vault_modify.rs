@@ -0,0 +1,41 @@
+use alloy::primitives::Address;
+use hl_rs_derive::L1Action;
+use serde::{Deserialize, Serialize};
+
+use crate::actions::serialization::ser_lowercase;
+
+/// Change the settings of a vault the signer leads. `None` leaves a setting unchanged.
+#[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultModify {
+    /// Vault address
+    #[serde(serialize_with = "ser_lowercase")]
+    pub vault_address: Address,
+    /// Whether followers may deposit
+    pub allow_deposits: Option<bool>,
+    /// Whether the leader's positions are closed when a follower withdraws
+    pub always_close_on_withdraw: Option<bool>,
+    #[serde(skip_serializing)]
+    pub nonce: Option<u64>,
+}
+
+impl VaultModify {
+    pub fn new(vault_address: Address) -> Self {
+        Self {
+            vault_address,
+            allow_deposits: None,
+            always_close_on_withdraw: None,
+            nonce: None,
+        }
+    }
+
+    pub fn with_allow_deposits(mut self, allow_deposits: bool) -> Self {
+        self.allow_deposits = Some(allow_deposits);
+        self
+    }
+
+    pub fn with_always_close_on_withdraw(mut self, always_close_on_withdraw: bool) -> Self {
+        self.always_close_on_withdraw = Some(always_close_on_withdraw);
+        self
+    }
+}
@@ -2,7 +2,7 @@ use alloy::primitives::Address;
 use hl_rs_derive::L1Action;
 use serde::{Deserialize, Serialize};
 
-use crate::actions::serialization::ser_lowercase;
+use crate::actions::{serialization::ser_lowercase, MicroUsd};
 
 /// Transfer USD to/from a vault.
 #[derive(Serialize, Deserialize, Debug, Clone, L1Action)]
@@ -13,15 +13,15 @@ pub struct VaultTransfer {
     pub vault_address: Address,
     /// True to deposit to vault, false to withdraw
     pub is_deposit: bool,
-    /// USD amount (raw integer, not decimal)
-    pub usd: i64,
+    /// USD amount in micro-USD
+    pub usd: MicroUsd,
     #[serde(skip_serializing)]
     pub nonce: Option<u64>,
 }
 
 impl VaultTransfer {
     /// Deposit USD to a vault
-    pub fn deposit(vault_address: Address, usd: i64) -> Self {
+    pub fn deposit(vault_address: Address, usd: MicroUsd) -> Self {
         Self {
             vault_address,
             is_deposit: true,
@@ -31,7 +31,7 @@ impl VaultTransfer {
     }
 
     /// Withdraw USD from a vault
-    pub fn withdraw(vault_address: Address, usd: i64) -> Self {
+    pub fn withdraw(vault_address: Address, usd: MicroUsd) -> Self {
         Self {
             vault_address,
             is_deposit: false,
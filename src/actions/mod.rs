@@ -6,18 +6,27 @@
 //! - `Action` trait: unified interface auto-implemented for both
 //! - `SignedAction<T>`: strongly typed signed action ready to submit
 
+use alloy::primitives::B256;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 
+use crate::Error;
+
+mod amounts;
+mod cloid;
 mod core;
 mod l1_actions;
+mod multi_sig;
 mod serialization;
 mod signing;
 mod traits;
 mod user_signed_actions;
 
+pub use amounts::{MicroUsd, Wei};
+pub use cloid::Cloid;
 pub use core::{PreparedAction, SignedAction, SignedActionKind};
 pub use l1_actions::*;
+pub use multi_sig::{MultiSigAction, MultiSigBuilder, PartialSignature};
 pub use traits::{Action, L1Action, UserSignedAction};
 pub use user_signed_actions::*;
 
@@ -47,6 +56,20 @@ macro_rules! impl_action_kind {
             },
         }
 
+        impl ActionKind {
+            /// Signing hash of the wrapped action, as [`Action::signing_hash`].
+            ///
+            /// Errors for [`ActionKind::Unknown`], whose hashing rules are not known.
+            pub fn signing_hash(&self, meta: &SigningMeta) -> Result<B256, Error> {
+                match self {
+                    $(ActionKind::$action(action) => action.signing_hash(meta),)*
+                    ActionKind::Unknown { action_type, .. } => Err(Error::GenericParse(format!(
+                        "cannot compute signing hash of unknown action type `{action_type}`"
+                    ))),
+                }
+            }
+        }
+
         /// Dispatch a JSON action object to the appropriate `ActionKind` variant.
         ///
         /// This is generated by the `impl_action_kind!` macro and used by
@@ -116,6 +139,8 @@ impl_action_kind![
     SendAsset,
     UserDexAbstraction,
     TokenDelegate,
+    CDeposit,
+    CWithdraw,
     ConvertToMultiSigUser,
     ApproveAgent,
     ApproveBuilderFee,
@@ -127,17 +152,23 @@ impl_action_kind![
     SubAccountTransfer,
     SubAccountSpotTransfer,
     VaultTransfer,
+    CreateVault,
+    VaultModify,
+    VaultDistribute,
+    ClaimRewards,
     AgentEnableDexAbstraction,
     FinalizeEvmContract,
     // Trading actions
     UpdateLeverage,
     UpdateIsolatedMargin,
     ScheduleCancel,
+    TwapCancel,
     // Sum types: order actions (action_type = "order"/"cancel"/etc, payload_key varies)
     BatchOrder,
     BatchCancel,
     CancelByCloid,
     BatchModify,
+    TwapOrder,
     // Sum types: perpDeploy (action_type = "perpDeploy", payload_key varies)
     RegisterAsset,
     SetOpenInterestCaps,
@@ -0,0 +1,651 @@
+// ============================================================================
+// Multi-sig Actions
+// ============================================================================
+
+use std::fmt;
+
+use alloy::{
+    dyn_abi::{DynSolType, DynSolValue},
+    primitives::{keccak256, Address, B256},
+};
+use alloy_signer::Signature;
+use serde::{
+    de::{MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::Value;
+
+use crate::{
+    actions::{
+        serialization::{deserialize_sig, serialize_sig},
+        traits::{Action, UserSignedAction},
+        ActionKind, L1ActionWrapper, MultiSigSigners, SigningMeta,
+    },
+    nonce, ActionSigner, Error, SigningChain, ToAbiValue,
+};
+
+/// `multiSig` action: `action` authorized by signers of `multi_sig_user` and submitted by
+/// `outer_signer`, one of those signers.
+///
+/// Built with [`MultiSigBuilder`]. The outer signature is produced like any other action, by
+/// `ExchangeClient::send_action` with the outer signer's key, and the response is the inner
+/// action's response.
+#[derive(Debug, Clone)]
+pub struct MultiSigAction<A: Action> {
+    pub multi_sig_user: Address,
+    pub outer_signer: Address,
+    pub action: A,
+    /// Signatures of the authorized users over the inner action, in collection order.
+    pub signatures: Vec<Signature>,
+    pub nonce: Option<u64>,
+    signing_chain: SigningChain,
+}
+
+/// EIP-712 envelope signed by the outer signer.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SendMultiSig {
+    multi_sig_action_hash: B256,
+    nonce: u64,
+}
+
+impl UserSignedAction for SendMultiSig {
+    const ACTION_TYPE: &'static str = "sendMultiSig";
+
+    fn struct_hash(&self, chain: &SigningChain) -> Result<B256, Error> {
+        let type_hash = keccak256(
+            "HyperliquidTransaction:SendMultiSig(string hyperliquidChain,bytes32 multiSigActionHash,uint64 nonce)",
+        );
+        let values = vec![
+            DynSolValue::FixedBytes(type_hash, 32),
+            chain
+                .get_hyperliquid_chain()
+                .to_abi_value(&DynSolType::String)?,
+            DynSolValue::FixedBytes(self.multi_sig_action_hash, 32),
+            self.nonce.to_abi_value(&DynSolType::Uint(64))?,
+        ];
+        Ok(keccak256(DynSolValue::Tuple(values).abi_encode()))
+    }
+}
+
+impl<A: Action + Clone> Action for MultiSigAction<A> {
+    const ACTION_TYPE: &'static str = "multiSig";
+    const PAYLOAD_KEY: &'static str = "multiSig";
+
+    type Response = A::Response;
+
+    /// Hash of the `SendMultiSig` envelope, which commits to the msgpack hash of the whole
+    /// action (inner action and signatures) plus nonce, vault and expiry.
+    fn signing_hash(&self, meta: &SigningMeta) -> Result<B256, Error> {
+        let multi_sig_action_hash = crate::actions::compute_l1_hash(
+            self,
+            meta.nonce,
+            meta.vault_address,
+            meta.expires_after,
+        )?;
+        SendMultiSig {
+            multi_sig_action_hash,
+            nonce: meta.nonce,
+        }
+        .eip712_signing_hash(meta.signing_chain)
+    }
+
+    fn multisig_signing_hash(
+        &self,
+        _meta: &SigningMeta,
+        _payload_multi_sig_user: Address,
+        _outer_signer: Address,
+    ) -> Result<B256, Error> {
+        Err(Error::InvalidMultiSig(
+            "multiSig actions cannot be nested".to_string(),
+        ))
+    }
+
+    fn nonce(&self) -> Option<u64> {
+        self.nonce
+    }
+
+    fn extract_action_kind(&self) -> ActionKind {
+        let mut raw = serde_json::to_value(self).unwrap_or_default();
+        if let Some(obj) = raw.as_object_mut() {
+            obj.insert("type".to_string(), Value::from(Self::ACTION_TYPE));
+        }
+        ActionKind::Unknown {
+            action_type: Self::ACTION_TYPE.to_string(),
+            raw,
+        }
+    }
+
+    fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+}
+
+/// Serializes the action body without its `type` tag, which is also the msgpack preimage of
+/// the multi-sig action hash: `{signatureChainId, signatures, payload}`.
+impl<A: Action + Clone> Serialize for MultiSigAction<A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let signatures: Vec<_> = self.signatures.iter().map(MultiSigSignature).collect();
+        let payload = MultiSigPayload {
+            multi_sig_user: self.multi_sig_user,
+            outer_signer: self.outer_signer,
+            action: InnerAction {
+                action: &self.action,
+                signing_chain: &self.signing_chain,
+            },
+        };
+
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry(
+            "signatureChainId",
+            &format!("0x{:x}", self.signing_chain.get_signature_chain_id()),
+        )?;
+        map.serialize_entry("signatures", &signatures)?;
+        map.serialize_entry("payload", &payload)?;
+        map.end()
+    }
+}
+
+struct MultiSigPayload<'a, A: Action> {
+    multi_sig_user: Address,
+    outer_signer: Address,
+    action: InnerAction<'a, A>,
+}
+
+impl<A: Action> Serialize for MultiSigPayload<'_, A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry(
+            "multiSigUser",
+            &self.multi_sig_user.to_string().to_lowercase(),
+        )?;
+        map.serialize_entry("outerSigner", &self.outer_signer.to_string().to_lowercase())?;
+        map.serialize_entry("action", &self.action)?;
+        map.end()
+    }
+}
+
+/// Inner signature as the Python SDK encodes it: `r` and `s` as unpadded hex. The encoding
+/// matters because signatures are part of the hashed payload.
+struct MultiSigSignature<'a>(&'a Signature);
+
+impl Serialize for MultiSigSignature<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("r", &format!("0x{:x}", self.0.r()))?;
+        map.serialize_entry("s", &format!("0x{:x}", self.0.s()))?;
+        map.serialize_entry("v", &(27 + self.0.v() as u64))?;
+        map.end()
+    }
+}
+
+/// Inner action with its `type` tag first. User-signed actions also carry
+/// `signatureChainId` and `hyperliquidChain`, and `time` instead of `nonce` where applicable.
+struct InnerAction<'a, A: Action> {
+    action: &'a A,
+    signing_chain: &'a SigningChain,
+}
+
+impl<A: Action> Serialize for InnerAction<'_, A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::Error as _;
+
+        if !A::is_user_signed() {
+            return L1ActionWrapper {
+                action: self.action,
+            }
+            .serialize(serializer);
+        }
+
+        // Field order is part of the msgpack hash, so walk the fields in declaration order
+        // rather than through `serde_json::Value`, whose maps are sorted.
+        let json = serde_json::to_string(self.action).map_err(S::Error::custom)?;
+        let OrderedFields(fields) = serde_json::from_str(&json).map_err(S::Error::custom)?;
+
+        let mut map = serializer.serialize_map(Some(fields.len() + 3))?;
+        map.serialize_entry("type", A::ACTION_TYPE)?;
+        map.serialize_entry(
+            "signatureChainId",
+            &format!("0x{:x}", self.signing_chain.get_signature_chain_id()),
+        )?;
+        map.serialize_entry(
+            "hyperliquidChain",
+            &self.signing_chain.get_hyperliquid_chain(),
+        )?;
+        for (key, value) in &fields {
+            let key = if key == "nonce" && A::uses_time() {
+                "time"
+            } else {
+                key
+            };
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Top-level fields of a JSON object in document order.
+struct OrderedFields(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for OrderedFields {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldsVisitor;
+
+        impl<'de> Visitor<'de> for FieldsVisitor {
+            type Value = OrderedFields;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an action object")
+            }
+
+            fn visit_map<M: MapAccess<'de>>(self, mut access: M) -> Result<Self::Value, M::Error> {
+                let mut fields = Vec::new();
+                while let Some(entry) = access.next_entry::<String, Value>()? {
+                    fields.push(entry);
+                }
+                Ok(OrderedFields(fields))
+            }
+        }
+
+        deserializer.deserialize_map(FieldsVisitor)
+    }
+}
+
+/// One authorized user's signature over a multi-sig inner action.
+///
+/// Serializes to JSON so co-signers can sign offline and send their part to the outer signer:
+/// ```json
+/// {"signer": "0x...", "signingHash": "0x...", "signature": {"r": "0x...", "s": "0x...", "v": 27}}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialSignature {
+    pub signer: Address,
+    /// Hash that was signed, from [`MultiSigBuilder::signing_hash`].
+    pub signing_hash: B256,
+    #[serde(serialize_with = "serialize_sig", deserialize_with = "deserialize_sig")]
+    pub signature: Signature,
+}
+
+impl PartialSignature {
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string(self).map_err(|e| Error::SerializationFailure(e.to_string()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).map_err(|e| Error::JsonParse(e.to_string()))
+    }
+
+    /// Check that `signature` over `signing_hash` recovers to `signer`.
+    pub fn verify(&self) -> Result<(), Error> {
+        let recovered = self
+            .signature
+            .recover_address_from_prehash(&self.signing_hash)
+            .map_err(|e| Error::InvalidMultiSig(e.to_string()))?;
+        if recovered != self.signer {
+            return Err(Error::InvalidMultiSig(format!(
+                "signature recovers to {recovered}, expected {}",
+                self.signer
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Collects the inner signatures of a multi-sig action and checks them against the user's
+/// [`MultiSigSigners`] config.
+///
+/// Every co-signer builds the same builder (same action, nonce, users and chain) and calls
+/// [`MultiSigBuilder::sign_partial`]; the outer signer gathers the results with
+/// [`MultiSigBuilder::add_signature`], then sends [`MultiSigBuilder::build`].
+///
+/// # Example
+/// ```no_run
+/// use alloy::primitives::Address;
+/// use alloy::signers::local::PrivateKeySigner;
+/// use hl_rs::{BaseUrl, ExchangeClient, PartialSignature, UsdSend};
+/// use rust_decimal_macros::dec;
+///
+/// # async fn run(leader: PrivateKeySigner, co_signer_json: &str) -> Result<(), hl_rs::Error> {
+/// let multi_sig_user: Address = "0x0000000000000000000000000000000000000001".parse().unwrap();
+/// let client = ExchangeClient::new(BaseUrl::Testnet).with_signer(leader.clone());
+///
+/// let mut builder = client.multi_sig(multi_sig_user, UsdSend::new(Address::ZERO, dec!(1)))?;
+/// builder.sign(&leader).await?;
+/// builder.add_signature(PartialSignature::from_json(co_signer_json)?)?;
+///
+/// let config = client
+///     .info()
+///     .user_to_multi_sig_signers(&multi_sig_user)
+///     .await?
+///     .expect("multi-sig user");
+/// client.send_action(builder.build(&config)?).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiSigBuilder<A: Action> {
+    multi_sig_user: Address,
+    outer_signer: Address,
+    action: A,
+    nonce: u64,
+    signing_chain: SigningChain,
+    signing_hash: B256,
+    signatures: Vec<PartialSignature>,
+}
+
+impl<A: Action + Clone> MultiSigBuilder<A> {
+    /// Start collecting signatures for `action`.
+    ///
+    /// The nonce is taken from the action, or the [shared](nonce::shared) nonce source when
+    /// unset; the outer action must be sent with the same nonce. `expires_after` must match
+    /// the outer signer's client.
+    pub fn new(
+        action: A,
+        multi_sig_user: Address,
+        outer_signer: Address,
+        signing_chain: &SigningChain,
+        expires_after: Option<u64>,
+    ) -> Result<Self, Error> {
+        let nonce = match action.nonce() {
+            Some(nonce) => nonce,
+            None => nonce::shared().next_nonce()?,
+        };
+        let action = action.with_nonce(nonce);
+
+        let meta = SigningMeta {
+            nonce,
+            vault_address: None,
+            expires_after,
+            signing_chain,
+        };
+        let signing_hash = action.multisig_signing_hash(&meta, multi_sig_user, outer_signer)?;
+
+        Ok(Self {
+            multi_sig_user,
+            outer_signer,
+            action,
+            nonce,
+            signing_chain: signing_chain.clone(),
+            signing_hash,
+            signatures: Vec::new(),
+        })
+    }
+
+    /// Hash each authorized user signs.
+    pub fn signing_hash(&self) -> B256 {
+        self.signing_hash
+    }
+
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Signers collected so far.
+    pub fn signers(&self) -> impl Iterator<Item = Address> + '_ {
+        self.signatures.iter().map(|partial| partial.signer)
+    }
+
+    /// Sign the inner action without adding the signature, e.g. on a co-signer's machine.
+    pub async fn sign_partial<S: ActionSigner + ?Sized>(
+        &self,
+        signer: &S,
+    ) -> Result<PartialSignature, Error> {
+        Ok(PartialSignature {
+            signer: signer.address(),
+            signing_hash: self.signing_hash,
+            signature: signer.sign_hash(&self.signing_hash).await?,
+        })
+    }
+
+    /// Sign the inner action and add the signature.
+    pub async fn sign<S: ActionSigner + ?Sized>(&mut self, signer: &S) -> Result<(), Error> {
+        let partial = self.sign_partial(signer).await?;
+        self.add_signature(partial)
+    }
+
+    /// Add a co-signer's signature after checking it is over this action and recovers to
+    /// its signer.
+    pub fn add_signature(&mut self, partial: PartialSignature) -> Result<(), Error> {
+        if partial.signing_hash != self.signing_hash {
+            return Err(Error::InvalidMultiSig(format!(
+                "{} signed {}, expected {}",
+                partial.signer, partial.signing_hash, self.signing_hash
+            )));
+        }
+        partial.verify()?;
+        if self.signers().any(|signer| signer == partial.signer) {
+            return Err(Error::InvalidMultiSig(format!(
+                "duplicate signature from {}",
+                partial.signer
+            )));
+        }
+        self.signatures.push(partial);
+        Ok(())
+    }
+
+    /// Build the action once every signer is authorized and the threshold is met.
+    pub fn build(self, config: &MultiSigSigners) -> Result<MultiSigAction<A>, Error> {
+        if let Some(signer) = self
+            .signers()
+            .find(|signer| !config.authorized_users.contains(signer))
+        {
+            return Err(Error::InvalidMultiSig(format!(
+                "{signer} is not an authorized user of {}",
+                self.multi_sig_user
+            )));
+        }
+        if self.signatures.len() < config.threshold as usize {
+            return Err(Error::MultiSigThresholdNotMet {
+                threshold: config.threshold,
+                collected: self.signatures.len(),
+            });
+        }
+
+        Ok(MultiSigAction {
+            multi_sig_user: self.multi_sig_user,
+            outer_signer: self.outer_signer,
+            action: self.action,
+            signatures: self
+                .signatures
+                .into_iter()
+                .map(|partial| partial.signature)
+                .collect(),
+            nonce: Some(self.nonce),
+            signing_chain: self.signing_chain,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::signers::local::PrivateKeySigner;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    use super::*;
+    use crate::actions::{PreparedAction, ToggleBigBlocks, UsdSend};
+
+    const NONCE: u64 = 1_700_000_000_000;
+
+    fn wallets() -> Vec<PrivateKeySigner> {
+        (1..=3)
+            .map(|i| PrivateKeySigner::from_bytes(&B256::repeat_byte(i)).unwrap())
+            .collect()
+    }
+
+    fn config(wallets: &[PrivateKeySigner], threshold: u32) -> MultiSigSigners {
+        let mut authorized_users: Vec<_> = wallets.iter().map(|w| w.address()).collect();
+        authorized_users.sort();
+        MultiSigSigners {
+            authorized_users,
+            threshold,
+        }
+    }
+
+    fn usd_send_builder(leader: Address) -> MultiSigBuilder<UsdSend> {
+        let action = UsdSend::new(Address::repeat_byte(0x33), dec!(10)).with_nonce(NONCE);
+        MultiSigBuilder::new(
+            action,
+            Address::repeat_byte(0x11),
+            leader,
+            &SigningChain::Testnet,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn builds_once_threshold_is_met() {
+        let wallets = wallets();
+        let mut builder = usd_send_builder(wallets[0].address());
+
+        builder.sign(&wallets[0]).await.unwrap();
+        let err = builder.clone().build(&config(&wallets, 2)).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::MultiSigThresholdNotMet {
+                threshold: 2,
+                collected: 1
+            }
+        ));
+
+        builder.sign(&wallets[1]).await.unwrap();
+        let action = builder.build(&config(&wallets, 2)).unwrap();
+        assert_eq!(action.signatures.len(), 2);
+        assert_eq!(action.nonce, Some(NONCE));
+    }
+
+    #[tokio::test]
+    async fn partial_signatures_round_trip_through_json() {
+        let wallets = wallets();
+        let builder = usd_send_builder(wallets[0].address());
+        let partial = builder.sign_partial(&wallets[2]).await.unwrap();
+
+        let json = partial.to_json().unwrap();
+        let parsed = PartialSignature::from_json(&json).unwrap();
+        assert_eq!(parsed, partial);
+
+        let mut leader = usd_send_builder(wallets[0].address());
+        leader.add_signature(parsed).unwrap();
+        assert_eq!(leader.signers().collect::<Vec<_>>(), [wallets[2].address()]);
+    }
+
+    #[tokio::test]
+    async fn rejects_foreign_duplicate_and_unauthorized_signatures() {
+        let wallets = wallets();
+        let mut builder = usd_send_builder(wallets[0].address());
+
+        // Signed for a different outer signer, so a different hash.
+        let other = usd_send_builder(wallets[1].address());
+        let foreign = other.sign_partial(&wallets[1]).await.unwrap();
+        assert!(matches!(
+            builder.add_signature(foreign),
+            Err(Error::InvalidMultiSig(_))
+        ));
+
+        // Claims the wrong signer.
+        let mut forged = builder.sign_partial(&wallets[1]).await.unwrap();
+        forged.signer = wallets[2].address();
+        assert!(matches!(
+            builder.add_signature(forged),
+            Err(Error::InvalidMultiSig(_))
+        ));
+
+        builder.sign(&wallets[1]).await.unwrap();
+        assert!(matches!(
+            builder.sign(&wallets[1]).await,
+            Err(Error::InvalidMultiSig(_))
+        ));
+
+        let outsider = PrivateKeySigner::random();
+        builder.sign(&outsider).await.unwrap();
+        assert!(matches!(
+            builder.build(&config(&wallets, 1)),
+            Err(Error::InvalidMultiSig(_))
+        ));
+    }
+
+    #[test]
+    fn wire_shape_matches_python_sdk() {
+        let signature = Signature::new(
+            alloy::primitives::U256::from(0xabcu64),
+            alloy::primitives::U256::from(0xdefu64),
+            true,
+        );
+        let action = MultiSigAction {
+            multi_sig_user: Address::repeat_byte(0xAA),
+            outer_signer: Address::repeat_byte(0xBB),
+            action: UsdSend::new(Address::repeat_byte(0x33), dec!(10)).with_nonce(NONCE),
+            signatures: vec![signature],
+            nonce: Some(NONCE),
+            signing_chain: SigningChain::Testnet,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            json!({
+                "signatureChainId": "0x66eee",
+                "signatures": [{"r": "0xabc", "s": "0xdef", "v": 28}],
+                "payload": {
+                    "multiSigUser": "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                    "outerSigner": "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+                    "action": {
+                        "type": "usdSend",
+                        "signatureChainId": "0x66eee",
+                        "hyperliquidChain": "Testnet",
+                        "destination": "0x3333333333333333333333333333333333333333",
+                        "amount": "10",
+                        "time": NONCE
+                    }
+                }
+            })
+        );
+
+        // The hashed msgpack keeps Python's key order for the inner action.
+        let inner = InnerAction {
+            action: &action.action,
+            signing_chain: &SigningChain::Testnet,
+        };
+        let bytes = rmp_serde::to_vec_named(&inner).unwrap();
+        let OrderedFields(fields) = rmp_serde::from_slice(&bytes).unwrap();
+        let keys: Vec<_> = fields.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "type",
+                "signatureChainId",
+                "hyperliquidChain",
+                "destination",
+                "amount",
+                "time"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn outer_hash_commits_to_inner_signatures() {
+        let wallets = wallets();
+        let mut builder = MultiSigBuilder::new(
+            ToggleBigBlocks::enable().with_nonce(NONCE),
+            Address::repeat_byte(0x11),
+            wallets[0].address(),
+            &SigningChain::Testnet,
+            None,
+        )
+        .unwrap();
+        builder.sign(&wallets[0]).await.unwrap();
+        let one = builder.clone().build(&config(&wallets, 1)).unwrap();
+        builder.sign(&wallets[1]).await.unwrap();
+        let two = builder.build(&config(&wallets, 1)).unwrap();
+
+        let hash = |action| {
+            PreparedAction::new(action, &SigningChain::Testnet, None, None)
+                .unwrap()
+                .signing_hash()
+        };
+        assert_ne!(hash(one), hash(two));
+    }
+}
@@ -242,7 +242,7 @@ impl<'de> Deserialize<'de> for SignedActionKind {
     }
 }
 
-fn serialize_sig<S>(sig: &Signature, serializer: S) -> Result<S::Ok, S::Error>
+pub(crate) fn serialize_sig<S>(sig: &Signature, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -253,7 +253,7 @@ where
     state.end()
 }
 
-fn deserialize_sig<'de, D>(deserializer: D) -> Result<Signature, D::Error>
+pub(crate) fn deserialize_sig<'de, D>(deserializer: D) -> Result<Signature, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -370,9 +370,9 @@ mod tests {
 
     use super::*;
     use crate::actions::{
-        ActionKind, BatchCancel, BatchModify, CancelByCloid, CancelWire,
-        LimitOrderType, OrderType, OrderWire, PreparedAction, SetOpenInterestCaps, SignedActionKind,
-        Tif, ToggleBigBlocks, UsdSend,
+        ActionKind, BatchCancel, BatchModify, CDeposit, CancelByCloid, CancelWire, ClaimRewards,
+        Cloid, LimitOrderType, OrderType, OrderWire, PreparedAction, SetOpenInterestCaps,
+        SignedActionKind, Tif, ToggleBigBlocks, TwapCancel, TwapOrder, UsdSend, Wei,
     };
     use crate::SigningChain;
 
@@ -424,7 +424,8 @@ mod tests {
 
     #[test]
     fn cancel_by_cloid_action_shape_matches_python_sdk() {
-        let action = CancelByCloid::single(110_000, "0x0123456789abcdef0123456789abcdef");
+        let cloid: Cloid = "0x0123456789abcdef0123456789abcdef".parse().unwrap();
+        let action = CancelByCloid::single(110_000, cloid);
         let v = build_action_value(&action, None).expect("build_action_value");
         let obj = v.as_object().expect("action object");
         assert_eq!(
@@ -433,6 +434,21 @@ mod tests {
         );
         let cancels = obj.get("cancels").expect("top-level cancels");
         assert!(cancels.is_array(), "expected array; got {cancels:?}");
+        assert_eq!(cancels[0]["cloid"], "0x0123456789abcdef0123456789abcdef");
+    }
+
+    /// Python packs the cloid as its `0x` hex string (str8, d9 22), never as raw bytes, and the
+    /// L1 hash is taken over that msgpack.
+    #[test]
+    fn order_cloid_is_packed_as_a_hex_string() {
+        let cloid = Cloid::from(1u128);
+        let order =
+            OrderWire::limit(1, true, dec!(100), dec!(100), Tif::Gtc).with_client_order_id(cloid);
+        let packed = rmp_serde::to_vec_named(&order).unwrap();
+
+        let mut expected = vec![0xa1, b'c', 0xd9, 0x22];
+        expected.extend_from_slice(b"0x00000000000000000000000000000001");
+        assert!(packed.ends_with(&expected), "{packed:02x?}");
     }
 
     #[test]
@@ -684,6 +700,151 @@ mod tests {
         assert_eq!(got.as_slice(), EXPECTED);
     }
 
+    /// Python SDK shape: `{"type": "twapOrder", "twap": {"a", "b", "s", "r", "m", "t"}}`, with the
+    /// size through `float_to_wire`. Generated via CPython `msgpack.packb`.
+    #[test]
+    fn l1_nested_twap_order_msgpack_matches_python_reference() {
+        use crate::actions::l1_actions::TwapOrder;
+
+        let action = TwapOrder::new(4, true, dec!(0.50), 30).with_randomize(true);
+        let wrapper = super::L1ActionWrapper { action: &action };
+        let got = rmp_serde::to_vec_named(&wrapper).unwrap();
+        const EXPECTED: &[u8] = &[
+            0x82, 0xa4, 0x74, 0x79, 0x70, 0x65, 0xa9, 0x74, 0x77, 0x61, 0x70, 0x4f, 0x72, 0x64,
+            0x65, 0x72, 0xa4, 0x74, 0x77, 0x61, 0x70, 0x86, 0xa1, 0x61, 0x04, 0xa1, 0x62, 0xc3,
+            0xa1, 0x73, 0xa3, 0x30, 0x2e, 0x35, 0xa1, 0x72, 0xc2, 0xa1, 0x6d, 0x1e, 0xa1, 0x74,
+            0xc3,
+        ];
+        assert_eq!(got.as_slice(), EXPECTED);
+    }
+
+    /// Python SDK shape: `{"type": "twapCancel", "a": asset, "t": twap_id}`; the id takes the
+    /// uint32 path (ce ...). Generated via CPython `msgpack.packb`.
+    #[test]
+    fn l1_flat_twap_cancel_msgpack_matches_python_reference() {
+        use crate::actions::l1_actions::TwapCancel;
+
+        let action = TwapCancel::new(4, 77738308);
+        let wrapper = super::L1ActionWrapper { action: &action };
+        let got = rmp_serde::to_vec_named(&wrapper).unwrap();
+        const EXPECTED: &[u8] = &[
+            0x83, 0xa4, 0x74, 0x79, 0x70, 0x65, 0xaa, 0x74, 0x77, 0x61, 0x70, 0x43, 0x61, 0x6e,
+            0x63, 0x65, 0x6c, 0xa1, 0x61, 0x04, 0xa1, 0x74, 0xce, 0x04, 0xa2, 0x31, 0x44,
+        ];
+        assert_eq!(got.as_slice(), EXPECTED);
+    }
+
+    /// Unset settings go out as `None` (msgpack nil), as Python packs them; the address takes
+    /// the str8 path (d9 2a). Generated via CPython `msgpack.packb`.
+    #[test]
+    fn l1_flat_vault_modify_msgpack_matches_python_reference() {
+        use crate::actions::l1_actions::VaultModify;
+
+        let action = VaultModify::new(Address::repeat_byte(0x55)).with_allow_deposits(true);
+        let wrapper = super::L1ActionWrapper { action: &action };
+        let got = rmp_serde::to_vec_named(&wrapper).unwrap();
+        const EXPECTED: &[u8] = &[
+            0x84, 0xa4, 0x74, 0x79, 0x70, 0x65, 0xab, 0x76, 0x61, 0x75, 0x6c, 0x74, 0x4d, 0x6f,
+            0x64, 0x69, 0x66, 0x79, 0xac, 0x76, 0x61, 0x75, 0x6c, 0x74, 0x41, 0x64, 0x64, 0x72,
+            0x65, 0x73, 0x73, 0xd9, 0x2a, 0x30, 0x78, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35,
+            0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35,
+            0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35,
+            0x35, 0x35, 0x35, 0x35, 0x35, 0xad, 0x61, 0x6c, 0x6c, 0x6f, 0x77, 0x44, 0x65, 0x70,
+            0x6f, 0x73, 0x69, 0x74, 0x73, 0xc3, 0xb5, 0x61, 0x6c, 0x77, 0x61, 0x79, 0x73, 0x43,
+            0x6c, 0x6f, 0x73, 0x65, 0x4f, 0x6e, 0x57, 0x69, 0x74, 0x68, 0x64, 0x72, 0x61, 0x77,
+            0xc0,
+        ];
+        assert_eq!(got.as_slice(), EXPECTED);
+    }
+
+    /// `createVault` carries the nonce inside the action too; `initialUsd` is micro-USD and
+    /// must pack as uint32 (ce) like Python's int. Generated via CPython `msgpack.packb`.
+    #[test]
+    fn l1_flat_create_vault_msgpack_matches_python_reference() {
+        use crate::actions::{l1_actions::CreateVault, MicroUsd};
+
+        let action =
+            CreateVault::new("mm", "majors", MicroUsd(100_000_000)).with_nonce(1_700_000_000_000);
+        let wrapper = super::L1ActionWrapper { action: &action };
+        let got = rmp_serde::to_vec_named(&wrapper).unwrap();
+        const EXPECTED: &[u8] = &[
+            0x85, 0xa4, 0x74, 0x79, 0x70, 0x65, 0xab, 0x63, 0x72, 0x65, 0x61, 0x74, 0x65, 0x56,
+            0x61, 0x75, 0x6c, 0x74, 0xa4, 0x6e, 0x61, 0x6d, 0x65, 0xa2, 0x6d, 0x6d, 0xab, 0x64,
+            0x65, 0x73, 0x63, 0x72, 0x69, 0x70, 0x74, 0x69, 0x6f, 0x6e, 0xa6, 0x6d, 0x61, 0x6a,
+            0x6f, 0x72, 0x73, 0xaa, 0x69, 0x6e, 0x69, 0x74, 0x69, 0x61, 0x6c, 0x55, 0x73, 0x64,
+            0xce, 0x05, 0xf5, 0xe1, 0x00, 0xa5, 0x6e, 0x6f, 0x6e, 0x63, 0x65, 0xcf, 0x00, 0x00,
+            0x01, 0x8b, 0xcf, 0xe5, 0x68, 0x00,
+        ];
+        assert_eq!(got.as_slice(), EXPECTED);
+    }
+
+    #[test]
+    fn test_signed_action_kind_dispatches_twap_actions() {
+        let signing_chain = SigningChain::Testnet;
+        let sig = Signature::new(U256::from(1), U256::from(2), true);
+
+        let order = TwapOrder::new(4, false, dec!(1.5), 60).with_reduce_only(true);
+        let signed = PreparedAction::new(order, &signing_chain, None, None)
+            .unwrap()
+            .with_signature(sig);
+        let json = serde_json::to_string(&signed).unwrap();
+        match SignedActionKind::from_json(&json).unwrap().action {
+            ActionKind::TwapOrder(action) => {
+                assert_eq!(action.size, dec!(1.5));
+                assert_eq!(action.minutes, 60);
+                assert!(action.reduce_only && !action.randomize);
+            }
+            other => panic!("expected ActionKind::TwapOrder, got: {other:?}"),
+        }
+
+        let signed = PreparedAction::new(TwapCancel::new(4, 9), &signing_chain, None, None)
+            .unwrap()
+            .with_signature(sig);
+        let json = serde_json::to_string(&signed).unwrap();
+        assert!(matches!(
+            SignedActionKind::from_json(&json).unwrap().action,
+            ActionKind::TwapCancel(TwapCancel {
+                asset: 4,
+                twap_id: 9,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_signed_action_kind_dispatches_staking_actions() {
+        let signing_chain = SigningChain::Mainnet;
+        let sig = Signature::new(U256::from(1), U256::from(2), true);
+
+        let deposit = CDeposit::new(Wei::from_hype(dec!(2.5)).unwrap());
+        let signed = PreparedAction::new(deposit, &signing_chain, None, None)
+            .unwrap()
+            .with_signature(sig);
+        let value = serde_json::to_value(&signed).unwrap();
+        assert_eq!(value["action"]["type"], "cDeposit");
+        assert_eq!(value["action"]["hyperliquidChain"], "Mainnet");
+        assert_eq!(value["action"]["wei"], 250_000_000);
+        match SignedActionKind::from_json(&value.to_string())
+            .unwrap()
+            .action
+        {
+            ActionKind::CDeposit(action) => assert_eq!(action.wei, Wei(250_000_000)),
+            other => panic!("expected ActionKind::CDeposit, got: {other:?}"),
+        }
+
+        let signed = PreparedAction::new(ClaimRewards::new(), &signing_chain, None, None)
+            .unwrap()
+            .with_signature(sig);
+        let value = serde_json::to_value(&signed).unwrap();
+        assert_eq!(value["action"], json!({"type": "claimRewards"}));
+        assert!(matches!(
+            SignedActionKind::from_json(&value.to_string())
+                .unwrap()
+                .action,
+            ActionKind::ClaimRewards(_)
+        ));
+    }
+
     #[test]
     fn test_signed_action_kind_deserializes_user_signed_action() {
         let dest = Address::repeat_byte(0xAB);
@@ -1,6 +1,8 @@
 use serde::Serialize;
 
-use crate::{actions::SigningMeta, Error, SigningChain};
+use crate::{
+    actions::SigningMeta, clients::exchange::responses::ActionResponse, Error, SigningChain,
+};
 use alloy::{
     primitives::{keccak256, Address, B256},
     sol_types::eip712_domain,
@@ -89,6 +91,10 @@ pub trait Action: Serialize + Send + Sync {
     /// Key to serialize action payload to, if it differs from 'ACTION_TYPE'
     const PAYLOAD_KEY: &'static str;
 
+    /// Typed response returned by `ExchangeClient::send_action` for this action.
+    /// Set with `#[action(response = "...")]`; defaults to [`ExchangeResponse`](crate::ExchangeResponse).
+    type Response: ActionResponse;
+
     /// Whether the action is user-signed (EIP-712).
     fn is_user_signed() -> bool {
         false
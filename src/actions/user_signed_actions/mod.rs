@@ -19,6 +19,9 @@ pub use user_dex_abstraction::UserDexAbstraction;
 mod token_delegate;
 pub use token_delegate::TokenDelegate;
 
+mod staking_transfer;
+pub use staking_transfer::{CDeposit, CWithdraw};
+
 mod convert_to_multisig_user;
 pub use convert_to_multisig_user::{ConvertToMultiSigUser, MultiSigSigners};
 
@@ -88,6 +88,23 @@ impl DexId {
     }
 }
 
+impl From<&str> for DexId {
+    /// Parse the string representation, ignoring case.
+    fn from(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "spot" => DexId::Spot,
+            "" => DexId::Perp,
+            other => DexId::Hip3(other.to_owned()),
+        }
+    }
+}
+
+impl From<String> for DexId {
+    fn from(s: String) -> Self {
+        DexId::from(s.as_str())
+    }
+}
+
 impl Serialize for DexId {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -103,12 +120,7 @@ impl<'de> Deserialize<'de> for DexId {
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-
-        Ok(match s.to_lowercase().as_str() {
-            "spot" => DexId::Spot,
-            "" => DexId::Perp,
-            other => DexId::Hip3(other.to_owned()),
-        })
+        Ok(DexId::from(s))
     }
 }
 
@@ -0,0 +1,56 @@
+use derive_builder::Builder;
+use hl_rs_derive::UserSignedAction;
+use serde::{Deserialize, Serialize};
+
+use crate::actions::Wei;
+
+/// Move HYPE from the spot balance into the staking balance, where it can be delegated.
+#[derive(Debug, Clone, Serialize, Deserialize, Builder, UserSignedAction)]
+#[action(
+    action_type = "cDeposit",
+    types = "CDeposit(string hyperliquidChain,uint64 wei,uint64 nonce)"
+)]
+#[serde(rename_all = "camelCase")]
+#[builder(setter(into))]
+pub struct CDeposit {
+    /// Amount to move
+    pub wei: Wei,
+    #[builder(default)]
+    pub nonce: Option<u64>,
+}
+
+impl CDeposit {
+    pub fn builder() -> CDepositBuilder {
+        CDepositBuilder::default()
+    }
+
+    pub fn new(wei: Wei) -> Self {
+        Self { wei, nonce: None }
+    }
+}
+
+/// Move undelegated HYPE from the staking balance back to spot. The withdrawal is pending
+/// for 7 days before it reaches the spot balance.
+#[derive(Debug, Clone, Serialize, Deserialize, Builder, UserSignedAction)]
+#[action(
+    action_type = "cWithdraw",
+    types = "CWithdraw(string hyperliquidChain,uint64 wei,uint64 nonce)"
+)]
+#[serde(rename_all = "camelCase")]
+#[builder(setter(into))]
+pub struct CWithdraw {
+    /// Amount to move
+    pub wei: Wei,
+    #[builder(default)]
+    pub nonce: Option<u64>,
+}
+
+impl CWithdraw {
+    pub fn builder() -> CWithdrawBuilder {
+        CWithdrawBuilder::default()
+    }
+
+    pub fn new(wei: Wei) -> Self {
+        Self { wei, nonce: None }
+    }
+}
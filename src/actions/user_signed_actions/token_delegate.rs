@@ -3,7 +3,7 @@ use derive_builder::Builder;
 use hl_rs_derive::UserSignedAction;
 use serde::{Deserialize, Serialize};
 
-use crate::actions::serialization::ser_lowercase;
+use crate::actions::{serialization::ser_lowercase, Wei};
 
 /// Delegate or undelegate tokens to a validator.
 #[derive(Debug, Clone, Serialize, Deserialize, Builder, UserSignedAction)]
@@ -16,8 +16,8 @@ pub struct TokenDelegate {
     /// Validator address to delegate to
     #[serde(serialize_with = "ser_lowercase")]
     pub validator: Address,
-    /// Amount in wei to delegate/undelegate
-    pub wei: u64,
+    /// Amount to delegate/undelegate
+    pub wei: Wei,
     /// True to undelegate, false to delegate
     pub is_undelegate: bool,
     #[builder(default)]
@@ -30,7 +30,7 @@ impl TokenDelegate {
     }
 
     /// Delegate tokens to a validator
-    pub fn delegate(validator: Address, wei: u64) -> Self {
+    pub fn delegate(validator: Address, wei: Wei) -> Self {
         Self {
             validator,
             wei,
@@ -40,7 +40,7 @@ impl TokenDelegate {
     }
 
     /// Undelegate tokens from a validator
-    pub fn undelegate(validator: Address, wei: u64) -> Self {
+    pub fn undelegate(validator: Address, wei: Wei) -> Self {
         Self {
             validator,
             wei,
@@ -0,0 +1,339 @@
+//! Recording of HTTP and WebSocket traffic to JSONL cassettes.
+//!
+//! A [`Recorder`] attached to [`InfoClient`](crate::InfoClient),
+//! [`ExchangeClient`](crate::ExchangeClient) or `WsClient` appends one [`CassetteEntry`] per
+//! line: every `/info` and `/exchange` exchange with its status, raw response body and
+//! latency, and every text frame sent or received on the socket. Lines are flushed as they
+//! are written, so a cassette survives the process crashing.
+//!
+//! Signatures and nonces differ on every run, so the fields named in [`MASKED_FIELDS`] are
+//! replaced by [`MASK`] in recorded requests and sent frames (see [`mask`]). Responses are kept
+//! verbatim. With the `mock` feature, `mock::ReplayServer` serves a cassette back to the
+//! clients.
+//!
+//! # Example
+//! ```no_run
+//! use hl_rs::{cassette::Recorder, BaseUrl, ExchangeClient};
+//!
+//! # async fn run() -> Result<(), hl_rs::Error> {
+//! let recorder = Recorder::create("incident.jsonl")?;
+//! let client = ExchangeClient::new(BaseUrl::Mainnet).with_recorder(recorder);
+//! let _meta = client.info().meta().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(feature = "ws")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{prelude::Result, Error};
+
+/// Request fields that change on every run and are masked in cassettes. `time` is the nonce
+/// of user-signed actions, `expiresAfter` is derived from the clock.
+pub const MASKED_FIELDS: [&str; 5] = ["signature", "signatures", "nonce", "time", "expiresAfter"];
+
+/// Value that replaces masked fields.
+pub const MASK: &str = "<masked>";
+
+/// One line of a cassette.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum CassetteEntry {
+    /// A POST to `/info` or `/exchange` and the response it got.
+    #[serde(rename_all = "camelCase")]
+    Http {
+        /// Milliseconds since the recorder was created.
+        at_ms: u64,
+        path: String,
+        /// Request body, masked.
+        request: Value,
+        status: u16,
+        /// Response body as received.
+        response: String,
+        elapsed_ms: u64,
+    },
+    /// A text frame on a WebSocket connection.
+    #[serde(rename_all = "camelCase")]
+    Ws {
+        /// Milliseconds since the recorder was created.
+        at_ms: u64,
+        /// Connections are numbered from 0 in the order they were recorded.
+        connection: u64,
+        direction: WsDirection,
+        /// Frame text; masked if sent by the client.
+        frame: String,
+    },
+}
+
+/// Which side sent a WebSocket frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WsDirection {
+    /// Client to server.
+    Send,
+    /// Server to client.
+    Recv,
+}
+
+/// Replace every field named in [`MASKED_FIELDS`], at any depth, with [`MASK`].
+pub fn mask(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if MASKED_FIELDS.contains(&key.as_str()) {
+                    *field = Value::String(MASK.to_string());
+                } else {
+                    mask(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(mask),
+        _ => {}
+    }
+}
+
+/// [`mask`] applied to a frame, which is kept as is if it isn't JSON.
+#[cfg(feature = "ws")]
+pub(crate) fn mask_frame(frame: &str) -> String {
+    match serde_json::from_str::<Value>(frame) {
+        Ok(mut value) => {
+            mask(&mut value);
+            value.to_string()
+        }
+        Err(_) => frame.to_string(),
+    }
+}
+
+/// Appends traffic to a cassette. Cheap to clone; clones write to the same cassette.
+///
+/// Write failures are logged and otherwise ignored, so recording never breaks the client.
+#[derive(Clone)]
+pub struct Recorder {
+    inner: Arc<RecorderInner>,
+}
+
+struct RecorderInner {
+    writer: Mutex<Box<dyn Write + Send>>,
+    started: Instant,
+    #[cfg(feature = "ws")]
+    next_connection: AtomicU64,
+}
+
+impl std::fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recorder")
+            .field("started", &self.inner.started)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Recorder {
+    /// Record to the file at `path`, replacing it if it exists.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::create(path).map_err(|e| Error::Cassette(e.to_string()))?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+
+    /// Record to any writer.
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            inner: Arc::new(RecorderInner {
+                writer: Mutex::new(Box::new(writer)),
+                started: Instant::now(),
+                #[cfg(feature = "ws")]
+                next_connection: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    pub(crate) fn record_http(
+        &self,
+        path: &str,
+        request: &Value,
+        status: u16,
+        response: &str,
+        elapsed: Duration,
+    ) {
+        let mut request = request.clone();
+        mask(&mut request);
+        self.write(&CassetteEntry::Http {
+            at_ms: self.at_ms(),
+            path: path.to_string(),
+            request,
+            status,
+            response: response.to_string(),
+            elapsed_ms: elapsed.as_millis() as u64,
+        });
+    }
+
+    /// Number for a new WebSocket connection.
+    #[cfg(feature = "ws")]
+    pub(crate) fn ws_connection(&self) -> u64 {
+        self.inner.next_connection.fetch_add(1, Ordering::Relaxed)
+    }
+
+    #[cfg(feature = "ws")]
+    pub(crate) fn record_ws(&self, connection: u64, direction: WsDirection, frame: &str) {
+        let frame = match direction {
+            WsDirection::Send => mask_frame(frame),
+            WsDirection::Recv => frame.to_string(),
+        };
+        self.write(&CassetteEntry::Ws {
+            at_ms: self.at_ms(),
+            connection,
+            direction,
+            frame,
+        });
+    }
+
+    fn at_ms(&self) -> u64 {
+        self.inner.started.elapsed().as_millis() as u64
+    }
+
+    fn write(&self, entry: &CassetteEntry) {
+        let mut writer = self.inner.writer.lock().unwrap();
+        let written = serde_json::to_writer(&mut *writer, entry)
+            .map_err(std::io::Error::from)
+            .and_then(|()| writer.write_all(b"\n"))
+            .and_then(|()| writer.flush());
+        if let Err(err) = written {
+            tracing::warn!(target: "hl_rs::cassette", %err, "failed to record cassette entry");
+        }
+    }
+}
+
+/// Recorded traffic, in recording order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Cassette {
+    pub entries: Vec<CassetteEntry>,
+}
+
+impl Cassette {
+    /// Read the cassette at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path).map_err(|e| Error::Cassette(e.to_string()))?;
+        let mut entries = Vec::new();
+        for (number, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|e| Error::Cassette(e.to_string()))?;
+            if let Some(entry) = parse_line(number, &line)? {
+                entries.push(entry);
+            }
+        }
+        Ok(Self { entries })
+    }
+
+    /// Parse cassette text, one entry per line. Blank lines are skipped.
+    pub fn from_jsonl(text: &str) -> Result<Self> {
+        let mut entries = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            if let Some(entry) = parse_line(number, line)? {
+                entries.push(entry);
+            }
+        }
+        Ok(Self { entries })
+    }
+}
+
+fn parse_line(number: usize, line: &str) -> Result<Option<CassetteEntry>> {
+    if line.trim().is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_str(line)
+        .map(Some)
+        .map_err(|e| Error::Cassette(format!("line {}: {e}", number + 1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    /// Writer whose contents stay readable after the recorder takes it.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn masks_signatures_and_nonces_at_any_depth() {
+        let mut request = json!({
+            "action": {
+                "type": "usdSend",
+                "time": 1700000000000u64,
+                "amount": "1",
+                "payload": {"signatures": [{"r": "0x1"}], "nonce": 7},
+            },
+            "nonce": 1700000000000u64,
+            "signature": {"r": "0x1", "s": "0x2", "v": 27},
+            "vaultAddress": null,
+        });
+        mask(&mut request);
+        assert_eq!(
+            request,
+            json!({
+                "action": {
+                    "type": "usdSend",
+                    "time": MASK,
+                    "amount": "1",
+                    "payload": {"signatures": MASK, "nonce": MASK},
+                },
+                "nonce": MASK,
+                "signature": MASK,
+                "vaultAddress": null,
+            })
+        );
+    }
+
+    #[test]
+    fn recorded_lines_load_back() {
+        let out = Shared::default();
+        let recorder = Recorder::new(out.clone());
+        recorder.record_http(
+            "/exchange",
+            &json!({"action": {"type": "noop"}, "nonce": 1}),
+            200,
+            r#"{"status":"ok"}"#,
+            Duration::from_millis(12),
+        );
+        recorder.record_http(
+            "/info",
+            &json!({"type": "allMids"}),
+            200,
+            r#"{"ETH":"3000"}"#,
+            Duration::from_millis(3),
+        );
+
+        let text = String::from_utf8(out.0.lock().unwrap().clone()).unwrap();
+        let cassette = Cassette::from_jsonl(&text).unwrap();
+        assert_eq!(cassette.entries.len(), 2);
+        assert!(matches!(
+            &cassette.entries[0],
+            CassetteEntry::Http { request, elapsed_ms: 12, .. } if request["nonce"] == MASK
+        ));
+        assert!(matches!(
+            &cassette.entries[1],
+            CassetteEntry::Http { path, response, .. } if path == "/info" && response == r#"{"ETH":"3000"}"#
+        ));
+        assert!(Cassette::from_jsonl("{\"type\":\"http\"}").is_err());
+    }
+}
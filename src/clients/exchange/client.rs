@@ -4,16 +4,25 @@
 
 use alloy::{primitives::Address, signers::local::PrivateKeySigner};
 use reqwest::Client;
+use rust_decimal::Decimal;
 use serde::Serialize;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
 
 use crate::{
-    actions::{Action, SignedAction},
-    clients::exchange::responses::{ExchangeResponse, ExchangeResponseStatusRaw},
+    actions::{
+        Action, ApproveAgent, BatchOrder, MultiSigBuilder, OrderPrecision, OrderWire, RoundingMode,
+        SignedAction, Tif,
+    },
+    cassette::Recorder,
+    clients::exchange::responses::{
+        ActionResponse, ExchangeDataStatus, ExchangeResponseStatusRaw, FillSummary,
+    },
+    error::ApiError,
     http::HttpClient,
-    BaseUrl, Error, PreparedAction,
+    info::{types::UserRoleResponse, AssetRegistry, InfoClient},
+    nonce::{self, NonceSource},
+    ActionSigner, BaseUrl, Error, PreparedAction,
 };
 
 /// Client for preparing, signing, and sending exchange actions.
@@ -24,14 +33,14 @@ use crate::{
 ///
 /// use alloy::primitives::Address;
 /// use alloy::signers::local::PrivateKeySigner;
-/// use hl_rs::{BaseUrl, ExchangeClientV2, UsdSend};
+/// use hl_rs::{BaseUrl, ExchangeClient, UsdSend};
 /// use rust_decimal_macros::dec;
 ///
 /// # async fn run() -> Result<(), hl_rs::Error> {
 /// let wallet =
 ///     PrivateKeySigner::from_str("0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef")
 ///         .expect("valid private key hex");
-/// let client = ExchangeClientV2::new(BaseUrl::Testnet).with_signer(wallet);
+/// let client = ExchangeClient::new(BaseUrl::Testnet).with_signer(wallet);
 ///
 /// let action = UsdSend::new(Address::ZERO, dec!(1.0));
 ///
@@ -46,16 +55,16 @@ use crate::{
 ///
 /// use alloy::primitives::Address;
 /// use alloy::signers::local::PrivateKeySigner;
-/// use hl_rs::{BaseUrl, ExchangeClientV2, SetSubDeployers, SubDeployer, SubDeployerVariant};
+/// use hl_rs::{BaseUrl, ExchangeClient, SetSubDeployers, SubDeployer, SubDeployerVariant};
 ///
 /// # async fn run() -> Result<(), hl_rs::Error> {
 /// let wallet =
 ///     PrivateKeySigner::from_str("0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef")
 ///         .expect("valid private key hex");
-/// let client = ExchangeClientV2::new(BaseUrl::Testnet).with_signer(wallet);
+/// let client = ExchangeClient::new(BaseUrl::Testnet).with_signer(wallet);
 ///
 /// let sub_deployer = SubDeployer::enable(Address::ZERO, SubDeployerVariant::SetOracle);
-/// let action = SetSubDeployers::new("km", vec![sub_deployer]);
+/// let action = SetSubDeployers::new("km").with_sub_deployer(sub_deployer);
 /// let _response = client.send_action(action).await?;
 /// # Ok(())
 /// # }
@@ -66,8 +75,12 @@ pub struct ExchangeClient {
     http_client: HttpClient,
     vault_address: Option<Address>,
     expires_after: Option<u64>,
-    signer_private_key: Option<PrivateKeySigner>,
-    nonce_counter: Arc<AtomicU64>,
+    signer: Option<Arc<dyn ActionSigner>>,
+    agent_master: Option<Address>,
+    /// `None` follows the process-wide [`nonce::shared`] source.
+    nonce_source: Option<Arc<dyn NonceSource>>,
+    info_client: InfoClient,
+    asset_registry: Arc<RwLock<Option<Arc<AssetRegistry>>>>,
 }
 
 impl ExchangeClient {
@@ -75,6 +88,11 @@ impl ExchangeClient {
         let http_client = HttpClient {
             client: Client::default(),
             base_url: base_url.get_url(),
+            recorder: None,
+        };
+
+        let info_client = InfoClient {
+            http_client: http_client.clone(),
         };
 
         Self {
@@ -82,8 +100,11 @@ impl ExchangeClient {
             http_client,
             vault_address: None,
             expires_after: None,
-            signer_private_key: None,
-            nonce_counter: Arc::new(AtomicU64::new(Self::current_timestamp_ms())),
+            signer: None,
+            agent_master: None,
+            nonce_source: None,
+            info_client,
+            asset_registry: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -96,11 +117,136 @@ impl ExchangeClient {
         self.expires_after = Some(expires_after);
         self
     }
-    pub fn with_signer(mut self, signer_private_key: PrivateKeySigner) -> Self {
-        self.signer_private_key = Some(signer_private_key);
+
+    /// Record every `/exchange` and `/info` request (including those of
+    /// [`info`](Self::info)) to `recorder`'s cassette.
+    pub fn with_recorder(mut self, recorder: Recorder) -> Self {
+        self.http_client.recorder = Some(recorder.clone());
+        self.info_client.http_client.recorder = Some(recorder);
+        self
+    }
+
+    /// Sign actions with `signer`: a `PrivateKeySigner`, any other `alloy` signer, or a
+    /// custom [`ActionSigner`].
+    pub fn with_signer(self, signer: impl ActionSigner + 'static) -> Self {
+        self.with_shared_signer(Arc::new(signer))
+    }
+
+    /// Sign actions with a signer shared with other clients.
+    pub fn with_shared_signer(mut self, signer: Arc<dyn ActionSigner>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Take nonces from `source` instead of the process-wide [`nonce::shared`] source, e.g.
+    /// a [`FileNonceSource`](nonce::FileNonceSource) shared with other processes signing
+    /// for the same account.
+    ///
+    /// Clients without a source of their own follow [`nonce::set_shared`], even if created
+    /// before it was called.
+    pub fn with_nonce_source(self, source: impl NonceSource + 'static) -> Self {
+        self.with_shared_nonce_source(Arc::new(source))
+    }
+
+    /// Take nonces from a source shared with other clients.
+    pub fn with_shared_nonce_source(mut self, source: Arc<dyn NonceSource>) -> Self {
+        self.nonce_source = Some(source);
+        self
+    }
+
+    /// Trade as an approved agent (API wallet) of `master`.
+    ///
+    /// L1 actions are signed by `agent` and act on `master`'s account (or the vault, if set).
+    /// User-signed actions are refused with [`Error::UserSignedByAgent`], since only the
+    /// master can sign them.
+    pub fn with_agent(self, master: Address, agent: impl ActionSigner + 'static) -> Self {
+        let mut client = self.with_signer(agent);
+        client.agent_master = Some(master);
+        client
+    }
+
+    /// Master account of the agent, when in agent mode.
+    pub fn agent_master(&self) -> Option<Address> {
+        self.agent_master
+    }
+
+    /// Generate a fresh agent key, approve it with `master`, and return a client trading as
+    /// that agent together with the agent key, which the caller should persist.
+    ///
+    /// The approval is checked against `userRole` before returning.
+    pub async fn approve_agent<S: ActionSigner + ?Sized>(
+        &self,
+        master: &S,
+        agent_name: Option<&str>,
+    ) -> Result<(Self, PrivateKeySigner), Error> {
+        let agent = PrivateKeySigner::random();
+        let action = match agent_name {
+            Some(name) => ApproveAgent::new(agent.address(), name),
+            None => ApproveAgent::without_name(agent.address()),
+        };
+
+        // User-signed, so never on behalf of a vault.
+        let signed = PreparedAction::new(
+            self.ensure_action_nonce(action)?,
+            self.base_url.get_signing_chain(),
+            None,
+            None,
+        )?
+        .sign_with(master)
+        .await?;
+        self.send_signed_action(signed).await?;
+
+        let client = self.clone().with_agent(master.address(), agent.clone());
+        client.verify_agent().await?;
+        Ok((client, agent))
+    }
+
+    /// Check via `userRole` that the agent signer is linked to the configured master.
+    pub async fn verify_agent(&self) -> Result<(), Error> {
+        let master = self.agent_master.ok_or(Error::AgentNotConfigured)?;
+        let agent = self.signer.as_ref().ok_or(Error::SignerNotSet)?.address();
+
+        let role = match self.info_client.user_role(&agent).await? {
+            UserRoleResponse::Agent(data) if data.user == master => return Ok(()),
+            UserRoleResponse::Agent(data) => format!("agent of {}", data.user),
+            UserRoleResponse::User => "user".to_string(),
+            UserRoleResponse::Vault => "vault".to_string(),
+            UserRoleResponse::SubAccount(data) => format!("sub-account of {}", data.master),
+            UserRoleResponse::Missing => "missing".to_string(),
+        };
+        Err(Error::AgentNotApproved {
+            agent,
+            master,
+            role,
+        })
+    }
+
+    /// Use an already-loaded registry instead of fetching one on first use.
+    pub fn with_asset_registry(self, registry: AssetRegistry) -> Self {
+        *self.asset_registry.write().unwrap() = Some(Arc::new(registry));
         self
     }
 
+    /// Info client pointed at the same API as this client.
+    pub fn info(&self) -> &InfoClient {
+        &self.info_client
+    }
+
+    /// The asset registry, fetched on first use and cached afterwards.
+    pub async fn asset_registry(&self) -> Result<Arc<AssetRegistry>, Error> {
+        if let Some(registry) = self.asset_registry.read().unwrap().as_ref() {
+            return Ok(registry.clone());
+        }
+        self.refresh_asset_registry().await
+    }
+
+    /// Re-fetch the asset registry, e.g. after new assets are listed.
+    pub async fn refresh_asset_registry(&self) -> Result<Arc<AssetRegistry>, Error> {
+        let registry = Arc::new(AssetRegistry::load(&self.info_client).await?);
+        *self.asset_registry.write().unwrap() = Some(registry.clone());
+        Ok(registry)
+    }
+
     pub fn prepare_action<A: Action>(&self, action: A) -> Result<PreparedAction<A>, Error> {
         PreparedAction::new(
             action,
@@ -110,67 +256,187 @@ impl ExchangeClient {
         )
     }
 
-    pub fn sign_action<A: Action>(
+    pub async fn sign_action<A: Action, S: ActionSigner + ?Sized>(
         &self,
         action: A,
-        wallet: &PrivateKeySigner,
+        signer: &S,
     ) -> Result<SignedAction<A>, Error> {
-        self.prepare_action(self.ensure_action_nonce(action))?.sign(wallet)
+        self.prepare_action(self.ensure_action_nonce(action)?)?
+            .sign_with(signer)
+            .await
+    }
+
+    /// Start a `multiSig` action on behalf of `multi_sig_user`, with this client's signer as
+    /// the outer signer. Send the built action with [`ExchangeClient::send_action`].
+    pub fn multi_sig<A: Action + Clone>(
+        &self,
+        multi_sig_user: Address,
+        action: A,
+    ) -> Result<MultiSigBuilder<A>, Error> {
+        let outer_signer = self.signer.as_ref().ok_or(Error::SignerNotSet)?.address();
+        MultiSigBuilder::new(
+            self.ensure_action_nonce(action)?,
+            multi_sig_user,
+            outer_signer,
+            self.base_url.get_signing_chain(),
+            self.expires_after,
+        )
     }
 
     pub async fn send_signed_action<A: Action + Serialize>(
         &self,
         signed_action: SignedAction<A>,
-    ) -> Result<ExchangeResponse, Error> {
+    ) -> Result<A::Response, Error> {
         let output = self.http_client.post("/exchange", signed_action).await?;
         let raw: ExchangeResponseStatusRaw =
             serde_json::from_str(&output).map_err(|e| Error::JsonParse(e.to_string()))?;
 
-        raw.into_result()
+        A::Response::from_response(raw.into_result()?)
     }
 
     pub async fn send_action<A: Action + Serialize>(
         &self,
         action: A,
-    ) -> Result<ExchangeResponse, Error> {
-        let prepared = self.prepare_action(self.ensure_action_nonce(action))?;
-        let signer = self.signer_private_key.as_ref().ok_or(Error::SignerNotSet)?;
-        let signed = prepared.sign(signer)?;
+    ) -> Result<A::Response, Error> {
+        if self.agent_master.is_some() && A::is_user_signed() {
+            return Err(Error::UserSignedByAgent(A::ACTION_TYPE));
+        }
+        let prepared = self.prepare_action(self.ensure_action_nonce(action)?)?;
+        let signer = self.signer.as_deref().ok_or(Error::SignerNotSet)?;
+        let signed = prepared.sign_with(signer).await?;
         self.send_signed_action(signed).await
     }
 
-    /// Ensure the action carries a nonce before signing. If the caller already
-    /// set one, respect it; otherwise assign a strictly-monotonic nonce so
-    /// rapid-fire actions (e.g. UpdateLeverage then an order) never collide.
-    fn ensure_action_nonce<A: Action>(&self, action: A) -> A {
-        if action.nonce().is_some() {
-            action
+    /// Current mid price for `coin`, from `allMids` or, failing that, the top of the L2 book.
+    pub async fn mid_price(&self, coin: &str) -> Result<Decimal, Error> {
+        if let Some(mid) = self.info_client.all_mids().await?.get(coin) {
+            return parse_decimal(mid);
+        }
+
+        let book = self.info_client.l2_book(coin).await?;
+        let best = |side: usize| {
+            book.levels
+                .get(side)
+                .and_then(|levels| levels.first())
+                .ok_or_else(|| Error::GenericParse(format!("empty {coin} book")))
+                .and_then(|level| parse_decimal(&level.px))
+        };
+        Ok((best(0)? + best(1)?) / Decimal::TWO)
+    }
+
+    /// Buy or sell `size` of `coin` immediately, paying at most `slippage` away from the mid
+    /// (`dec!(0.01)` for 1%).
+    ///
+    /// Sends a single IOC limit order at the slippage-bounded price, rounded onto the
+    /// asset's tick grid without widening the bound. Errors if nothing fills.
+    pub async fn market_open(
+        &self,
+        coin: &str,
+        is_buy: bool,
+        size: Decimal,
+        slippage: Decimal,
+    ) -> Result<FillSummary, Error> {
+        self.market_order(coin, is_buy, size, slippage, false).await
+    }
+
+    /// Close the open perp position in `coin` with a reduce-only IOC order.
+    ///
+    /// Closes the whole position unless `size` is given. The position is read from
+    /// `clearinghouseState` of the vault address if set, otherwise of the signer, on the perp
+    /// dex `coin` trades on.
+    pub async fn market_close(
+        &self,
+        coin: &str,
+        size: Option<Decimal>,
+        slippage: Decimal,
+    ) -> Result<FillSummary, Error> {
+        let user = self.account_address()?;
+        let registry = self.asset_registry().await?;
+        let info = registry.info(coin)?;
+        let state = self
+            .info_client
+            .user_state(&user, info.dex.as_deref())
+            .await?;
+        let szi = state
+            .asset_positions
+            .iter()
+            .find(|p| p.position.coin == info.coin)
+            .map(|p| p.position.szi)
+            .unwrap_or_default();
+        if szi.is_zero() {
+            return Err(Error::NoOpenPosition(info.coin.clone()));
+        }
+
+        let size = size.unwrap_or(szi.abs()).min(szi.abs());
+        self.market_order(&info.coin, szi.is_sign_negative(), size, slippage, true)
+            .await
+    }
+
+    async fn market_order(
+        &self,
+        coin: &str,
+        is_buy: bool,
+        size: Decimal,
+        slippage: Decimal,
+        reduce_only: bool,
+    ) -> Result<FillSummary, Error> {
+        let registry = self.asset_registry().await?;
+        let info = registry.info(coin)?;
+        let precision = OrderPrecision::from(info);
+
+        let mid = self.mid_price(&info.coin).await?;
+        let limit_px = if is_buy {
+            mid * (Decimal::ONE + slippage)
         } else {
-            action.with_nonce(self.next_nonce())
+            mid * (Decimal::ONE - slippage)
+        };
+
+        // Passive rounding keeps the price inside the slippage bound and never grows the size.
+        let order = OrderWire::limit(info.asset, is_buy, limit_px, size, Tif::Ioc)
+            .with_reduce_only(reduce_only)
+            .rounded(&precision, RoundingMode::Passive)?;
+
+        let response = self.send_action(BatchOrder::new(vec![order])).await?;
+        let status = response
+            .statuses
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::GenericParse("no order status in response".to_string()))?;
+
+        match status {
+            ExchangeDataStatus::Filled(filled) => FillSummary::try_from(&filled),
+            ExchangeDataStatus::Error(message) => Err(Error::Api(ApiError::classify(message))),
+            other => Err(Error::GenericParse(format!(
+                "unexpected status for IOC order: {other:?}"
+            ))),
         }
     }
 
-    /// Monotonic nonce: current ms, bumped by 1 if we'd otherwise repeat or go
-    /// backwards within the same millisecond. Lock-free via a CAS loop.
-    fn next_nonce(&self) -> u64 {
-        let now = Self::current_timestamp_ms();
-        loop {
-            let last = self.nonce_counter.load(Ordering::Relaxed);
-            let next = if now > last { now } else { last + 1 };
-            if self
-                .nonce_counter
-                .compare_exchange(last, next, Ordering::SeqCst, Ordering::Relaxed)
-                .is_ok()
-            {
-                return next;
-            }
+    /// Account the exchange acts on: the vault if set, then the agent's master, otherwise
+    /// the signer.
+    pub(crate) fn account_address(&self) -> Result<Address, Error> {
+        if let Some(account) = self.vault_address.or(self.agent_master) {
+            return Ok(account);
         }
+        self.signer
+            .as_ref()
+            .map(|signer| signer.address())
+            .ok_or(Error::SignerNotSet)
     }
 
-    fn current_timestamp_ms() -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64
+    /// Ensure the action carries a nonce before signing. If the caller already
+    /// set one, respect it; otherwise take the next one from the nonce source so
+    /// rapid-fire actions (e.g. UpdateLeverage then an order) never collide.
+    fn ensure_action_nonce<A: Action>(&self, action: A) -> Result<A, Error> {
+        if action.nonce().is_some() {
+            Ok(action)
+        } else {
+            let source = self.nonce_source.clone().unwrap_or_else(nonce::shared);
+            Ok(action.with_nonce(source.next_nonce()?))
+        }
     }
 }
+
+fn parse_decimal(value: &str) -> Result<Decimal, Error> {
+    Decimal::from_str(value).map_err(|e| Error::GenericParse(format!("{value}: {e}")))
+}
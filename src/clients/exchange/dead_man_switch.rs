@@ -0,0 +1,405 @@
+//! Dead man's switch built on `scheduleCancel`.
+//!
+//! [`DeadManSwitch`] owns a background task that keeps a scheduled cancel-all a little in the
+//! future. If the process hangs, crashes or loses its connection, renewals stop and the
+//! exchange cancels every open order of the account once the scheduled time passes.
+//!
+//! The exchange fires at most 10 scheduled cancels per UTC day. The switch counts the ones
+//! that fired (a renewal that comes after the scheduled time) and, once only
+//! [`DeadManSwitchConfig::reserved_triggers`] are left, schedules further out so short
+//! hiccups don't spend the rest. Shutting the switch down cancels directly and doesn't spend
+//! a trigger.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::{
+    sync::oneshot,
+    task::JoinHandle,
+    time::{interval_at, Instant, MissedTickBehavior},
+};
+
+use crate::{
+    actions::{BatchCancel, CancelWire, ScheduleCancel},
+    nonce::current_timestamp_ms,
+    prelude::Result,
+    Error, ExchangeClient,
+};
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// The exchange refuses cancel times closer than this.
+const MIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Timing and trigger budget of a [`DeadManSwitch`].
+#[derive(Debug, Clone)]
+pub struct DeadManSwitchConfig {
+    /// How long after a renewal the orders are canceled if no further renewal comes.
+    /// At least 5 seconds.
+    pub timeout: Duration,
+    /// How often the cancel time is pushed forward. Must be shorter than `timeout`.
+    pub renew_interval: Duration,
+    /// Scheduled cancels that may fire per UTC day.
+    pub daily_trigger_limit: u32,
+    /// Triggers kept for real failures: once only this many are left, `degraded_timeout` is
+    /// used instead of `timeout`.
+    pub reserved_triggers: u32,
+    /// Timeout while degraded.
+    pub degraded_timeout: Duration,
+}
+
+impl Default for DeadManSwitchConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(60),
+            renew_interval: Duration::from_secs(15),
+            daily_trigger_limit: 10,
+            reserved_triggers: 2,
+            degraded_timeout: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+impl DeadManSwitchConfig {
+    fn validate(&self) -> Result<()> {
+        if self.timeout < MIN_TIMEOUT || self.degraded_timeout < MIN_TIMEOUT {
+            return Err(Error::DeadManSwitch(
+                "timeouts must be at least 5 seconds".to_string(),
+            ));
+        }
+        if self.renew_interval.is_zero() || self.renew_interval >= self.timeout {
+            return Err(Error::DeadManSwitch(
+                "renew_interval must be shorter than timeout".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// How the switch is running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadManMode {
+    /// Renewing with [`DeadManSwitchConfig::timeout`].
+    Armed,
+    /// Near the daily trigger limit; renewing with
+    /// [`DeadManSwitchConfig::degraded_timeout`].
+    Degraded,
+    /// No trigger left today: the exchange won't cancel until 00:00 UTC, though the switch
+    /// keeps renewing.
+    Exhausted,
+    /// Shut down or dropped.
+    Stopped,
+}
+
+/// Snapshot of a [`DeadManSwitch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadManStatus {
+    pub mode: DeadManMode,
+    /// Time (ms) at which open orders are canceled unless renewed. `None` once stopped, and
+    /// after a cancel fired until the next successful renewal.
+    pub cancel_at: Option<u64>,
+    /// Scheduled cancels that fired today (UTC), as seen by this switch.
+    pub triggers_today: u32,
+    /// Error of the last failed renewal, cleared by the next successful one.
+    pub last_error: Option<String>,
+}
+
+/// Keeps a `scheduleCancel` renewed in the background; see the [module docs](self).
+///
+/// Dropping the switch stops renewals and cancels open orders from a background task on the
+/// current Tokio runtime, if there is one. Prefer [`shutdown`](Self::shutdown), which reports
+/// whether that worked; either way the scheduled cancel is the fallback.
+///
+/// # Example
+/// ```no_run
+/// use hl_rs::{DeadManSwitch, DeadManSwitchConfig, ExchangeClient};
+///
+/// # async fn run(client: ExchangeClient) -> Result<(), hl_rs::Error> {
+/// let switch = DeadManSwitch::start(client.clone(), DeadManSwitchConfig::default()).await?;
+/// // ... quote ...
+/// switch.shutdown().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DeadManSwitch {
+    client: ExchangeClient,
+    state: Arc<Mutex<SwitchState>>,
+    stop: Option<oneshot::Sender<()>>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl DeadManSwitch {
+    /// Schedule the first cancel and start renewing it. Fails if the first schedule fails.
+    pub async fn start(client: ExchangeClient, config: DeadManSwitchConfig) -> Result<Self> {
+        config.validate()?;
+        let state = Arc::new(Mutex::new(SwitchState::new(&config)));
+        renew(&client, &state, &config).await?;
+
+        let (stop, stopped) = oneshot::channel();
+        let task = tokio::spawn(run(client.clone(), state.clone(), config, stopped));
+        Ok(Self {
+            client,
+            state,
+            stop: Some(stop),
+            task: Some(task),
+        })
+    }
+
+    /// Current state of the switch.
+    pub fn status(&self) -> DeadManStatus {
+        self.state.lock().unwrap().status(current_timestamp_ms())
+    }
+
+    /// Stop renewing, cancel every open order of the account now and unschedule the cancel.
+    pub async fn shutdown(mut self) -> Result<()> {
+        self.stop_renewing();
+        if let Some(task) = self.task.take() {
+            let _ = task.await;
+        }
+        disarm(&self.client, &self.state).await
+    }
+
+    fn stop_renewing(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+    }
+}
+
+impl Drop for DeadManSwitch {
+    fn drop(&mut self) {
+        let Some(task) = self.task.take() else {
+            return;
+        };
+        self.stop_renewing();
+        task.abort();
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                let client = self.client.clone();
+                let state = self.state.clone();
+                runtime.spawn(async move {
+                    if let Err(err) = disarm(&client, &state).await {
+                        tracing::warn!(target: "hl_rs::dead_man_switch", %err, "cancel on drop failed");
+                    }
+                });
+            }
+            Err(_) => tracing::warn!(
+                target: "hl_rs::dead_man_switch",
+                "dropped outside a Tokio runtime; orders are left to the scheduled cancel"
+            ),
+        }
+    }
+}
+
+async fn run(
+    client: ExchangeClient,
+    state: Arc<Mutex<SwitchState>>,
+    config: DeadManSwitchConfig,
+    mut stopped: oneshot::Receiver<()>,
+) {
+    let mut ticks = interval_at(
+        Instant::now() + config.renew_interval,
+        config.renew_interval,
+    );
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = &mut stopped => return,
+            _ = ticks.tick() => {}
+        }
+        if let Err(err) = renew(&client, &state, &config).await {
+            tracing::warn!(target: "hl_rs::dead_man_switch", %err, "failed to renew scheduled cancel");
+        }
+    }
+}
+
+/// Push the cancel time forward, counting the trigger if the last one was missed.
+async fn renew(
+    client: &ExchangeClient,
+    state: &Mutex<SwitchState>,
+    config: &DeadManSwitchConfig,
+) -> Result<()> {
+    let now = current_timestamp_ms();
+    let timeout = {
+        let mut state = state.lock().unwrap();
+        // The scheduled cancel fired and is gone; count it once.
+        if state.cancel_at.take_if(|at| *at <= now).is_some() {
+            state.budget.record(now);
+            tracing::warn!(
+                target: "hl_rs::dead_man_switch",
+                triggers_today = state.budget.used,
+                "scheduled cancel fired before it was renewed"
+            );
+        }
+        match state.budget.mode(now) {
+            DeadManMode::Armed => config.timeout,
+            _ => config.degraded_timeout,
+        }
+    };
+
+    let cancel_at = now + timeout.as_millis() as u64;
+    let result = client.send_action(ScheduleCancel::at(cancel_at)).await;
+    let mut state = state.lock().unwrap();
+    match result {
+        Ok(_) => {
+            state.cancel_at = Some(cancel_at);
+            state.last_error = None;
+            Ok(())
+        }
+        Err(err) => {
+            state.last_error = Some(err.to_string());
+            Err(err)
+        }
+    }
+}
+
+/// Cancel every open order of the account, then drop the scheduled cancel.
+async fn disarm(client: &ExchangeClient, state: &Mutex<SwitchState>) -> Result<()> {
+    state.lock().unwrap().stopped = true;
+
+    let user = client.account_address()?;
+    let open = client.info().open_orders(&user).await?;
+    if !open.is_empty() {
+        let registry = client.asset_registry().await?;
+        let cancels = open
+            .iter()
+            .map(|order| {
+                Ok(CancelWire {
+                    a: registry.asset(&order.coin)?,
+                    o: order.oid,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // Orders that filled in the meantime come back as per-order errors, which is fine.
+        client.send_action(BatchCancel::new(cancels)).await?;
+    }
+
+    // Without a time, `scheduleCancel` removes the scheduled cancel.
+    client.send_action(ScheduleCancel::now()).await?;
+    state.lock().unwrap().cancel_at = None;
+    Ok(())
+}
+
+#[derive(Debug)]
+struct SwitchState {
+    cancel_at: Option<u64>,
+    budget: TriggerBudget,
+    last_error: Option<String>,
+    stopped: bool,
+}
+
+impl SwitchState {
+    fn new(config: &DeadManSwitchConfig) -> Self {
+        Self {
+            cancel_at: None,
+            budget: TriggerBudget::new(config.daily_trigger_limit, config.reserved_triggers),
+            last_error: None,
+            stopped: false,
+        }
+    }
+
+    fn status(&self, now: u64) -> DeadManStatus {
+        DeadManStatus {
+            mode: if self.stopped {
+                DeadManMode::Stopped
+            } else {
+                self.budget.mode(now)
+            },
+            cancel_at: self.cancel_at,
+            triggers_today: self.budget.used_on(now),
+            last_error: self.last_error.clone(),
+        }
+    }
+}
+
+/// Scheduled cancels fired per UTC day.
+#[derive(Debug)]
+struct TriggerBudget {
+    limit: u32,
+    reserved: u32,
+    /// Days since the epoch of the count in `used`.
+    day: u64,
+    used: u32,
+}
+
+impl TriggerBudget {
+    fn new(limit: u32, reserved: u32) -> Self {
+        Self {
+            limit,
+            reserved,
+            day: 0,
+            used: 0,
+        }
+    }
+
+    fn record(&mut self, now: u64) {
+        self.used = self.used_on(now) + 1;
+        self.day = now / DAY_MS;
+    }
+
+    fn used_on(&self, now: u64) -> u32 {
+        if now / DAY_MS == self.day {
+            self.used
+        } else {
+            0
+        }
+    }
+
+    fn mode(&self, now: u64) -> DeadManMode {
+        let left = self.limit.saturating_sub(self.used_on(now));
+        if left == 0 {
+            DeadManMode::Exhausted
+        } else if left <= self.reserved {
+            DeadManMode::Degraded
+        } else {
+            DeadManMode::Armed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIDNIGHT: u64 = 1_700_006_400_000;
+
+    #[test]
+    fn trigger_budget_degrades_and_resets_at_midnight() {
+        let mut budget = TriggerBudget::new(10, 2);
+        let noon = MIDNIGHT + DAY_MS / 2;
+        for _ in 0..7 {
+            budget.record(noon);
+        }
+        assert_eq!(budget.mode(noon), DeadManMode::Armed);
+        budget.record(noon);
+        assert_eq!(budget.mode(noon), DeadManMode::Degraded);
+        budget.record(noon);
+        budget.record(noon);
+        assert_eq!(budget.used_on(noon), 10);
+        assert_eq!(budget.mode(noon), DeadManMode::Exhausted);
+
+        let next_day = MIDNIGHT + DAY_MS;
+        assert_eq!(budget.used_on(next_day), 0);
+        assert_eq!(budget.mode(next_day), DeadManMode::Armed);
+        budget.record(next_day);
+        assert_eq!(budget.used_on(next_day), 1);
+    }
+
+    #[test]
+    fn config_is_validated() {
+        assert!(DeadManSwitchConfig::default().validate().is_ok());
+        let short = DeadManSwitchConfig {
+            timeout: Duration::from_secs(4),
+            ..Default::default()
+        };
+        assert!(short.validate().is_err());
+        let slow = DeadManSwitchConfig {
+            renew_interval: Duration::from_secs(60),
+            ..Default::default()
+        };
+        assert!(slow.validate().is_err());
+    }
+}
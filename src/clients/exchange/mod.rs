@@ -2,3 +2,6 @@ pub mod responses;
 
 mod client;
 pub use client::ExchangeClient;
+
+mod dead_man_switch;
+pub use dead_man_switch::{DeadManMode, DeadManStatus, DeadManSwitch, DeadManSwitchConfig};
@@ -1,3 +1,7 @@
+use std::str::FromStr;
+
+use alloy::primitives::Address;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 use crate::{error::ApiError, Error};
@@ -16,26 +20,7 @@ impl ExchangeResponseStatusRaw {
     pub fn into_result(self) -> Result<ExchangeResponse, Error> {
         match self {
             ExchangeResponseStatusRaw::Ok(response) => Ok(response),
-            ExchangeResponseStatusRaw::Err(msg) => {
-                let api_error = if msg.contains("insufficient staked HYPE")
-                    || msg.contains("insufficient staked")
-                {
-                    ApiError::InsufficientStakedHype { message: msg }
-                } else {
-                    ApiError::Other { message: msg }
-                };
-                Err(Error::Api(api_error))
-                // } else if msg.contains("User or API Wallet") || msg.contains("does not exist") {
-                //     let address =
-                //         extract_address_from_error(&msg).unwrap_or_else(|| "unknown".to_string());
-                //     ApiError::WalletNotFound { address }
-                // } else if msg.contains("signature") || msg.contains("Signature") {
-                //     ApiError::SignatureMismatch { message: msg }
-                // } else {
-                //     ApiError::Other { message: msg }
-                // };
-                // Err(Error::ApiError(api_error))
-            }
+            ExchangeResponseStatusRaw::Err(msg) => Err(Error::Api(ApiError::classify(msg))),
         }
     }
 }
@@ -53,6 +38,36 @@ pub struct FilledOrder {
     pub oid: u64,
 }
 
+/// Fill of an immediately-matched order, with numbers parsed from [`FilledOrder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FillSummary {
+    pub oid: u64,
+    pub total_sz: Decimal,
+    pub avg_px: Decimal,
+}
+
+impl FillSummary {
+    /// Quote notional of the fill (`total_sz * avg_px`).
+    pub fn notional(&self) -> Decimal {
+        self.total_sz * self.avg_px
+    }
+}
+
+impl TryFrom<&FilledOrder> for FillSummary {
+    type Error = Error;
+
+    fn try_from(filled: &FilledOrder) -> Result<Self, Error> {
+        let parse = |value: &str| {
+            Decimal::from_str(value).map_err(|e| Error::GenericParse(format!("{value}: {e}")))
+        };
+        Ok(Self {
+            oid: filled.oid,
+            total_sz: parse(&filled.total_sz)?,
+            avg_px: parse(&filled.avg_px)?,
+        })
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum ExchangeDataStatus {
@@ -68,11 +83,179 @@ pub enum ExchangeDataStatus {
     Unknown,
 }
 
+/// Per-order statuses returned by `order` and `batchModify`, in request order.
+///
+/// A response can be `ok` while individual orders failed; those come back as
+/// [`ExchangeDataStatus::Error`].
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ExchangeDataStatuses {
     pub statuses: Vec<ExchangeDataStatus>,
 }
 
+impl ExchangeDataStatuses {
+    /// Error messages of the orders that were rejected, with their position in the batch.
+    pub fn errors(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.statuses
+            .iter()
+            .enumerate()
+            .filter_map(|(i, status)| match status {
+                ExchangeDataStatus::Error(message) => Some((i, message.as_str())),
+                _ => None,
+            })
+    }
+}
+
+impl ExchangeDataStatus {
+    /// Classified rejection when this order failed.
+    pub fn api_error(&self) -> Option<ApiError> {
+        match self {
+            ExchangeDataStatus::Error(message) => Some(ApiError::classify(message.as_str())),
+            _ => None,
+        }
+    }
+}
+
+/// Result of a single cancel within a `cancel` or `cancelByCloid` batch.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CancelStatus {
+    Success,
+    /// Cancel rejected (e.g. the order was already filled or canceled)
+    Error(String),
+}
+
+impl CancelStatus {
+    /// Classified rejection when this cancel failed.
+    pub fn api_error(&self) -> Option<ApiError> {
+        match self {
+            CancelStatus::Error(message) => Some(ApiError::classify(message.as_str())),
+            CancelStatus::Success => None,
+        }
+    }
+}
+
+/// Per-cancel statuses, in request order.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CancelStatuses {
+    pub statuses: Vec<CancelStatus>,
+}
+
+impl CancelStatuses {
+    /// Error messages of the cancels that were rejected, with their position in the batch.
+    pub fn errors(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.statuses
+            .iter()
+            .enumerate()
+            .filter_map(|(i, status)| match status {
+                CancelStatus::Error(message) => Some((i, message.as_str())),
+                CancelStatus::Success => None,
+            })
+    }
+}
+
+/// State of a TWAP order right after `twapOrder`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TwapOrderStatus {
+    Running {
+        #[serde(rename = "twapId")]
+        twap_id: u64,
+    },
+    /// TWAP rejected (e.g. duration out of range or insufficient margin)
+    Error(String),
+}
+
+/// Response to `twapOrder`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct TwapOrderResponse {
+    pub status: TwapOrderStatus,
+}
+
+impl TwapOrderResponse {
+    /// Id of the started TWAP, for [`TwapCancel`](crate::TwapCancel) and the TWAP feeds.
+    pub fn twap_id(&self) -> Option<u64> {
+        match self.status {
+            TwapOrderStatus::Running { twap_id } => Some(twap_id),
+            TwapOrderStatus::Error(_) => None,
+        }
+    }
+
+    /// Classified rejection when the TWAP was not started.
+    pub fn api_error(&self) -> Option<ApiError> {
+        match &self.status {
+            TwapOrderStatus::Error(message) => Some(ApiError::classify(message.as_str())),
+            TwapOrderStatus::Running { .. } => None,
+        }
+    }
+}
+
+/// Response to `twapCancel`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct TwapCancelResponse {
+    pub status: CancelStatus,
+}
+
+/// Messages returned by `perpDeploy` and `spotDeploy` actions.
+///
+/// Empty when the exchange acknowledges without a `setGlobal` payload.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct SetGlobalResponse {
+    pub messages: Vec<String>,
+}
+
+/// Conversion from the generic `ok` response into an action's typed response.
+pub trait ActionResponse: Sized {
+    fn from_response(response: ExchangeResponse) -> Result<Self, Error>;
+}
+
+impl ActionResponse for ExchangeResponse {
+    fn from_response(response: ExchangeResponse) -> Result<Self, Error> {
+        Ok(response)
+    }
+}
+
+impl ActionResponse for ExchangeDataStatuses {
+    fn from_response(response: ExchangeResponse) -> Result<Self, Error> {
+        response.parse_data("order")
+    }
+}
+
+impl ActionResponse for CancelStatuses {
+    fn from_response(response: ExchangeResponse) -> Result<Self, Error> {
+        response.parse_data("cancel")
+    }
+}
+
+impl ActionResponse for TwapOrderResponse {
+    fn from_response(response: ExchangeResponse) -> Result<Self, Error> {
+        response.parse_data("twapOrder")
+    }
+}
+
+impl ActionResponse for TwapCancelResponse {
+    fn from_response(response: ExchangeResponse) -> Result<Self, Error> {
+        response.parse_data("twapCancel")
+    }
+}
+
+impl ActionResponse for SetGlobalResponse {
+    fn from_response(response: ExchangeResponse) -> Result<Self, Error> {
+        Ok(Self {
+            messages: response.set_global_messages().unwrap_or_default(),
+        })
+    }
+}
+
+/// `createSubAccount` and `createVault` answer with the new account's address.
+impl ActionResponse for Address {
+    fn from_response(response: ExchangeResponse) -> Result<Self, Error> {
+        match response.response_type.as_str() {
+            "createVault" => response.parse_data("createVault"),
+            _ => response.parse_data("createSubAccount"),
+        }
+    }
+}
+
 /// Exchange API response. `data` is generic JSON so different action types
 /// (order, setGlobal, etc.) can return different structures without deserialization failures.
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -84,6 +267,20 @@ pub struct ExchangeResponse {
 }
 
 impl ExchangeResponse {
+    /// Deserialize `data`, checking that the response has the expected `type`.
+    fn parse_data<T: for<'de> Deserialize<'de>>(self, expected_type: &str) -> Result<T, Error> {
+        if self.response_type != expected_type {
+            return Err(Error::GenericParse(format!(
+                "expected `{expected_type}` response, got `{}`",
+                self.response_type
+            )));
+        }
+        let data = self.data.ok_or_else(|| {
+            Error::GenericParse(format!("`{expected_type}` response has no data"))
+        })?;
+        serde_json::from_value(data).map_err(|e| Error::JsonParse(e.to_string()))
+    }
+
     /// Parsed order statuses when `response_type` is `"order"` and `data` has `statuses`.
     pub fn order_data(&self) -> Option<ExchangeDataStatuses> {
         if self.response_type != "order" {
@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+
+use crate::{
+    info::InfoClient,
+    prelude::{Error, Result},
+    types::{Meta, SpotMeta},
+};
+
+/// Asset ids for spot pairs start here (`10000 + spot index`).
+pub const SPOT_ASSET_OFFSET: u32 = 10_000;
+/// Asset ids for builder-deployed (HIP-3) perp dexes start here.
+pub const PERP_DEX_ASSET_OFFSET: u32 = 100_000;
+/// Width of the asset id range reserved for each HIP-3 perp dex.
+pub const PERP_DEX_ASSET_STRIDE: u32 = 10_000;
+
+/// Whether an asset trades on a perp dex or the spot book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetKind {
+    Perp,
+    Spot,
+}
+
+/// Everything needed to address and size orders for a single asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetInfo {
+    /// Asset id used on the wire (`OrderWire.asset`, `CancelWire.a`, ...).
+    pub asset: u32,
+    /// Coin name as it appears in info and ws responses (`"BTC"`, `"@107"`, `"mydex:BTC"`).
+    pub coin: String,
+    pub kind: AssetKind,
+    /// Perp dex name for HIP-3 assets, `None` for the default dex and spot.
+    pub dex: Option<String>,
+    pub sz_decimals: u32,
+    /// Maximum leverage, `None` for spot.
+    pub max_leverage: Option<usize>,
+    pub only_isolated: bool,
+}
+
+/// Resolves coin names to asset ids across the default perp dex, spot, and HIP-3 perp dexes.
+///
+/// Asset ids follow the exchange's numbering:
+/// - default perp dex: index in `meta.universe`
+/// - spot: `10000 + index` in `spotMeta.universe`
+/// - HIP-3 perp dex: `100000 + perp_dex_index * 10000 + index` in that dex's `meta.universe`,
+///   where `perp_dex_index` is the dex's position in `perpDexs` (the default dex is 0)
+///
+/// Spot pairs resolve by pair name (`"PURR/USDC"`), by `@index` (`"@107"`), and by their
+/// `BASE/QUOTE` token names. HIP-3 assets resolve as `"dex:COIN"`.
+///
+/// # Example
+/// ```no_run
+/// use hl_rs::{AssetRegistry, BaseUrl, InfoClient};
+///
+/// # async fn run() -> Result<(), hl_rs::Error> {
+/// let info = InfoClient::builder(BaseUrl::Testnet).build()?;
+/// let registry = AssetRegistry::load(&info).await?;
+///
+/// let btc = registry.asset("BTC")?;
+/// let purr = registry.asset("PURR/USDC")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AssetRegistry {
+    assets: Vec<AssetInfo>,
+    by_name: HashMap<String, usize>,
+    by_asset: HashMap<u32, usize>,
+}
+
+impl AssetRegistry {
+    /// Build a registry from already-fetched metadata.
+    ///
+    /// `perp_dex_metas` holds the `meta` of each HIP-3 dex, in `perpDexs` order and without
+    /// the default dex.
+    pub fn from_meta(meta: &Meta, spot_meta: &SpotMeta, perp_dex_metas: &[(String, Meta)]) -> Self {
+        let mut registry = Self::default();
+
+        for (index, asset) in meta.universe.iter().enumerate() {
+            registry.insert(
+                AssetInfo {
+                    asset: index as u32,
+                    coin: asset.name.clone(),
+                    kind: AssetKind::Perp,
+                    dex: None,
+                    sz_decimals: asset.sz_decimals,
+                    max_leverage: Some(asset.max_leverage),
+                    only_isolated: asset.only_isolated.unwrap_or(false),
+                },
+                &[],
+            );
+        }
+
+        let tokens: HashMap<usize, _> = spot_meta
+            .tokens
+            .iter()
+            .map(|token| (token.index, token))
+            .collect();
+        for pair in &spot_meta.universe {
+            let (Some(base), Some(quote)) =
+                (tokens.get(&pair.tokens[0]), tokens.get(&pair.tokens[1]))
+            else {
+                continue;
+            };
+            let aliases = [
+                format!("@{}", pair.index),
+                format!("{}/{}", base.name, quote.name),
+            ];
+            registry.insert(
+                AssetInfo {
+                    asset: SPOT_ASSET_OFFSET + pair.index as u32,
+                    coin: pair.name.clone(),
+                    kind: AssetKind::Spot,
+                    dex: None,
+                    sz_decimals: base.sz_decimals as u32,
+                    max_leverage: None,
+                    only_isolated: false,
+                },
+                &aliases,
+            );
+        }
+
+        for (position, (dex, dex_meta)) in perp_dex_metas.iter().enumerate() {
+            let base = PERP_DEX_ASSET_OFFSET + (position as u32 + 1) * PERP_DEX_ASSET_STRIDE;
+            for (index, asset) in dex_meta.universe.iter().enumerate() {
+                // HIP-3 metas already report prefixed names, but don't rely on it.
+                let coin = if asset.name.contains(':') {
+                    asset.name.clone()
+                } else {
+                    format!("{dex}:{}", asset.name)
+                };
+                registry.insert(
+                    AssetInfo {
+                        asset: base + index as u32,
+                        coin,
+                        kind: AssetKind::Perp,
+                        dex: Some(dex.clone()),
+                        sz_decimals: asset.sz_decimals,
+                        max_leverage: Some(asset.max_leverage),
+                        only_isolated: asset.only_isolated.unwrap_or(false),
+                    },
+                    &[],
+                );
+            }
+        }
+
+        registry
+    }
+
+    /// Fetch `meta`, `spotMeta`, `perpDexs` and every HIP-3 dex `meta`, and build a registry.
+    pub async fn load(info: &InfoClient) -> Result<Self> {
+        let meta = info.meta().await?;
+        let spot_meta = info.spot_meta().await?;
+
+        let mut perp_dex_metas = Vec::new();
+        for dex in info.perp_dexs().await? {
+            let dex_meta = info.perp_dex_meta(&dex.name).await?;
+            perp_dex_metas.push((dex.name, dex_meta));
+        }
+
+        Ok(Self::from_meta(&meta, &spot_meta, &perp_dex_metas))
+    }
+
+    /// Re-fetch all metadata, replacing the current mapping.
+    pub async fn refresh(&mut self, info: &InfoClient) -> Result<()> {
+        *self = Self::load(info).await?;
+        Ok(())
+    }
+
+    /// Asset id for a coin name.
+    pub fn asset(&self, coin: &str) -> Result<u32> {
+        self.info(coin).map(|info| info.asset)
+    }
+
+    /// Full asset info for a coin name.
+    pub fn info(&self, coin: &str) -> Result<&AssetInfo> {
+        self.get(coin)
+            .ok_or_else(|| Error::UnknownCoin(coin.to_string()))
+    }
+
+    /// Full asset info for a coin name, `None` if it is not listed.
+    pub fn get(&self, coin: &str) -> Option<&AssetInfo> {
+        self.by_name.get(coin).map(|&i| &self.assets[i])
+    }
+
+    /// Reverse lookup from an asset id.
+    pub fn by_asset(&self, asset: u32) -> Option<&AssetInfo> {
+        self.by_asset.get(&asset).map(|&i| &self.assets[i])
+    }
+
+    /// All known assets.
+    pub fn iter(&self) -> impl Iterator<Item = &AssetInfo> {
+        self.assets.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.assets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty()
+    }
+
+    fn insert(&mut self, info: AssetInfo, aliases: &[String]) {
+        let i = self.assets.len();
+        self.by_name.insert(info.coin.clone(), i);
+        for alias in aliases {
+            self.by_name.entry(alias.clone()).or_insert(i);
+        }
+        self.by_asset.insert(info.asset, i);
+        self.assets.push(info);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(json: &str) -> Meta {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn registry() -> AssetRegistry {
+        let perp = meta(
+            r#"{"universe":[
+                {"name":"BTC","szDecimals":5,"maxLeverage":40},
+                {"name":"ETH","szDecimals":4,"maxLeverage":25},
+                {"name":"HYPE","szDecimals":2,"maxLeverage":10,"onlyIsolated":true}
+            ]}"#,
+        );
+        let spot: SpotMeta = serde_json::from_str(
+            r#"{
+                "universe":[
+                    {"tokens":[1,0],"name":"PURR/USDC","index":0,"isCanonical":true},
+                    {"tokens":[150,0],"name":"@107","index":107,"isCanonical":false}
+                ],
+                "tokens":[
+                    {"name":"USDC","szDecimals":8,"weiDecimals":8,"index":0,"tokenId":"0x6d1e7cde53ba9467b783cb7c530ce054","isCanonical":true},
+                    {"name":"PURR","szDecimals":0,"weiDecimals":5,"index":1,"tokenId":"0xc1fb593aeffbeb02f85e0308e9956a90","isCanonical":true},
+                    {"name":"HYPE","szDecimals":2,"weiDecimals":8,"index":150,"tokenId":"0x0d01dc56dcaaca66ad901c959b4011ec","isCanonical":false}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let mydex = meta(
+            r#"{"universe":[
+                {"name":"mydex:BTC","szDecimals":3,"maxLeverage":20},
+                {"name":"mydex:GOLD","szDecimals":2,"maxLeverage":10}
+            ]}"#,
+        );
+        let other = meta(r#"{"universe":[{"name":"NVDA","szDecimals":1,"maxLeverage":5}]}"#);
+
+        AssetRegistry::from_meta(
+            &perp,
+            &spot,
+            &[("mydex".to_string(), mydex), ("other".to_string(), other)],
+        )
+    }
+
+    #[test]
+    fn resolves_default_perps() {
+        let registry = registry();
+        assert_eq!(registry.asset("BTC").unwrap(), 0);
+        assert_eq!(registry.asset("HYPE").unwrap(), 2);
+
+        let eth = registry.info("ETH").unwrap();
+        assert_eq!(eth.kind, AssetKind::Perp);
+        assert_eq!(eth.sz_decimals, 4);
+        assert_eq!(eth.max_leverage, Some(25));
+        assert!(registry.info("HYPE").unwrap().only_isolated);
+    }
+
+    #[test]
+    fn resolves_spot_by_name_index_and_tokens() {
+        let registry = registry();
+        assert_eq!(registry.asset("PURR/USDC").unwrap(), 10_000);
+        assert_eq!(registry.asset("@0").unwrap(), 10_000);
+        assert_eq!(registry.asset("@107").unwrap(), 10_107);
+        assert_eq!(registry.asset("HYPE/USDC").unwrap(), 10_107);
+
+        let hype = registry.info("@107").unwrap();
+        assert_eq!(hype.kind, AssetKind::Spot);
+        assert_eq!(hype.coin, "@107");
+        assert_eq!(hype.sz_decimals, 2);
+        assert_eq!(hype.max_leverage, None);
+    }
+
+    #[test]
+    fn resolves_hip3_dexes_with_offsets() {
+        let registry = registry();
+        assert_eq!(registry.asset("mydex:BTC").unwrap(), 110_000);
+        assert_eq!(registry.asset("mydex:GOLD").unwrap(), 110_001);
+        assert_eq!(registry.asset("other:NVDA").unwrap(), 120_000);
+
+        let gold = registry.info("mydex:GOLD").unwrap();
+        assert_eq!(gold.dex.as_deref(), Some("mydex"));
+        assert_eq!(gold.sz_decimals, 2);
+    }
+
+    #[test]
+    fn reverse_lookup_returns_canonical_coin() {
+        let registry = registry();
+        assert_eq!(registry.by_asset(10_107).unwrap().coin, "@107");
+        assert_eq!(registry.by_asset(120_000).unwrap().coin, "other:NVDA");
+        assert!(registry.by_asset(3).is_none());
+    }
+
+    #[test]
+    fn unknown_coin_is_an_error() {
+        let err = registry().asset("DOGE").unwrap_err();
+        assert!(matches!(err, Error::UnknownCoin(coin) if coin == "DOGE"));
+    }
+}
@@ -1,15 +1,28 @@
+use std::collections::HashMap;
+
 use alloy::primitives::Address;
 use serde::Deserialize;
 
 use crate::{
+    actions::{Cloid, MultiSigSigners},
     error::ApiError,
     http::HttpClient,
     info::{
         client_builder::InfoClientBuilder,
-        types::{InfoRequest, UserRoleResponse},
+        types::{
+            ActiveAssetDataResponse, Candle, CandleInterval, CandleSnapshotRequest, Delegation,
+            DelegatorEvent, DelegatorReward, FundingHistoryResponse, InfoRequest,
+            L2SnapshotResponse, OpenOrdersResponse, OrderId, OrderInfo, OrderStatusResponse,
+            RecentTradesResponse, ReferralResponse, UserFeesResponse, UserFillsResponse,
+            UserFundingResponse, UserRoleResponse, UserStateResponse, UserTokenBalanceResponse,
+            UserVaultEquity, ValidatorSummary, VaultDetailsResponse,
+        },
     },
     prelude::{Error, Result},
-    types::{Meta, PerpDeployAuctionStatus, PerpDex, PerpDexStatus, SpotMeta, UserStakingSummary},
+    types::{
+        AssetContext, Meta, PerpDeployAuctionStatus, PerpDex, PerpDexStatus, SpotAssetContext,
+        SpotMeta, UserStakingSummary,
+    },
     BaseUrl,
 };
 
@@ -32,7 +45,15 @@ impl InfoClient {
     }
 
     pub async fn meta(&self) -> Result<Meta> {
-        self.send_request(InfoRequest::Meta).await
+        self.send_request(InfoRequest::Meta { dex: None }).await
+    }
+
+    /// Perp universe of a HIP-3 perp dex. Coin names come back prefixed (`"mydex:BTC"`).
+    pub async fn perp_dex_meta(&self, dex: &str) -> Result<Meta> {
+        self.send_request(InfoRequest::Meta {
+            dex: Some(dex.to_string()),
+        })
+        .await
     }
 
     pub async fn spot_meta(&self) -> Result<SpotMeta> {
@@ -46,6 +67,201 @@ impl InfoClient {
         .await
     }
 
+    /// Stake `user` has delegated, per validator.
+    pub async fn delegations(&self, user: &Address) -> Result<Vec<Delegation>> {
+        self.send_request(InfoRequest::Delegations {
+            user: user.to_owned(),
+        })
+        .await
+    }
+
+    /// Staking rewards `user` has accrued, newest first.
+    pub async fn delegator_rewards(&self, user: &Address) -> Result<Vec<DelegatorReward>> {
+        self.send_request(InfoRequest::DelegatorRewards {
+            user: user.to_owned(),
+        })
+        .await
+    }
+
+    /// Deposits, withdrawals and (un)delegations of `user`'s staking balance, newest first.
+    pub async fn delegator_history(&self, user: &Address) -> Result<Vec<DelegatorEvent>> {
+        self.send_request(InfoRequest::DelegatorHistory {
+            user: user.to_owned(),
+        })
+        .await
+    }
+
+    /// All validators with their stake, commission and recent performance.
+    pub async fn validator_summaries(&self) -> Result<Vec<ValidatorSummary>> {
+        self.send_request(InfoRequest::ValidatorSummaries).await
+    }
+
+    /// Perp clearinghouse state (positions, margin summaries) for a user on the default perp
+    /// dex, or on the HIP-3 perp dex `dex`. Positions on a HIP-3 dex (`"mydex:BTC"`) only
+    /// appear in that dex's state.
+    pub async fn user_state(&self, user: &Address, dex: Option<&str>) -> Result<UserStateResponse> {
+        self.send_request(InfoRequest::UserState {
+            user: user.to_owned(),
+            dex: dex.map(str::to_string),
+        })
+        .await
+    }
+
+    /// Perp clearinghouse states for several users in one request.
+    pub async fn user_states(&self, users: &[Address]) -> Result<Vec<UserStateResponse>> {
+        self.send_request(InfoRequest::UserStates {
+            users: users.to_vec(),
+        })
+        .await
+    }
+
+    /// Spot token balances for a user.
+    pub async fn user_token_balances(&self, user: &Address) -> Result<UserTokenBalanceResponse> {
+        self.send_request(InfoRequest::UserTokenBalances {
+            user: user.to_owned(),
+        })
+        .await
+    }
+
+    pub async fn user_fees(&self, user: &Address) -> Result<UserFeesResponse> {
+        self.send_request(InfoRequest::UserFees {
+            user: user.to_owned(),
+        })
+        .await
+    }
+
+    pub async fn open_orders(&self, user: &Address) -> Result<Vec<OpenOrdersResponse>> {
+        self.send_request(InfoRequest::OpenOrders {
+            user: user.to_owned(),
+        })
+        .await
+    }
+
+    /// Look up a single order by oid. `order` is `None` when the oid is unknown.
+    pub async fn order_status(&self, user: &Address, oid: u64) -> Result<OrderStatusResponse> {
+        self.send_request(InfoRequest::OrderStatus {
+            user: user.to_owned(),
+            oid: OrderId::Oid(oid),
+        })
+        .await
+    }
+
+    /// Look up a single order by client order id. `order` is `None` when the cloid is unknown.
+    pub async fn order_status_by_cloid(
+        &self,
+        user: &Address,
+        cloid: Cloid,
+    ) -> Result<OrderStatusResponse> {
+        self.send_request(InfoRequest::OrderStatus {
+            user: user.to_owned(),
+            oid: OrderId::Cloid(cloid),
+        })
+        .await
+    }
+
+    /// Perp universe together with the per-asset contexts (mark, funding, OI, ...).
+    pub async fn meta_and_asset_ctxs(&self) -> Result<(Meta, Vec<AssetContext>)> {
+        self.send_request(InfoRequest::MetaAndAssetCtxs).await
+    }
+
+    /// Spot universe together with the per-pair contexts.
+    pub async fn spot_meta_and_asset_ctxs(&self) -> Result<(SpotMeta, Vec<SpotAssetContext>)> {
+        self.send_request(InfoRequest::SpotMetaAndAssetCtxs).await
+    }
+
+    /// Mid price for every coin, keyed by coin name (`"BTC"`, `"@107"`, ...).
+    pub async fn all_mids(&self) -> Result<HashMap<String, String>> {
+        self.send_request(InfoRequest::AllMids).await
+    }
+
+    pub async fn user_fills(&self, user: &Address) -> Result<Vec<UserFillsResponse>> {
+        self.send_request(InfoRequest::UserFills {
+            user: user.to_owned(),
+        })
+        .await
+    }
+
+    pub async fn funding_history(
+        &self,
+        coin: &str,
+        start_time: u64,
+        end_time: Option<u64>,
+    ) -> Result<Vec<FundingHistoryResponse>> {
+        self.send_request(InfoRequest::FundingHistory {
+            coin: coin.to_string(),
+            start_time,
+            end_time,
+        })
+        .await
+    }
+
+    pub async fn user_funding(
+        &self,
+        user: &Address,
+        start_time: u64,
+        end_time: Option<u64>,
+    ) -> Result<Vec<UserFundingResponse>> {
+        self.send_request(InfoRequest::UserFunding {
+            user: user.to_owned(),
+            start_time,
+            end_time,
+        })
+        .await
+    }
+
+    pub async fn l2_book(&self, coin: &str) -> Result<L2SnapshotResponse> {
+        self.send_request(InfoRequest::L2Book {
+            coin: coin.to_string(),
+        })
+        .await
+    }
+
+    pub async fn recent_trades(&self, coin: &str) -> Result<Vec<RecentTradesResponse>> {
+        self.send_request(InfoRequest::RecentTrades {
+            coin: coin.to_string(),
+        })
+        .await
+    }
+
+    pub async fn candle_snapshot(
+        &self,
+        coin: &str,
+        interval: CandleInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<Candle>> {
+        self.send_request(InfoRequest::CandleSnapshot {
+            req: CandleSnapshotRequest::new(coin, interval, start_time, end_time),
+        })
+        .await
+    }
+
+    pub async fn referral(&self, user: &Address) -> Result<ReferralResponse> {
+        self.send_request(InfoRequest::Referral {
+            user: user.to_owned(),
+        })
+        .await
+    }
+
+    pub async fn historical_orders(&self, user: &Address) -> Result<Vec<OrderInfo>> {
+        self.send_request(InfoRequest::HistoricalOrders {
+            user: user.to_owned(),
+        })
+        .await
+    }
+
+    pub async fn active_asset_data(
+        &self,
+        user: &Address,
+        coin: &str,
+    ) -> Result<ActiveAssetDataResponse> {
+        self.send_request(InfoRequest::ActiveAssetData {
+            user: user.to_owned(),
+            coin: coin.to_string(),
+        })
+        .await
+    }
+
     pub async fn perp_dexs(&self) -> Result<Vec<PerpDex>> {
         use serde_json::Value;
 
@@ -92,6 +308,39 @@ impl InfoClient {
         })
         .await
     }
+
+    /// Signer config of a multi-sig user, `None` if `user` is not a multi-sig user.
+    pub async fn user_to_multi_sig_signers(
+        &self,
+        user: &Address,
+    ) -> Result<Option<MultiSigSigners>> {
+        self.send_request(InfoRequest::UserToMultiSigSigners {
+            user: user.to_owned(),
+        })
+        .await
+    }
+
+    /// Summary, followers and performance of a vault. With `user`, also fills in
+    /// [`follower_state`](VaultDetailsResponse::follower_state) for that user.
+    pub async fn vault_details(
+        &self,
+        vault_address: &Address,
+        user: Option<&Address>,
+    ) -> Result<VaultDetailsResponse> {
+        self.send_request(InfoRequest::VaultDetails {
+            vault_address: vault_address.to_owned(),
+            user: user.copied(),
+        })
+        .await
+    }
+
+    /// Equity of `user` in each vault they follow.
+    pub async fn user_vault_equities(&self, user: &Address) -> Result<Vec<UserVaultEquity>> {
+        self.send_request(InfoRequest::UserVaultEquities {
+            user: user.to_owned(),
+        })
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -1,11 +1,14 @@
 use reqwest::Client;
 
-use crate::{http::HttpClient, info::InfoClient, prelude::Result, types::BaseUrl};
+use crate::{
+    cassette::Recorder, http::HttpClient, info::InfoClient, prelude::Result, types::BaseUrl,
+};
 
 #[derive(Debug, Clone)]
 pub struct InfoClientBuilder {
     base_url: BaseUrl,
     http_client: Option<HttpClient>,
+    recorder: Option<Recorder>,
 }
 
 impl InfoClientBuilder {
@@ -13,6 +16,7 @@ impl InfoClientBuilder {
         Self {
             base_url,
             http_client: None,
+            recorder: None,
         }
     }
 
@@ -21,11 +25,21 @@ impl InfoClientBuilder {
         self
     }
 
+    /// Record every request and response to `recorder`'s cassette.
+    pub fn with_recorder(mut self, recorder: Recorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
     pub fn build(self) -> Result<InfoClient> {
-        let http_client = self.http_client.unwrap_or(HttpClient {
+        let mut http_client = self.http_client.unwrap_or(HttpClient {
             client: Client::default(),
             base_url: self.base_url.get_url(),
+            recorder: None,
         });
+        if let Some(recorder) = self.recorder {
+            http_client.recorder = Some(recorder);
+        }
 
         Ok(InfoClient { http_client })
     }
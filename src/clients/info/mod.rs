@@ -1,6 +1,8 @@
 pub mod types;
 
+mod asset_registry;
 mod client;
 mod client_builder;
 
+pub use asset_registry::{AssetInfo, AssetKind, AssetRegistry};
 pub use client::InfoClient;
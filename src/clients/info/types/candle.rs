@@ -0,0 +1,307 @@
+//! Candle intervals and the candle type shared by `candleSnapshot` and the `candle` feed.
+
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+const SECOND_MS: u64 = 1_000;
+const MINUTE_MS: u64 = 60 * SECOND_MS;
+const HOUR_MS: u64 = 60 * MINUTE_MS;
+const DAY_MS: u64 = 24 * HOUR_MS;
+const WEEK_MS: u64 = 7 * DAY_MS;
+
+/// Candle length.
+///
+/// The named variants are the intervals the API serves, written as on the wire (`"1m"`,
+/// `"4h"`, `"1M"`, ...). [`Custom`](Self::Custom) bars (e.g. 10s) can only be built locally,
+/// see `CandleBuilder`.
+///
+/// Buckets are aligned to the Unix epoch, except weeks (Monday 00:00 UTC) and months (first
+/// of the month, UTC).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum CandleInterval {
+    OneMinute,
+    ThreeMinutes,
+    FiveMinutes,
+    FifteenMinutes,
+    ThirtyMinutes,
+    OneHour,
+    TwoHours,
+    FourHours,
+    EightHours,
+    TwelveHours,
+    OneDay,
+    ThreeDays,
+    OneWeek,
+    OneMonth,
+    /// Fixed length in milliseconds. Use [`from_millis`](Self::from_millis) so lengths the API
+    /// serves map to their named variant.
+    Custom(u64),
+}
+
+/// Named intervals with a fixed length, as (variant, wire name, length in ms).
+const FIXED: [(CandleInterval, &str, u64); 13] = [
+    (CandleInterval::OneMinute, "1m", MINUTE_MS),
+    (CandleInterval::ThreeMinutes, "3m", 3 * MINUTE_MS),
+    (CandleInterval::FiveMinutes, "5m", 5 * MINUTE_MS),
+    (CandleInterval::FifteenMinutes, "15m", 15 * MINUTE_MS),
+    (CandleInterval::ThirtyMinutes, "30m", 30 * MINUTE_MS),
+    (CandleInterval::OneHour, "1h", HOUR_MS),
+    (CandleInterval::TwoHours, "2h", 2 * HOUR_MS),
+    (CandleInterval::FourHours, "4h", 4 * HOUR_MS),
+    (CandleInterval::EightHours, "8h", 8 * HOUR_MS),
+    (CandleInterval::TwelveHours, "12h", 12 * HOUR_MS),
+    (CandleInterval::OneDay, "1d", DAY_MS),
+    (CandleInterval::ThreeDays, "3d", 3 * DAY_MS),
+    (CandleInterval::OneWeek, "1w", WEEK_MS),
+];
+
+impl CandleInterval {
+    /// Interval of `ms` milliseconds: the named variant if the API serves that length,
+    /// otherwise [`Custom`](Self::Custom).
+    pub fn from_millis(ms: u64) -> Self {
+        FIXED
+            .iter()
+            .find(|(_, _, len)| *len == ms)
+            .map_or(Self::Custom(ms), |(interval, _, _)| *interval)
+    }
+
+    /// Interval of `secs` seconds, see [`from_millis`](Self::from_millis).
+    pub fn from_secs(secs: u64) -> Self {
+        Self::from_millis(secs * SECOND_MS)
+    }
+
+    /// Whether the API serves candles of this interval.
+    pub fn is_api(&self) -> bool {
+        !matches!(self, Self::Custom(_))
+    }
+
+    /// Length in milliseconds, `None` for months.
+    pub fn millis(&self) -> Option<u64> {
+        match self {
+            Self::OneMonth => None,
+            Self::Custom(ms) => Some(*ms),
+            named => FIXED
+                .iter()
+                .find(|(interval, _, _)| interval == named)
+                .map(|(_, _, len)| *len),
+        }
+    }
+
+    /// Offset of bucket boundaries from the epoch (the epoch was a Thursday).
+    fn offset_ms(&self) -> u64 {
+        match self {
+            Self::OneWeek => 4 * DAY_MS,
+            _ => 0,
+        }
+    }
+
+    /// Open time (ms) of the bucket containing `time_ms`.
+    pub fn bucket_start(&self, time_ms: u64) -> u64 {
+        match self.millis() {
+            Some(len) => {
+                let len = len.max(1);
+                time_ms - (time_ms + len - self.offset_ms() % len) % len
+            }
+            None => {
+                let date = utc(time_ms);
+                Utc.with_ymd_and_hms(date.year(), date.month(), 1, 0, 0, 0)
+                    .unwrap()
+                    .timestamp_millis() as u64
+            }
+        }
+    }
+
+    /// Open time (ms) of the bucket after the one starting at `start_ms`.
+    pub fn next_bucket(&self, start_ms: u64) -> u64 {
+        match self.millis() {
+            Some(len) => start_ms + len.max(1),
+            None => {
+                let date = utc(start_ms);
+                let (year, month) = match date.month() {
+                    12 => (date.year() + 1, 1),
+                    month => (date.year(), month + 1),
+                };
+                Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0)
+                    .unwrap()
+                    .timestamp_millis() as u64
+            }
+        }
+    }
+
+    /// Whether every bucket of `target` is made of whole buckets of `self`.
+    pub fn divides(&self, target: CandleInterval) -> bool {
+        match (self.millis(), target.millis()) {
+            (Some(len), Some(target_len)) => {
+                len > 0
+                    && target_len.is_multiple_of(len)
+                    && target
+                        .offset_ms()
+                        .abs_diff(self.offset_ms())
+                        .is_multiple_of(len)
+            }
+            (Some(len), None) => len > 0 && DAY_MS.is_multiple_of(len) && self.offset_ms() == 0,
+            (None, None) => true,
+            (None, Some(_)) => false,
+        }
+    }
+}
+
+fn utc(time_ms: u64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(time_ms as i64).unwrap_or_default()
+}
+
+impl fmt::Display for CandleInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OneMonth => f.write_str("1M"),
+            Self::Custom(ms) if ms.is_multiple_of(SECOND_MS) => write!(f, "{}s", ms / SECOND_MS),
+            Self::Custom(ms) => write!(f, "{ms}ms"),
+            named => {
+                let (_, name, _) = FIXED.iter().find(|(i, _, _)| i == named).unwrap();
+                f.write_str(name)
+            }
+        }
+    }
+}
+
+impl FromStr for CandleInterval {
+    type Err = Error;
+
+    /// Parse a wire name (`"15m"`, `"1M"`, ...) or a custom length in seconds or
+    /// milliseconds (`"10s"`, `"250ms"`).
+    fn from_str(s: &str) -> Result<Self, Error> {
+        if s == "1M" {
+            return Ok(Self::OneMonth);
+        }
+        if let Some((interval, _, _)) = FIXED.iter().find(|(_, name, _)| *name == s) {
+            return Ok(*interval);
+        }
+        let parse = |n: &str| {
+            n.parse::<u64>()
+                .ok()
+                .filter(|n| *n > 0)
+                .ok_or_else(|| Error::GenericParse(format!("invalid candle interval: {s}")))
+        };
+        match (s.strip_suffix("ms"), s.strip_suffix('s')) {
+            (Some(ms), _) => parse(ms).map(Self::from_millis),
+            (None, Some(secs)) => parse(secs).map(Self::from_secs),
+            _ => Err(Error::GenericParse(format!("invalid candle interval: {s}"))),
+        }
+    }
+}
+
+impl TryFrom<String> for CandleInterval {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Error> {
+        value.parse()
+    }
+}
+
+impl From<CandleInterval> for String {
+    fn from(interval: CandleInterval) -> Self {
+        interval.to_string()
+    }
+}
+
+/// OHLCV candle, as returned by `candleSnapshot` and streamed on the `candle` channel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    /// Open time (ms).
+    #[serde(rename = "t")]
+    pub open_ms: u64,
+    /// Close time (ms), the last millisecond of the bucket.
+    #[serde(rename = "T")]
+    pub close_ms: u64,
+    #[serde(rename = "s")]
+    pub coin: String,
+    #[serde(rename = "i")]
+    pub interval: CandleInterval,
+    #[serde(rename = "o")]
+    pub open: Decimal,
+    #[serde(rename = "c")]
+    pub close: Decimal,
+    #[serde(rename = "h")]
+    pub high: Decimal,
+    #[serde(rename = "l")]
+    pub low: Decimal,
+    #[serde(rename = "v")]
+    pub volume: Decimal,
+    #[serde(rename = "n")]
+    pub num_trades: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-03-15 12:34:56.789 UTC, a Friday.
+    const T: u64 = 1_710_506_096_789;
+
+    #[test]
+    fn intervals_round_trip_wire_names() {
+        for name in ["1m", "15m", "4h", "1d", "3d", "1w", "1M", "10s", "250ms"] {
+            let interval: CandleInterval = name.parse().unwrap();
+            assert_eq!(interval.to_string(), name);
+        }
+        assert_eq!(
+            "60s".parse::<CandleInterval>().unwrap(),
+            CandleInterval::OneMinute
+        );
+        assert_eq!(
+            serde_json::to_value(CandleInterval::FifteenMinutes).unwrap(),
+            "15m"
+        );
+        assert!("2x".parse::<CandleInterval>().is_err());
+        assert!("0s".parse::<CandleInterval>().is_err());
+    }
+
+    #[test]
+    fn buckets_align_to_calendar() {
+        assert_eq!(
+            CandleInterval::from_secs(10).bucket_start(T),
+            1_710_506_090_000
+        );
+        assert_eq!(CandleInterval::OneHour.bucket_start(T), 1_710_504_000_000);
+        // Monday 2024-03-11.
+        assert_eq!(CandleInterval::OneWeek.bucket_start(T), 1_710_115_200_000);
+        // 2024-03-01 and 2024-04-01.
+        let month = CandleInterval::OneMonth.bucket_start(T);
+        assert_eq!(month, 1_709_251_200_000);
+        assert_eq!(
+            CandleInterval::OneMonth.next_bucket(month),
+            1_711_929_600_000
+        );
+    }
+
+    #[test]
+    fn divides_checks_length_and_alignment() {
+        use CandleInterval::*;
+        assert!(OneMinute.divides(FifteenMinutes));
+        assert!(CandleInterval::from_secs(10).divides(OneMinute));
+        assert!(OneDay.divides(OneWeek));
+        assert!(OneHour.divides(OneMonth));
+        assert!(!FiveMinutes.divides(ThreeMinutes));
+        assert!(!ThreeDays.divides(OneMonth));
+        assert!(!OneWeek.divides(CandleInterval::Custom(2 * WEEK_MS)));
+        assert!(!OneMonth.divides(OneWeek));
+    }
+
+    #[test]
+    fn candle_parses_snapshot_shape() {
+        let candle: Candle = serde_json::from_value(serde_json::json!({
+            "t": 1681923600000u64, "T": 1681924499999u64, "s": "BTC", "i": "15m",
+            "o": "29295.0", "c": "29258.0", "h": "29309.0", "l": "29250.0",
+            "v": "0.98639", "n": 189,
+        }))
+        .unwrap();
+        assert_eq!(candle.interval, CandleInterval::FifteenMinutes);
+        assert_eq!(candle.volume, Decimal::new(98639, 5));
+    }
+}
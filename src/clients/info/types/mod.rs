@@ -1,5 +1,7 @@
+mod candle;
 mod request;
 mod response;
 
+pub use candle::*;
 pub use request::*;
 pub use response::*;
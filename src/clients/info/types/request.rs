@@ -1,6 +1,9 @@
 use alloy::primitives::Address;
 use serde::{Deserialize, Serialize};
 
+use super::CandleInterval;
+use crate::Cloid;
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(tag = "type")]
 #[serde(rename_all = "camelCase")]
@@ -8,6 +11,9 @@ pub enum InfoRequest {
     #[serde(rename = "clearinghouseState")]
     UserState {
         user: Address,
+        /// HIP-3 perp dex name; the default dex when `None`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        dex: Option<String>,
     },
     #[serde(rename = "batchClearinghouseStates")]
     UserStates {
@@ -30,9 +36,13 @@ pub enum InfoRequest {
     },
     OrderStatus {
         user: Address,
-        oid: u64,
+        oid: OrderId,
+    },
+    Meta {
+        /// HIP-3 perp dex name; the default dex when `None`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        dex: Option<String>,
     },
-    Meta,
     MetaAndAssetCtxs,
     SpotMeta,
     SpotMetaAndAssetCtxs,
@@ -80,13 +90,71 @@ pub enum InfoRequest {
     UserRole {
         user: Address,
     },
+    UserToMultiSigSigners {
+        user: Address,
+    },
+    #[serde(rename_all = "camelCase")]
+    VaultDetails {
+        vault_address: Address,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        user: Option<Address>,
+    },
+    UserVaultEquities {
+        user: Address,
+    },
+    Delegations {
+        user: Address,
+    },
+    DelegatorRewards {
+        user: Address,
+    },
+    DelegatorHistory {
+        user: Address,
+    },
+    ValidatorSummaries,
+}
+
+/// Order looked up by `orderStatus`: the exchange oid or the client order id.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum OrderId {
+    Oid(u64),
+    Cloid(Cloid),
+}
+
+impl From<u64> for OrderId {
+    fn from(oid: u64) -> Self {
+        Self::Oid(oid)
+    }
+}
+
+impl From<Cloid> for OrderId {
+    fn from(cloid: Cloid) -> Self {
+        Self::Cloid(cloid)
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CandleSnapshotRequest {
     coin: String,
-    interval: String,
+    interval: CandleInterval,
     start_time: u64,
     end_time: u64,
 }
+
+impl CandleSnapshotRequest {
+    pub fn new(
+        coin: impl Into<String>,
+        interval: CandleInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> Self {
+        Self {
+            coin: coin.into(),
+            interval,
+            start_time,
+            end_time,
+        }
+    }
+}
@@ -1,54 +1,67 @@
 use serde::{Deserialize, Serialize};
 
 use alloy::primitives::Address;
+use rust_decimal::Decimal;
 
-#[derive(Debug, Deserialize)]
+use crate::{Cloid, Wei};
+
+/// Perp account state (`clearinghouseState`).
+///
+/// Shared by [`InfoClient::user_state`](crate::InfoClient::user_state) and the WebSocket
+/// `clearinghouseState`, `allDexsClearinghouseState` and `webData3` feeds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UserStateResponse {
     pub asset_positions: Vec<AssetPosition>,
     pub cross_margin_summary: MarginSummary,
     pub margin_summary: MarginSummary,
-    pub withdrawable: String,
+    #[serde(default)]
+    pub cross_maintenance_margin_used: Decimal,
+    pub withdrawable: Decimal,
+    /// Exchange time (ms) of the snapshot.
+    #[serde(default)]
+    pub time: u64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetPosition {
     pub position: PositionData,
     #[serde(rename = "type")]
     pub type_string: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PositionData {
     pub coin: String,
-    pub entry_px: Option<String>,
+    pub entry_px: Option<Decimal>,
     pub leverage: Leverage,
-    pub liquidation_px: Option<String>,
-    pub margin_used: String,
-    pub position_value: String,
-    pub return_on_equity: String,
-    pub szi: String,
-    pub unrealized_pnl: String,
+    pub liquidation_px: Option<Decimal>,
+    pub margin_used: Decimal,
+    pub position_value: Decimal,
+    pub return_on_equity: Decimal,
+    /// Signed size: positive for long, negative for short.
+    pub szi: Decimal,
+    pub unrealized_pnl: Decimal,
     pub max_leverage: u32,
     pub cum_funding: CumulativeFunding,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CumulativeFunding {
-    pub all_time: String,
-    pub since_open: String,
-    pub since_change: String,
+    pub all_time: Decimal,
+    pub since_open: Decimal,
+    pub since_change: Decimal,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MarginSummary {
-    pub account_value: String,
-    pub total_margin_used: String,
-    pub total_ntl_pos: String,
-    pub total_raw_usd: String,
+    pub account_value: Decimal,
+    pub total_margin_used: Decimal,
+    pub total_ntl_pos: Decimal,
+    pub total_raw_usd: Decimal,
 }
 
 #[derive(Debug, Deserialize)]
@@ -123,7 +136,7 @@ pub struct OpenOrdersResponse {
     pub side: String,
     pub sz: String,
     pub timestamp: u64,
-    pub cloid: Option<String>,
+    pub cloid: Option<Cloid>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -144,6 +157,8 @@ pub struct UserFillsResponse {
     pub tid: u64,
     pub fee_token: String,
     pub twap_id: Option<u64>,
+    #[serde(default)]
+    pub cloid: Option<Cloid>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -200,30 +215,6 @@ pub struct RecentTradesResponse {
     pub hash: String,
 }
 
-#[derive(serde::Deserialize, Debug)]
-pub struct CandlesSnapshotResponse {
-    #[serde(rename = "t")]
-    pub time_open: u64,
-    #[serde(rename = "T")]
-    pub time_close: u64,
-    #[serde(rename = "s")]
-    pub coin: String,
-    #[serde(rename = "i")]
-    pub candle_interval: String,
-    #[serde(rename = "o")]
-    pub open: String,
-    #[serde(rename = "c")]
-    pub close: String,
-    #[serde(rename = "h")]
-    pub high: String,
-    #[serde(rename = "l")]
-    pub low: String,
-    #[serde(rename = "v")]
-    pub vlm: String,
-    #[serde(rename = "n")]
-    pub num_trades: u64,
-}
-
 #[derive(Deserialize, Debug)]
 pub struct OrderStatusResponse {
     pub status: String,
@@ -256,7 +247,7 @@ pub struct BasicOrderInfo {
     pub order_type: String,
     pub orig_sz: String,
     pub tif: Option<String>,
-    pub cloid: Option<String>,
+    pub cloid: Option<Cloid>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -286,7 +277,12 @@ pub struct ReferrerState {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReferrerData {
-    pub required: String,
+    /// Volume still required before a code can be created (`needToTrade` stage).
+    #[serde(default)]
+    pub required: Option<String>,
+    /// The referrer's code (`ready` stage).
+    #[serde(default)]
+    pub code: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -306,7 +302,7 @@ pub struct Leverage {
     #[serde(rename = "type")]
     pub type_string: String,
     pub value: u32,
-    pub raw_usd: Option<String>,
+    pub raw_usd: Option<Decimal>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -333,3 +329,159 @@ pub struct AgentRoleData {
 pub struct SubAccountRoleData {
     pub master: Address,
 }
+
+/// Vault summary returned by `vaultDetails`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultDetailsResponse {
+    pub name: String,
+    pub vault_address: Address,
+    pub leader: Address,
+    pub description: String,
+    /// Account value and PnL history per window (`"day"`, `"week"`, `"month"`, `"allTime"`, ...).
+    pub portfolio: Vec<(String, VaultPortfolio)>,
+    pub apr: Decimal,
+    /// State of the `user` passed to the query, if they follow the vault.
+    pub follower_state: Option<VaultFollower>,
+    pub leader_fraction: Decimal,
+    pub leader_commission: Decimal,
+    pub followers: Vec<VaultFollower>,
+    pub max_distributable: Decimal,
+    pub max_withdrawable: Decimal,
+    pub is_closed: bool,
+    pub relationship: VaultRelationship,
+    pub allow_deposits: bool,
+    #[serde(default)]
+    pub always_close_on_withdraw: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultPortfolio {
+    /// `(time ms, account value)` points.
+    pub account_value_history: Vec<(u64, Decimal)>,
+    /// `(time ms, pnl)` points.
+    pub pnl_history: Vec<(u64, Decimal)>,
+    pub vlm: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultFollower {
+    /// Follower address, or `"Leader"` for the vault leader.
+    pub user: String,
+    pub vault_equity: Decimal,
+    pub pnl: Decimal,
+    pub all_time_pnl: Decimal,
+    pub days_following: u64,
+    /// Time (ms) of the first deposit.
+    pub vault_entry_time: u64,
+    /// Time (ms) until which deposits are locked, `None` for the leader.
+    #[serde(default)]
+    pub lockup_until: Option<u64>,
+}
+
+/// How a vault relates to other vaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum VaultRelationship {
+    Normal,
+    #[serde(rename_all = "camelCase")]
+    Parent {
+        child_addresses: Vec<Address>,
+    },
+    /// Child vault, or a relationship this crate does not know yet.
+    #[serde(other)]
+    Other,
+}
+
+/// A user's equity in one vault (`userVaultEquities`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserVaultEquity {
+    pub vault_address: Address,
+    pub equity: Decimal,
+    /// Time (ms) until which the equity is locked.
+    #[serde(default)]
+    pub locked_until_timestamp: Option<u64>,
+}
+
+/// Stake delegated to one validator (`delegations`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Delegation {
+    pub validator: Address,
+    /// Delegated HYPE.
+    pub amount: Decimal,
+    /// Time (ms) until which the stake can't be undelegated.
+    pub locked_until_timestamp: u64,
+}
+
+/// A staking reward accrual (`delegatorRewards`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorReward {
+    pub time: u64,
+    /// `"delegation"`, or `"commission"` for rewards earned as a validator.
+    pub source: String,
+    /// Rewarded HYPE.
+    pub total_amount: Decimal,
+}
+
+/// A change to the staking balance (`delegatorHistory`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorEvent {
+    pub time: u64,
+    pub hash: String,
+    pub delta: DelegatorDelta,
+}
+
+/// What a [`DelegatorEvent`] did. Amounts are in HYPE.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DelegatorDelta {
+    /// Delegation to or undelegation from a validator.
+    #[serde(rename_all = "camelCase")]
+    Delegate {
+        validator: Address,
+        amount: Decimal,
+        is_undelegate: bool,
+    },
+    /// Transfer from spot into the staking balance.
+    CDeposit { amount: Decimal },
+    /// Withdrawal from the staking balance to spot; `phase` is `"initiated"` or `"finalized"`.
+    Withdrawal { amount: Decimal, phase: String },
+    /// A delta this crate does not know yet.
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+/// A validator as listed by `validatorSummaries`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorSummary {
+    pub validator: Address,
+    pub signer: Address,
+    pub name: String,
+    pub description: String,
+    pub n_recent_blocks: u64,
+    /// Total delegated stake.
+    pub stake: Wei,
+    pub is_jailed: bool,
+    /// Time (ms) after which a jailed validator can unjail.
+    pub unjailable_after: Option<u64>,
+    pub is_active: bool,
+    /// Commission rate, as a fraction.
+    pub commission: Decimal,
+    /// Uptime and APR per window (`"day"`, `"week"`, `"month"`).
+    pub stats: Vec<(String, ValidatorStats)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorStats {
+    pub uptime_fraction: Decimal,
+    pub predicted_apr: Decimal,
+    pub n_samples: u64,
+}
@@ -0,0 +1,361 @@
+//! OHLCV bars for one coin built locally from `trades` messages or finer candles, for any
+//! [`CandleInterval`] including ones the API does not serve (e.g. 10s).
+
+use std::collections::{BTreeMap, HashSet};
+
+use rust_decimal::Decimal;
+
+use super::responses::WsTrade;
+use crate::{
+    error::Error,
+    info::types::{Candle, CandleInterval},
+    prelude::Result,
+};
+
+/// Bars a [`CandleBuilder`] keeps unless configured otherwise.
+pub const DEFAULT_CANDLE_HISTORY: usize = 1000;
+
+/// Builds bars of one interval for one coin.
+///
+/// Feed it `trades` messages with [`apply_trade`](Self::apply_trade), and/or candles of a
+/// finer interval (from `candleSnapshot` or the `candle` feed) with
+/// [`apply_candle`](Self::apply_candle). A finer candle replaces any earlier version with the
+/// same open time, so live `candle` updates can be applied as they arrive. Trades are
+/// de-duplicated by `tid`, so the trade snapshot resent after a reconnect is not counted twice.
+///
+/// To seed from history without counting trades twice, request the snapshot up to the first
+/// streamed trade and [`seed`](Self::seed) it before applying trades.
+///
+/// Buckets without any trade have no bar. Only the most recent
+/// [`DEFAULT_CANDLE_HISTORY`] bars are kept unless configured with
+/// [`with_history`](Self::with_history); updates older than that are ignored.
+///
+/// # Example
+/// ```
+/// use hl_rs::{info::types::CandleInterval, responses::WsTrade, CandleBuilder};
+/// use rust_decimal_macros::dec;
+///
+/// let trade = |tid: u64, time: u64, px: &str| -> WsTrade {
+///     serde_json::from_value(serde_json::json!({
+///         "coin": "ETH", "side": "B", "px": px, "sz": "1", "hash": "0x", "time": time,
+///         "tid": tid, "users": ["0x1", "0x2"],
+///     }))
+///     .unwrap()
+/// };
+///
+/// let mut bars = CandleBuilder::new("ETH", CandleInterval::from_secs(10));
+/// bars.apply_trade(&trade(1, 1_000, "100"));
+/// bars.apply_trade(&trade(2, 9_000, "103"));
+/// bars.apply_trade(&trade(3, 12_000, "101"));
+///
+/// let first = bars.candles().next().unwrap();
+/// assert_eq!((first.open, first.high, first.close), (dec!(100), dec!(103), dec!(103)));
+/// assert_eq!(bars.last().unwrap().open_ms, 10_000);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CandleBuilder {
+    coin: String,
+    interval: CandleInterval,
+    bars: BTreeMap<u64, Bar>,
+    history: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Bar {
+    candle: Candle,
+    /// Finer candles, by open time.
+    parts: BTreeMap<u64, Candle>,
+    /// Trades so far, with `open_ms` / `close_ms` set to the first and last trade time.
+    trades: Option<Candle>,
+    tids: HashSet<u64>,
+}
+
+impl CandleBuilder {
+    pub fn new(coin: impl Into<String>, interval: CandleInterval) -> Self {
+        Self {
+            coin: coin.into(),
+            interval,
+            bars: BTreeMap::new(),
+            history: DEFAULT_CANDLE_HISTORY,
+        }
+    }
+
+    /// Number of bars to keep (default [`DEFAULT_CANDLE_HISTORY`]).
+    pub fn with_history(mut self, bars: usize) -> Self {
+        self.history = bars.max(1);
+        self
+    }
+
+    pub fn coin(&self) -> &str {
+        &self.coin
+    }
+
+    pub fn interval(&self) -> CandleInterval {
+        self.interval
+    }
+
+    /// Apply `candleSnapshot` history, see [`apply_candle`](Self::apply_candle).
+    pub fn seed(&mut self, history: &[Candle]) -> Result<()> {
+        for candle in history {
+            self.apply_candle(candle)?;
+        }
+        Ok(())
+    }
+
+    /// Merge a candle of this interval or a finer one into its bar. Returns `Ok(false)` if the
+    /// candle is for another coin or older than the kept history.
+    ///
+    /// Fails with [`Error::IncompatibleInterval`] if the candle's interval does not divide
+    /// this one (e.g. 5m into 3m, or 1w into 1M).
+    pub fn apply_candle(&mut self, candle: &Candle) -> Result<bool> {
+        if candle.coin != self.coin {
+            return Ok(false);
+        }
+        if !candle.interval.divides(self.interval) {
+            return Err(Error::IncompatibleInterval {
+                from: candle.interval,
+                to: self.interval,
+            });
+        }
+        let Some(bar) = self.bar(candle.open_ms) else {
+            return Ok(false);
+        };
+        bar.parts.insert(candle.open_ms, candle.clone());
+        bar.rebuild();
+        self.evict();
+        Ok(true)
+    }
+
+    /// Add a trade to its bar. Returns `false` if the trade is for another coin, was already
+    /// applied, or is older than the kept history.
+    pub fn apply_trade(&mut self, trade: &WsTrade) -> bool {
+        if trade.coin != self.coin {
+            return false;
+        }
+        let Some(bar) = self.bar(trade.time) else {
+            return false;
+        };
+        if !bar.tids.insert(trade.tid) {
+            return false;
+        }
+        bar.trades = Some(match bar.trades.take() {
+            None => Candle {
+                open_ms: trade.time,
+                close_ms: trade.time,
+                open: trade.px,
+                close: trade.px,
+                high: trade.px,
+                low: trade.px,
+                volume: trade.sz,
+                num_trades: 1,
+                ..bar.candle.clone()
+            },
+            Some(mut trades) => {
+                if trade.time < trades.open_ms {
+                    trades.open_ms = trade.time;
+                    trades.open = trade.px;
+                }
+                if trade.time >= trades.close_ms {
+                    trades.close_ms = trade.time;
+                    trades.close = trade.px;
+                }
+                trades.high = trades.high.max(trade.px);
+                trades.low = trades.low.min(trade.px);
+                trades.volume += trade.sz;
+                trades.num_trades += 1;
+                trades
+            }
+        });
+        bar.rebuild();
+        self.evict();
+        true
+    }
+
+    /// Apply every trade of a `trades` message. Returns how many were new.
+    pub fn apply_trades(&mut self, trades: &[WsTrade]) -> usize {
+        trades.iter().filter(|t| self.apply_trade(t)).count()
+    }
+
+    /// Bars, oldest first. The last one may still be forming.
+    pub fn candles(&self) -> impl Iterator<Item = &Candle> {
+        self.bars.values().map(|bar| &bar.candle)
+    }
+
+    /// Most recent bar.
+    pub fn last(&self) -> Option<&Candle> {
+        self.bars.values().next_back().map(|bar| &bar.candle)
+    }
+
+    /// Bar of the bucket containing `time_ms`, created if needed. `None` if the bucket is
+    /// older than the kept history.
+    fn bar(&mut self, time_ms: u64) -> Option<&mut Bar> {
+        let start = self.interval.bucket_start(time_ms);
+        let full = self.bars.len() >= self.history;
+        if full
+            && self
+                .bars
+                .first_key_value()
+                .is_some_and(|(oldest, _)| start < *oldest)
+        {
+            return None;
+        }
+        let end = self.interval.next_bucket(start);
+        Some(self.bars.entry(start).or_insert_with(|| Bar {
+            candle: Candle {
+                open_ms: start,
+                close_ms: end - 1,
+                coin: self.coin.clone(),
+                interval: self.interval,
+                open: Decimal::ZERO,
+                close: Decimal::ZERO,
+                high: Decimal::ZERO,
+                low: Decimal::ZERO,
+                volume: Decimal::ZERO,
+                num_trades: 0,
+            },
+            parts: BTreeMap::new(),
+            trades: None,
+            tids: HashSet::new(),
+        }))
+    }
+
+    fn evict(&mut self) {
+        while self.bars.len() > self.history {
+            self.bars.pop_first();
+        }
+    }
+}
+
+impl Bar {
+    /// Recompute the bar from its finer candles and trades.
+    fn rebuild(&mut self) {
+        let mut sources: Vec<&Candle> = self.parts.values().chain(&self.trades).collect();
+        sources.sort_by_key(|c| (c.open_ms, c.close_ms));
+        let (Some(first), Some(last)) =
+            (sources.first(), sources.iter().max_by_key(|c| c.close_ms))
+        else {
+            return;
+        };
+        let candle = &mut self.candle;
+        candle.open = first.open;
+        candle.close = last.close;
+        candle.high = sources.iter().map(|c| c.high).max().unwrap_or_default();
+        candle.low = sources.iter().map(|c| c.low).min().unwrap_or_default();
+        candle.volume = sources.iter().map(|c| c.volume).sum();
+        candle.num_trades = sources.iter().map(|c| c.num_trades).sum();
+    }
+}
+
+/// Combine candles of one coin into bars of a coarser `interval`, e.g. 1m history into 10m
+/// bars. Candles for other coins than the first one are skipped.
+pub fn resample(candles: &[Candle], interval: CandleInterval) -> Result<Vec<Candle>> {
+    let Some(first) = candles.first() else {
+        return Ok(Vec::new());
+    };
+    let mut builder = CandleBuilder::new(&first.coin, interval).with_history(usize::MAX);
+    builder.seed(candles)?;
+    Ok(builder.candles().cloned().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    use super::*;
+
+    fn trade(tid: u64, time: u64, px: &str, sz: &str) -> WsTrade {
+        serde_json::from_value(json!({
+            "coin": "ETH", "side": "B", "px": px, "sz": sz, "hash": "0x", "time": time,
+            "tid": tid, "users": ["0x1", "0x2"],
+        }))
+        .unwrap()
+    }
+
+    fn minute(open_ms: u64, o: &str, h: &str, l: &str, c: &str, v: &str) -> Candle {
+        serde_json::from_value(json!({
+            "t": open_ms, "T": open_ms + 59_999, "s": "ETH", "i": "1m",
+            "o": o, "h": h, "l": l, "c": c, "v": v, "n": 2,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn trades_build_ten_second_bars() {
+        let mut bars = CandleBuilder::new("ETH", CandleInterval::from_secs(10));
+        let trades = [
+            trade(1, 10_500, "100", "1"),
+            trade(3, 19_000, "99", "2"),
+            // Out of order within the bucket: high, but neither open nor close.
+            trade(2, 12_000, "105", "1"),
+            trade(4, 20_000, "101", "1"),
+        ];
+        assert_eq!(bars.apply_trades(&trades), 4);
+        // Replayed after a reconnect.
+        assert_eq!(bars.apply_trades(&trades), 0);
+
+        let candles: Vec<_> = bars.candles().collect();
+        assert_eq!(candles.len(), 2);
+        let first = candles[0];
+        assert_eq!((first.open_ms, first.close_ms), (10_000, 19_999));
+        assert_eq!(
+            (first.open, first.high, first.low, first.close),
+            (dec!(100), dec!(105), dec!(99), dec!(99))
+        );
+        assert_eq!((first.volume, first.num_trades), (dec!(4), 3));
+        assert_eq!(first.interval.to_string(), "10s");
+        assert_eq!(candles[1].close, dec!(101));
+    }
+
+    #[test]
+    fn seeded_history_is_extended_by_trades() {
+        let mut bars = CandleBuilder::new("ETH", CandleInterval::FiveMinutes);
+        bars.seed(&[
+            minute(0, "100", "102", "99", "101", "3"),
+            minute(60_000, "101", "104", "100", "103", "2"),
+        ])
+        .unwrap();
+        bars.apply_trade(&trade(1, 150_000, "98", "1"));
+
+        let bar = bars.last().unwrap();
+        assert_eq!(bars.candles().count(), 1);
+        assert_eq!(
+            (bar.open, bar.high, bar.low, bar.close),
+            (dec!(100), dec!(104), dec!(98), dec!(98))
+        );
+        assert_eq!((bar.volume, bar.num_trades), (dec!(6), 5));
+    }
+
+    #[test]
+    fn live_candle_updates_replace_earlier_versions() {
+        let mut bars = CandleBuilder::new("ETH", CandleInterval::FiveMinutes);
+        bars.apply_candle(&minute(0, "100", "101", "100", "101", "1"))
+            .unwrap();
+        bars.apply_candle(&minute(0, "100", "103", "99", "102", "4"))
+            .unwrap();
+        let bar = bars.last().unwrap();
+        assert_eq!(
+            (bar.high, bar.close, bar.volume),
+            (dec!(103), dec!(102), dec!(4))
+        );
+    }
+
+    #[test]
+    fn resample_checks_intervals_and_history_limit() {
+        let minutes: Vec<_> = (0..10)
+            .map(|i| minute(i * 60_000, "1", "2", "1", "1", "1"))
+            .collect();
+        let bars = resample(&minutes, CandleInterval::FiveMinutes).unwrap();
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[1].open_ms, 300_000);
+        assert_eq!(bars[1].volume, dec!(5));
+
+        let err = resample(&minutes, CandleInterval::from_secs(90)).unwrap_err();
+        assert!(matches!(err, Error::IncompatibleInterval { .. }));
+
+        let mut bars = CandleBuilder::new("ETH", CandleInterval::OneMinute).with_history(2);
+        bars.seed(&minutes).unwrap();
+        assert_eq!(bars.candles().count(), 2);
+        assert!(!bars.apply_trade(&trade(1, 0, "1", "1")));
+    }
+}
@@ -0,0 +1,725 @@
+//! Self-healing WebSocket connection.
+//!
+//! [`ManagedWsClient`] owns a background task that keeps the connection alive: it pings on an
+//! interval, treats a missing pong as a dead connection, reconnects with jittered exponential
+//! backoff and replays every active subscription. Consumers read [`WsEvent`]s instead of raw
+//! messages so they can tell when to rebuild local state.
+//!
+//! Info and action posts can be issued concurrently; their responses are routed back by id.
+//! Posts are never replayed: one in flight when the connection drops fails with
+//! [`Error::WsReceive`], since an action may or may not have reached the exchange.
+//!
+//! [`ManagedWsClient::subscribe_typed`] gives each subscription its own stream of concrete
+//! payloads. Messages routed to a stream are not also delivered as [`WsEvent::Message`].
+//! Events wait in a bounded buffer that drops the oldest when full, so a client reading only
+//! typed streams does not accumulate every unrouted message.
+//! A feed subscribed only for streams is unsubscribed once its last stream is dropped; one
+//! also passed to [`ManagedWsClient::subscribe`] stays until unsubscribed.
+
+use std::{
+    collections::{
+        hash_map::{HashMap, RandomState},
+        VecDeque,
+    },
+    hash::{BuildHasher, Hasher},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use futures_util::{SinkExt, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::{
+    sync::{
+        mpsc::{self, error::TryRecvError, UnboundedReceiver, UnboundedSender},
+        oneshot, Notify,
+    },
+    time::{interval_at, sleep, sleep_until, timeout, Instant, MissedTickBehavior},
+};
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+use super::{
+    post::PostRequest,
+    send_request,
+    subscription::FeedKey,
+    typed::{Route, SubscriptionStream, WsPayload, DEFAULT_STREAM_BUFFER},
+    Frame, Subscription, WsClient, WsMessage, WsPostResponse, WsRequest, WsWrite,
+    DEFAULT_POST_TIMEOUT,
+};
+use crate::actions::{Action, SignedAction};
+use crate::error::Error;
+use crate::info::types::InfoRequest;
+use crate::prelude::Result;
+use crate::types::BaseUrl;
+
+/// Events buffered for [`ManagedWsClient::next_event`] unless configured otherwise.
+pub const DEFAULT_EVENT_BUFFER: usize = 4096;
+
+/// Keepalive and backoff settings for [`ManagedWsClient`].
+#[derive(Debug, Clone)]
+pub struct ReconnectConfig {
+    /// How often to send an application-level ping. The API drops connections idle for 60s.
+    pub ping_interval: Duration,
+    /// How long to wait for the pong before treating the connection as dead.
+    pub pong_timeout: Duration,
+    /// Delay before the first reconnect attempt.
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between reconnect attempts.
+    pub max_backoff: Duration,
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            ping_interval: Duration::from_secs(20),
+            pong_timeout: Duration::from_secs(10),
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ReconnectConfig {
+    /// Delay before reconnect attempt `attempt` (0-based): exponential, capped at
+    /// `max_backoff`, with the upper half jittered so many clients don't reconnect in lockstep.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let base = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff);
+        let jitter = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        base.mul_f64(0.5 + jitter / 2.0)
+    }
+}
+
+/// Event delivered by [`ManagedWsClient::next_event`].
+#[derive(Debug)]
+pub enum WsEvent {
+    /// Connected and every active subscription has been (re)sent. `reconnect` is `false` for
+    /// the initial connection.
+    Connected { reconnect: bool },
+    /// The connection was lost; the client is reconnecting in the background.
+    Disconnected { error: Error },
+    /// Message from the server.
+    ///
+    /// After a reconnect, `resync` is `true` on the first message of each replayed
+    /// subscription. Treat it as a fresh snapshot: replace local state built from earlier
+    /// messages instead of applying it as an update, since anything sent while disconnected
+    /// was missed.
+    Message { message: WsMessage, resync: bool },
+}
+
+enum Command {
+    Subscribe(Subscription),
+    /// Unsubscribe and subscribe again, so the server sends a fresh snapshot.
+    Resubscribe(Subscription),
+    Unsubscribe(Subscription),
+    Post(PendingPost),
+    Route(Route),
+}
+
+struct PendingPost {
+    id: u64,
+    request: PostRequest,
+    reply: oneshot::Sender<Result<WsPostResponse>>,
+}
+
+/// Events waiting for [`ManagedWsClient::next_event`], shared with the background task.
+struct EventQueue {
+    buffer: Mutex<EventBuffer>,
+    ready: Notify,
+    /// Events dropped because the buffer was full.
+    lagged: AtomicU64,
+}
+
+struct EventBuffer {
+    events: VecDeque<WsEvent>,
+    capacity: usize,
+    /// Set once the background task has stopped.
+    closed: bool,
+}
+
+impl EventQueue {
+    fn new(capacity: usize) -> Self {
+        Self {
+            buffer: Mutex::new(EventBuffer {
+                events: VecDeque::new(),
+                capacity,
+                closed: false,
+            }),
+            ready: Notify::new(),
+            lagged: AtomicU64::new(0),
+        }
+    }
+
+    /// Queue `event`, dropping the oldest events if the buffer is full.
+    fn push(&self, event: WsEvent) {
+        let mut buffer = self.buffer.lock().unwrap();
+        while buffer.events.len() >= buffer.capacity {
+            buffer.events.pop_front();
+            self.lagged.fetch_add(1, Ordering::Relaxed);
+        }
+        buffer.events.push_back(event);
+        drop(buffer);
+        self.ready.notify_one();
+    }
+
+    fn close(&self) {
+        self.buffer.lock().unwrap().closed = true;
+        self.ready.notify_one();
+    }
+
+    async fn pop(&self) -> Option<WsEvent> {
+        loop {
+            {
+                let mut buffer = self.buffer.lock().unwrap();
+                if let Some(event) = buffer.events.pop_front() {
+                    return Some(event);
+                }
+                if buffer.closed {
+                    return None;
+                }
+            }
+            self.ready.notified().await;
+        }
+    }
+}
+
+/// Subscriptions shared between [`ManagedWsClient`] and its background task.
+#[derive(Debug, Default)]
+struct Subscriptions {
+    /// Replayed on reconnect, in subscribe order.
+    active: Vec<Subscription>,
+    /// Passed to [`ManagedWsClient::subscribe`]: kept when their last typed stream is dropped.
+    explicit: Vec<Subscription>,
+}
+
+/// WebSocket client that reconnects and resubscribes on its own.
+///
+/// # Example
+/// ```no_run
+/// use hl_rs::{BaseUrl, ManagedWsClient, Subscription, WsEvent};
+///
+/// # async fn run() -> hl_rs::Result<()> {
+/// let mut ws = ManagedWsClient::connect_with_base_url(&BaseUrl::Mainnet).await?;
+/// ws.subscribe(Subscription::Trades { coin: "ETH".to_string() })?;
+///
+/// while let Some(event) = ws.next_event().await {
+///     match event {
+///         WsEvent::Message { message, resync } => println!("{resync} {message:?}"),
+///         WsEvent::Disconnected { error } => eprintln!("reconnecting: {error}"),
+///         WsEvent::Connected { .. } => {}
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Dropping the client closes the connection and stops the background task.
+pub struct ManagedWsClient {
+    commands: UnboundedSender<Command>,
+    events: Arc<EventQueue>,
+    subscriptions: Arc<Mutex<Subscriptions>>,
+    next_post_id: AtomicU64,
+    post_timeout: Duration,
+    stream_buffer: usize,
+}
+
+impl ManagedWsClient {
+    /// Connect with [`ReconnectConfig::default`].
+    ///
+    /// The initial connection is not retried, so a bad URL fails here; later disconnects are.
+    pub async fn connect(url: &str) -> Result<Self> {
+        Self::connect_with_config(url, ReconnectConfig::default()).await
+    }
+
+    /// Connect using [`BaseUrl::ws_url`].
+    pub async fn connect_with_base_url(base: &BaseUrl) -> Result<Self> {
+        Self::connect(base.ws_url().as_str()).await
+    }
+
+    /// Connect with custom keepalive and backoff settings.
+    pub async fn connect_with_config(url: &str, config: ReconnectConfig) -> Result<Self> {
+        let ws = WsClient::connect(url).await?;
+        let (commands, command_rx) = mpsc::unbounded_channel();
+        let events = Arc::new(EventQueue::new(DEFAULT_EVENT_BUFFER));
+        let subscriptions = Arc::new(Mutex::new(Subscriptions::default()));
+
+        let connection = Connection {
+            url: url.to_string(),
+            config,
+            commands: command_rx,
+            events: events.clone(),
+            subscriptions: subscriptions.clone(),
+            pending: HashMap::new(),
+            routes: Vec::new(),
+        };
+        tokio::spawn(connection.run(ws));
+
+        Ok(Self {
+            commands,
+            events,
+            subscriptions,
+            next_post_id: AtomicU64::new(0),
+            post_timeout: DEFAULT_POST_TIMEOUT,
+            stream_buffer: DEFAULT_STREAM_BUFFER,
+        })
+    }
+
+    /// How long posts wait for their response (default [`DEFAULT_POST_TIMEOUT`]).
+    pub fn with_post_timeout(mut self, timeout: Duration) -> Self {
+        self.post_timeout = timeout;
+        self
+    }
+
+    /// Buffer size of streams from [`subscribe_typed`](Self::subscribe_typed) (default
+    /// [`DEFAULT_STREAM_BUFFER`]).
+    pub fn with_stream_buffer(mut self, capacity: usize) -> Self {
+        self.stream_buffer = capacity.max(1);
+        self
+    }
+
+    /// Number of events [`next_event`](Self::next_event) buffers (default
+    /// [`DEFAULT_EVENT_BUFFER`]). Once full, the oldest event is dropped for each new one and
+    /// counted in [`events_lagged`](Self::events_lagged).
+    pub fn with_event_buffer(self, capacity: usize) -> Self {
+        self.events.buffer.lock().unwrap().capacity = capacity.max(1);
+        self
+    }
+
+    /// Subscribe to a feed. The subscription is replayed after every reconnect until
+    /// [`unsubscribe`](Self::unsubscribe)d, even after the last
+    /// [typed stream](Self::subscribe_typed) of the feed is dropped. Subscribing twice to the
+    /// same feed is a no-op.
+    pub fn subscribe(&self, sub: Subscription) -> Result<()> {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        if !subscriptions.explicit.contains(&sub) {
+            subscriptions.explicit.push(sub.clone());
+        }
+        if subscriptions.active.contains(&sub) {
+            return Ok(());
+        }
+        subscriptions.active.push(sub.clone());
+        self.send(Command::Subscribe(sub))
+    }
+
+    /// Subscribe to a feed and receive its payloads on a dedicated stream.
+    ///
+    /// Messages are routed by channel and by the subscription's coin, user and dex, so e.g.
+    /// `l2Book` streams for two coins each see only their own coin. Several streams may share
+    /// one subscription; each gets every message. A stream joining a feed that is already
+    /// subscribed resubscribes it so the new stream starts with a snapshot, which the other
+    /// streams of the feed receive too. Reconnects are still reported on
+    /// [`next_event`](Self::next_event), and the first message after one is a fresh snapshot,
+    /// flagged by [`SubscriptionStream::resynced`].
+    ///
+    /// The feed is unsubscribed when its last stream is dropped, unless it was also
+    /// [`subscribe`](Self::subscribe)d.
+    ///
+    /// Fails with [`Error::WsPayloadMismatch`] if `sub` does not produce `T` messages.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use hl_rs::{responses::WsBook, BaseUrl, ManagedWsClient, Subscription};
+    ///
+    /// # async fn run() -> hl_rs::Result<()> {
+    /// let ws = ManagedWsClient::connect_with_base_url(&BaseUrl::Mainnet).await?;
+    /// let mut books = ws.subscribe_typed::<WsBook>(Subscription::L2Book {
+    ///     coin: "ETH".to_string(),
+    ///     n_sig_figs: None,
+    ///     mantissa: None,
+    /// })?;
+    /// while let Some(book) = books.next().await {
+    ///     println!("{} levels, {} dropped", book.levels[0].len(), books.lagged());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn subscribe_typed<T: WsPayload>(
+        &self,
+        sub: Subscription,
+    ) -> Result<SubscriptionStream<T>> {
+        let channel = sub.channel();
+        if channel != T::CHANNEL {
+            return Err(Error::WsPayloadMismatch {
+                subscription: channel,
+                payload: T::CHANNEL,
+            });
+        }
+        let (route, stream) = Route::new(sub.clone(), self.stream_buffer);
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        self.send(Command::Route(route))?;
+        if subscriptions.active.contains(&sub) {
+            self.send(Command::Resubscribe(sub))?;
+        } else {
+            subscriptions.active.push(sub.clone());
+            self.send(Command::Subscribe(sub))?;
+        }
+        Ok(stream)
+    }
+
+    /// Unsubscribe from a feed and stop replaying it. Streams of the subscription end.
+    pub fn unsubscribe(&self, sub: Subscription) -> Result<()> {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        subscriptions.explicit.retain(|s| *s != sub);
+        let Some(index) = subscriptions.active.iter().position(|s| *s == sub) else {
+            return Ok(());
+        };
+        subscriptions.active.remove(index);
+        self.send(Command::Unsubscribe(sub))
+    }
+
+    /// Subscriptions that will be replayed on reconnect, in subscribe order.
+    pub fn subscriptions(&self) -> Vec<Subscription> {
+        self.subscriptions.lock().unwrap().active.clone()
+    }
+
+    /// Wait for the next event. Returns `None` only if the background task has stopped.
+    pub async fn next_event(&mut self) -> Option<WsEvent> {
+        self.events.pop().await
+    }
+
+    /// Events dropped so far because nobody read them before the buffer filled up. A dropped
+    /// [`WsEvent::Message`] may have been a resync, so rebuild local state when this grows.
+    pub fn events_lagged(&self) -> u64 {
+        self.events.lagged.load(Ordering::Relaxed)
+    }
+
+    /// Send an info request over the socket, see [`WsClient::post_info`].
+    ///
+    /// Fails immediately with [`Error::WsSend`] while reconnecting.
+    pub async fn post_info<T: DeserializeOwned>(&self, request: InfoRequest) -> Result<T> {
+        self.post(PostRequest::info(&request)?).await?.into_info()
+    }
+
+    /// Send a signed action over the socket, see [`WsClient::post_action`].
+    ///
+    /// Fails immediately with [`Error::WsSend`] while reconnecting.
+    pub async fn post_action<A: Action + Serialize>(
+        &self,
+        signed_action: SignedAction<A>,
+    ) -> Result<A::Response> {
+        self.post(PostRequest::action(&signed_action)?)
+            .await?
+            .into_action()
+    }
+
+    async fn post(&self, request: PostRequest) -> Result<WsPostResponse> {
+        let id = self.next_post_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let (reply, response) = oneshot::channel();
+        self.send(Command::Post(PendingPost { id, request, reply }))?;
+        match timeout(self.post_timeout, response).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(Error::WsReceive("connection task has stopped".to_string())),
+            Err(_) => Err(Error::WsPostTimeout { id }),
+        }
+    }
+
+    fn send(&self, command: Command) -> Result<()> {
+        self.commands
+            .send(command)
+            .map_err(|_| Error::WsSend("connection task has stopped".to_string()))
+    }
+}
+
+/// State owned by the background task.
+struct Connection {
+    url: String,
+    config: ReconnectConfig,
+    commands: UnboundedReceiver<Command>,
+    events: Arc<EventQueue>,
+    subscriptions: Arc<Mutex<Subscriptions>>,
+    /// Posts sent on the current connection, awaiting their response.
+    pending: HashMap<u64, oneshot::Sender<Result<WsPostResponse>>>,
+    /// Typed streams, kept across reconnects.
+    routes: Vec<Route>,
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.events.close();
+    }
+}
+
+impl Connection {
+    async fn run(mut self, mut ws: WsClient) {
+        let mut reconnect = false;
+        loop {
+            // Subscribe/unsubscribe commands queued so far are already reflected in the set
+            // being replayed; drop them so nothing is sent twice.
+            let Some((replay, posts)) = self.take_replay() else {
+                return;
+            };
+            let result = match self.resume(&mut ws, &replay, posts).await {
+                Ok(()) => {
+                    self.events.push(WsEvent::Connected { reconnect });
+                    let resync = if reconnect { replay } else { Vec::new() };
+                    self.session(&mut ws, resync).await
+                }
+                Err(e) => Err(e),
+            };
+            let error = match result {
+                Ok(()) => return,
+                Err(error) => error,
+            };
+            for (_, reply) in self.pending.drain() {
+                let _ = reply.send(Err(Error::WsReceive(
+                    "connection lost before the response arrived".to_string(),
+                )));
+            }
+
+            tracing::debug!(target: "hl_rs::ws", error = %error, "WebSocket disconnected");
+            self.events.push(WsEvent::Disconnected { error });
+            ws = match self.reconnect().await {
+                Some(ws) => ws,
+                None => return,
+            };
+            reconnect = true;
+        }
+    }
+
+    fn take_replay(&mut self) -> Option<(Vec<Subscription>, Vec<PendingPost>)> {
+        let subscriptions = self.subscriptions.lock().unwrap();
+        let mut posts = Vec::new();
+        loop {
+            match self.commands.try_recv() {
+                Ok(Command::Post(post)) => posts.push(post),
+                Ok(Command::Route(route)) => self.routes.push(route),
+                Ok(Command::Unsubscribe(sub)) => self.routes.retain(|r| r.subscription != sub),
+                Ok(Command::Subscribe(_) | Command::Resubscribe(_)) => continue,
+                Err(TryRecvError::Empty) => return Some((subscriptions.active.clone(), posts)),
+                Err(TryRecvError::Disconnected) => return None,
+            }
+        }
+    }
+
+    /// Replay subscriptions, then send posts queued before the connection was up.
+    async fn resume(
+        &mut self,
+        ws: &mut WsClient,
+        subscriptions: &[Subscription],
+        posts: Vec<PendingPost>,
+    ) -> Result<()> {
+        for sub in subscriptions {
+            ws.subscribe(sub.clone()).await?;
+        }
+        for post in posts {
+            self.start_post(&mut ws.write, post).await?;
+        }
+        Ok(())
+    }
+
+    async fn start_post(&mut self, write: &mut WsWrite, post: PendingPost) -> Result<()> {
+        let PendingPost { id, request, reply } = post;
+        match send_request(write, &WsRequest::post(id, request)).await {
+            Ok(()) => {
+                self.pending.insert(id, reply);
+                Ok(())
+            }
+            Err(e) => {
+                let _ = reply.send(Err(Error::WsSend(e.to_string())));
+                Err(e)
+            }
+        }
+    }
+
+    /// Pump one connection until it fails (`Err`) or the client is dropped (`Ok`).
+    ///
+    /// `resync` holds the replayed subscriptions whose first message is still to come.
+    async fn session(&mut self, ws: &mut WsClient, resync: Vec<Subscription>) -> Result<()> {
+        let WsClient { write, read, .. } = ws;
+        let mut resync: Vec<FeedKey> = resync.iter().map(Subscription::key).collect();
+        let period = self.config.ping_interval;
+        let mut ping = interval_at(Instant::now() + period, period);
+        ping.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut pong_deadline: Option<Instant> = None;
+
+        loop {
+            let deadline = pong_deadline.unwrap_or_else(Instant::now);
+            tokio::select! {
+                item = read.next() => {
+                    let Some(item) = item else {
+                        return Err(Error::WsReceive("connection closed".to_string()));
+                    };
+                    match Frame::from(item) {
+                        Frame::Message(Ok(WsMessage::Pong)) => pong_deadline = None,
+                        Frame::Message(Ok(WsMessage::Post { data })) => {
+                            if let Some(reply) = self.pending.remove(&data.id) {
+                                let _ = reply.send(Ok(data));
+                            }
+                        }
+                        Frame::Message(Ok(message)) => {
+                            let resync = match resync.iter().position(|key| key.matches(&message)) {
+                                Some(index) => {
+                                    resync.swap_remove(index);
+                                    true
+                                }
+                                None => false,
+                            };
+                            if self.route(write, &message, resync).await? {
+                                continue;
+                            }
+                            self.events.push(WsEvent::Message { message, resync });
+                        }
+                        Frame::Message(Err(Error::WsReceive(e))) => return Err(Error::WsReceive(e)),
+                        Frame::Message(Err(e)) => {
+                            tracing::warn!(
+                                target: "hl_rs::ws",
+                                error = %e,
+                                "Dropping unparseable message"
+                            );
+                        }
+                        Frame::Ping(payload) => write
+                            .send(Message::Pong(payload))
+                            .await
+                            .map_err(|e| Error::WsSend(e.to_string()))?,
+                        Frame::Close => {
+                            return Err(Error::WsReceive("connection closed by server".to_string()));
+                        }
+                        Frame::Skip => {}
+                    }
+                }
+                command = self.commands.recv() => match command {
+                    Some(Command::Subscribe(sub)) => {
+                        send_request(write, &WsRequest::subscribe(sub)).await?;
+                    }
+                    Some(Command::Resubscribe(sub)) => {
+                        send_request(write, &WsRequest::unsubscribe(sub.clone())).await?;
+                        send_request(write, &WsRequest::subscribe(sub)).await?;
+                    }
+                    Some(Command::Unsubscribe(sub)) => {
+                        self.drop_routes(&sub);
+                        send_request(write, &WsRequest::unsubscribe(sub)).await?;
+                    }
+                    Some(Command::Post(post)) => self.start_post(write, post).await?,
+                    Some(Command::Route(route)) => self.routes.push(route),
+                    None => {
+                        let _ = write.close().await;
+                        return Ok(());
+                    }
+                },
+                _ = ping.tick(), if pong_deadline.is_none() => {
+                    // Forget posts whose caller gave up waiting.
+                    self.pending.retain(|_, reply| !reply.is_closed());
+                    self.release_closed_routes(write).await?;
+                    send_request(write, &WsRequest::ping()).await?;
+                    pong_deadline = Some(Instant::now() + self.config.pong_timeout);
+                }
+                _ = sleep_until(deadline), if pong_deadline.is_some() => {
+                    return Err(Error::WsReceive(format!(
+                        "no pong within {:?}",
+                        self.config.pong_timeout
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Hand `message`, flagged with `resync`, to every stream of its subscription. Returns
+    /// whether any stream took it.
+    async fn route(
+        &mut self,
+        write: &mut WsWrite,
+        message: &WsMessage,
+        resync: bool,
+    ) -> Result<bool> {
+        let mut routed = false;
+        for route in &self.routes {
+            if route.key.matches(message) {
+                routed |= route.deliver(message, resync);
+            }
+        }
+        if !routed && self.routes.iter().any(Route::is_closed) {
+            self.release_closed_routes(write).await?;
+        }
+        Ok(routed)
+    }
+
+    /// Forget streams that were dropped, unsubscribing from feeds no stream is left on unless
+    /// they were subscribed explicitly.
+    async fn release_closed_routes(&mut self, write: &mut WsWrite) -> Result<()> {
+        let (closed, open): (Vec<_>, Vec<_>) =
+            self.routes.drain(..).partition(|route| route.is_closed());
+        self.routes = open;
+        for route in closed {
+            let sub = route.subscription;
+            if self.routes.iter().any(|r| r.subscription == sub) {
+                continue;
+            }
+            let removed = {
+                let mut subscriptions = self.subscriptions.lock().unwrap();
+                let index = subscriptions.active.iter().position(|s| *s == sub);
+                match index {
+                    Some(index) if !subscriptions.explicit.contains(&sub) => {
+                        subscriptions.active.remove(index);
+                        true
+                    }
+                    _ => false,
+                }
+            };
+            if removed {
+                send_request(write, &WsRequest::unsubscribe(sub)).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// End the streams of `sub`.
+    fn drop_routes(&mut self, sub: &Subscription) {
+        self.routes.retain(|route| route.subscription != *sub);
+    }
+
+    /// Reconnect with backoff. Returns `None` if the client is dropped meanwhile.
+    async fn reconnect(&mut self) -> Option<WsClient> {
+        for attempt in 0.. {
+            let delay = sleep(self.config.backoff(attempt));
+            tokio::pin!(delay);
+            loop {
+                tokio::select! {
+                    _ = &mut delay => break,
+                    // Subscription changes are already applied to the set and will be
+                    // replayed; posts fail fast rather than wait for the connection.
+                    command = self.commands.recv() => match command? {
+                        Command::Post(post) => {
+                            let _ = post.reply.send(Err(Error::WsSend(
+                                "not connected, reconnecting".to_string(),
+                            )));
+                        }
+                        Command::Route(route) => self.routes.push(route),
+                        Command::Unsubscribe(sub) => self.drop_routes(&sub),
+                        Command::Subscribe(_) | Command::Resubscribe(_) => {}
+                    },
+                }
+            }
+            match WsClient::connect(&self.url).await {
+                Ok(ws) => return Some(ws),
+                Err(e) => {
+                    tracing::debug!(target: "hl_rs::ws", attempt, error = %e, "Reconnect failed");
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_jittered_and_capped() {
+        let config = ReconnectConfig {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+            ..ReconnectConfig::default()
+        };
+        for (attempt, base) in [(0, 100), (1, 200), (3, 800), (4, 1000), (40, 1000)] {
+            let delay = config.backoff(attempt);
+            let base = Duration::from_millis(base);
+            assert!(delay >= base / 2 && delay <= base, "{attempt}: {delay:?}");
+        }
+    }
+}
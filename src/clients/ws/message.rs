@@ -6,15 +6,21 @@
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
+use super::post::{PostRequest, WsPostResponse};
 use super::responses::*;
+use super::subscription::Identity;
 use super::Subscription;
 
-/// Outgoing request (subscribe, unsubscribe, ping).
+/// Outgoing request (subscribe, unsubscribe, ping, post).
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct WsRequest {
     method: &'static str,
     #[serde(skip_serializing_if = "Option::is_none")]
     subscription: Option<Subscription>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request: Option<PostRequest>,
 }
 
 impl WsRequest {
@@ -22,6 +28,8 @@ impl WsRequest {
         Self {
             method: "subscribe",
             subscription: Some(sub),
+            id: None,
+            request: None,
         }
     }
 
@@ -29,6 +37,8 @@ impl WsRequest {
         Self {
             method: "unsubscribe",
             subscription: Some(sub),
+            id: None,
+            request: None,
         }
     }
 
@@ -36,6 +46,17 @@ impl WsRequest {
         Self {
             method: "ping",
             subscription: None,
+            id: None,
+            request: None,
+        }
+    }
+
+    pub fn post(id: u64, request: PostRequest) -> Self {
+        Self {
+            method: "post",
+            subscription: None,
+            id: Some(id),
+            request: Some(request),
         }
     }
 }
@@ -47,6 +68,8 @@ pub enum WsMessage {
     SubscriptionResponse { data: Subscription },
     /// Response to [`super::WsClient::ping`].
     Pong,
+    /// Response to an info or action post, see [`super::WsClient::post_info`].
+    Post { data: WsPostResponse },
     AllMids {
         data: AllMids,
         is_snapshot: Option<bool>,
@@ -63,9 +86,9 @@ pub enum WsMessage {
         data: TwapStates,
         is_snapshot: Option<bool>,
     },
-    /// Clearinghouse / margin state (same overall shape as the info `clearinghouseState` snapshot).
+    /// Clearinghouse / margin state (same model as the info `clearinghouseState` snapshot).
     ClearinghouseState {
-        data: Value,
+        data: WsClearinghouseState,
         is_snapshot: Option<bool>,
     },
     OpenOrders {
@@ -150,6 +173,7 @@ impl WsMessage {
         match self {
             WsMessage::SubscriptionResponse { .. } => "subscriptionResponse",
             WsMessage::Pong => "pong",
+            WsMessage::Post { .. } => "post",
             WsMessage::AllMids { .. } => "allMids",
             WsMessage::Notification { .. } => "notification",
             WsMessage::WebData3 { .. } => "webData3",
@@ -179,7 +203,9 @@ impl WsMessage {
     /// Snapshot / streaming flag from the outer message (`isSnapshot`), when present.
     pub fn is_snapshot(&self) -> Option<bool> {
         match self {
-            WsMessage::SubscriptionResponse { .. } | WsMessage::Pong => None,
+            WsMessage::SubscriptionResponse { .. } | WsMessage::Pong | WsMessage::Post { .. } => {
+                None
+            }
             WsMessage::AllMids { is_snapshot, .. }
             | WsMessage::Notification { is_snapshot, .. }
             | WsMessage::WebData3 { is_snapshot, .. }
@@ -211,11 +237,12 @@ impl WsMessage {
         match self {
             WsMessage::SubscriptionResponse { data } => serde_json::to_value(data).ok(),
             WsMessage::Pong => None,
+            WsMessage::Post { data } => serde_json::to_value(data).ok(),
             WsMessage::AllMids { data, .. } => serde_json::to_value(data).ok(),
             WsMessage::Notification { data, .. } => serde_json::to_value(data).ok(),
             WsMessage::WebData3 { data, .. } => serde_json::to_value(data).ok(),
             WsMessage::TwapStates { data, .. } => serde_json::to_value(data).ok(),
-            WsMessage::ClearinghouseState { data, .. } => Some(data.clone()),
+            WsMessage::ClearinghouseState { data, .. } => serde_json::to_value(data).ok(),
             WsMessage::OpenOrders { data, .. } => serde_json::to_value(data).ok(),
             WsMessage::Candle { data, .. } => serde_json::to_value(data).ok(),
             WsMessage::L2Book { data, .. } => serde_json::to_value(data).ok(),
@@ -238,6 +265,64 @@ impl WsMessage {
     }
 }
 
+impl WsMessage {
+    /// Coin, candle interval, user and dex the payload is for, read from the typed data.
+    pub(crate) fn identity(&self) -> Identity<'_> {
+        let mut id = Identity::default();
+        match self {
+            WsMessage::TwapStates { data, .. } => {
+                (id.user, id.dex) = (Some(data.user.as_str()), Some(data.dex.as_str()));
+            }
+            WsMessage::ClearinghouseState { data, .. } => {
+                (id.user, id.dex) = (Some(data.user.as_str()), Some(data.dex.as_str()));
+            }
+            WsMessage::OpenOrders { data, .. } => {
+                (id.user, id.dex) = (Some(data.user.as_str()), Some(data.dex.as_str()));
+            }
+            WsMessage::Candle { data, .. } => {
+                (id.coin, id.interval) = (Some(data.coin.as_str()), Some(data.interval));
+            }
+            WsMessage::L2Book { data, .. } => id.coin = Some(data.coin.as_str()),
+            // Trade batches carry the coin on each trade.
+            WsMessage::Trades { data, .. } => id.coin = data.first().map(|t| t.coin.as_str()),
+            WsMessage::UserFills { data, .. } => id.user = Some(data.user.as_str()),
+            WsMessage::UserFundings { data, .. } => id.user = Some(data.user.as_str()),
+            WsMessage::UserNonFundingLedgerUpdates { data, .. } => {
+                id.user = Some(data.user.as_str())
+            }
+            WsMessage::ActiveAssetCtx { data, .. } => {
+                id.coin = Some(match data {
+                    WsActiveAssetCtx::Perp(ctx) => ctx.coin.as_str(),
+                    WsActiveAssetCtx::Spot(ctx) => ctx.coin.as_str(),
+                });
+            }
+            WsMessage::ActiveAssetData { data, .. } => {
+                (id.coin, id.user) = (Some(data.coin.as_str()), Some(data.user.as_str()));
+            }
+            WsMessage::UserTwapSliceFills { data, .. } => id.user = Some(data.user.as_str()),
+            WsMessage::UserTwapHistory { data, .. } => id.user = Some(data.user.as_str()),
+            WsMessage::Bbo { data, .. } => id.coin = Some(data.coin.as_str()),
+            WsMessage::SpotState { data, .. } => id.user = Some(data.user.as_str()),
+            WsMessage::AllDexsClearinghouseState { data, .. } => id.user = Some(data.user.as_str()),
+            WsMessage::Unknown {
+                data: Some(data), ..
+            } => {
+                let data = match data {
+                    Value::Array(items) => items.first().unwrap_or(&Value::Null),
+                    data => data,
+                };
+                let field = |names: &[&str]| names.iter().find_map(|name| data[*name].as_str());
+                id.coin = field(&["coin", "s"]);
+                id.interval = field(&["interval", "i"]).and_then(|i| i.parse().ok());
+                id.user = field(&["user"]);
+                id.dex = field(&["dex"]);
+            }
+            _ => {}
+        }
+        id
+    }
+}
+
 impl<'de> Deserialize<'de> for WsMessage {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -304,17 +389,16 @@ fn parse_ws_message(v: Value) -> Result<WsMessage, String> {
             Ok(WsMessage::SubscriptionResponse { data: sub })
         }
         "pong" => Ok(WsMessage::Pong),
+        "post" => {
+            let d = data.ok_or_else(|| "missing \"data\" for post".to_string())?;
+            let data = serde_json::from_value(d).map_err(|e| e.to_string())?;
+            Ok(WsMessage::Post { data })
+        }
         "allMids" => typed!(AllMids, AllMids),
         "notification" => typed!(Notification, Notification),
         "webData3" => typed!(WebData3, WebData3),
         "twapStates" => typed!(TwapStates, TwapStates),
-        "clearinghouseState" => {
-            let d = data.ok_or_else(|| "missing \"data\" for clearinghouseState".to_string())?;
-            Ok(WsMessage::ClearinghouseState {
-                data: d,
-                is_snapshot: outer_snapshot,
-            })
-        }
+        "clearinghouseState" => typed!(ClearinghouseState, WsClearinghouseState),
         "openOrders" => typed!(OpenOrders, OpenOrders),
         "candle" => typed!(Candle, Candle),
         "l2Book" => typed!(L2Book, WsBook),
@@ -468,4 +552,62 @@ mod tests {
             _ => panic!("expected subscription response"),
         }
     }
+
+    #[test]
+    fn clearinghouse_state_is_typed() {
+        let summary = serde_json::json!({
+            "accountValue": "100.5",
+            "totalNtlPos": "50",
+            "totalRawUsd": "50.5",
+            "totalMarginUsed": "5",
+        });
+        let v = serde_json::json!({
+            "channel": "clearinghouseState",
+            "data": {
+                "dex": "",
+                "user": "0xabc",
+                "clearinghouseState": {
+                    "marginSummary": summary,
+                    "crossMarginSummary": summary,
+                    "crossMaintenanceMarginUsed": "2.5",
+                    "withdrawable": "95.5",
+                    "assetPositions": [{
+                        "type": "oneWay",
+                        "position": {
+                            "coin": "ETH",
+                            "szi": "-0.02",
+                            "leverage": {"type": "isolated", "value": 5, "rawUsd": "60.1"},
+                            "entryPx": "2500",
+                            "positionValue": "50",
+                            "unrealizedPnl": "0",
+                            "returnOnEquity": "0",
+                            "liquidationPx": "3000.5",
+                            "marginUsed": "10.1",
+                            "maxLeverage": 25,
+                            "cumFunding": {"allTime": "1", "sinceOpen": "0", "sinceChange": "0"},
+                        },
+                    }],
+                    "time": 1,
+                },
+            },
+        });
+        let m: WsMessage = serde_json::from_value(v).unwrap();
+        let WsMessage::ClearinghouseState { data, .. } = &m else {
+            panic!("expected clearinghouse state, got {m:?}");
+        };
+        let position = &data.clearinghouse_state.asset_positions[0].position;
+        assert_eq!(position.szi, rust_decimal_macros::dec!(-0.02));
+        assert_eq!(
+            position.leverage.raw_usd,
+            Some(rust_decimal_macros::dec!(60.1))
+        );
+        assert_eq!(
+            data.clearinghouse_state.margin_summary.account_value,
+            rust_decimal_macros::dec!(100.5)
+        );
+        assert_eq!(
+            m.data_json().unwrap()["clearinghouseState"]["withdrawable"],
+            "95.5"
+        );
+    }
 }
@@ -2,27 +2,44 @@
 //!
 //! Connect with [`WsClient::connect`] or [`WsClient::connect_with_base_url`], then
 //! [`WsClient::subscribe`] / [`WsClient::unsubscribe`]. Read frames with [`WsClient::next_message`].
+//! Info requests and signed actions can be sent over the same socket with
+//! [`WsClient::post_info`] / [`WsClient::post_action`].
 //!
 //! **Reconnect:** The API may disconnect without notice. Callers should reconnect and resubscribe;
-//! snapshot acks on resubscribe include data missed while disconnected.
+//! snapshot acks on resubscribe include data missed while disconnected. [`ManagedWsClient`] does
+//! this automatically.
 
+mod candle_builder;
+mod managed;
 mod message;
+mod order_book;
+mod post;
 pub mod responses;
 mod subscription;
+mod typed;
 
+pub use candle_builder::{resample, CandleBuilder, DEFAULT_CANDLE_HISTORY};
+pub use managed::{ManagedWsClient, ReconnectConfig, WsEvent, DEFAULT_EVENT_BUFFER};
 pub use message::WsMessage;
+pub use order_book::OrderBook;
+pub use post::{WsPostPayload, WsPostResponse, DEFAULT_POST_TIMEOUT};
 pub use responses::{
     AllMids, Candle, Notification, OpenOrders, PerpsAssetCtx, Price, SpotAssetCtx, TwapState,
     TwapStates, UserBalance, WsActiveAssetCtx, WsActiveAssetData, WsAllDexsAssetCtxs,
-    WsAllDexsClearinghouseState, WsBasicOrder, WsBbo, WsBook, WsFill, WsLevel, WsLiquidation,
-    WsNonUserCancel, WsOrder, WsSpotState, WsTrade, WsUserEvent, WsUserFunding, WsUserFundings,
-    WsUserNonFundingLedgerUpdate, WsUserNonFundingLedgerUpdates, WsUserTwapHistory,
-    WsUserTwapSliceFills, WebData3,
+    WsAllDexsClearinghouseState, WsBasicOrder, WsBbo, WsBook, WsClearinghouseState, WsFill,
+    WsLevel, WsLiquidation, WsNonUserCancel, WsOrder, WsPerpDexState, WsSpotState, WsTrade,
+    WsUserEvent, WsUserFunding, WsUserFundings, WsUserNonFundingLedgerUpdate,
+    WsUserNonFundingLedgerUpdates, WsUserTwapHistory, WsUserTwapSliceFills, WebData3,
 };
 pub use subscription::Subscription;
+pub use typed::{SubscriptionStream, WsPayload, DEFAULT_STREAM_BUFFER};
+
+use std::{collections::VecDeque, time::Duration};
 
 use futures_util::{SinkExt, StreamExt};
 use message::WsRequest;
+use post::PostRequest;
+use serde::{de::DeserializeOwned, Serialize};
 use tokio::net::TcpStream;
 use tokio_tungstenite::{
     connect_async,
@@ -30,7 +47,10 @@ use tokio_tungstenite::{
     MaybeTlsStream, WebSocketStream,
 };
 
+use crate::actions::{Action, SignedAction};
+use crate::cassette::{Recorder, WsDirection};
 use crate::error::Error;
+use crate::info::types::InfoRequest;
 use crate::prelude::Result;
 use crate::types::BaseUrl;
 
@@ -38,8 +58,14 @@ type HlWsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
 
 /// WebSocket client for Hyperliquid streaming feeds.
 pub struct WsClient {
-    write: futures_util::stream::SplitSink<HlWsStream, Message>,
+    write: WsWrite,
     read: futures_util::stream::SplitStream<HlWsStream>,
+    /// Messages that arrived while waiting for a post response.
+    buffered: VecDeque<Result<WsMessage>>,
+    next_post_id: u64,
+    post_timeout: Duration,
+    /// Recorder and this connection's number in its cassette.
+    recorder: Option<(Recorder, u64)>,
 }
 
 impl WsClient {
@@ -49,7 +75,14 @@ impl WsClient {
             .await
             .map_err(|e| Error::WsConnect(e.to_string()))?;
         let (write, read) = ws.split();
-        Ok(Self { write, read })
+        Ok(Self {
+            write,
+            read,
+            buffered: VecDeque::new(),
+            next_post_id: 0,
+            post_timeout: DEFAULT_POST_TIMEOUT,
+            recorder: None,
+        })
     }
 
     /// Connect using [`BaseUrl::ws_url`].
@@ -59,35 +92,76 @@ impl WsClient {
 
     /// Subscribe to a feed.
     pub async fn subscribe(&mut self, sub: Subscription) -> Result<()> {
-        let json = serde_json::to_string(&WsRequest::subscribe(sub))
-            .map_err(|e| Error::SerializationFailure(e.to_string()))?;
-        self.write
-            .send(Message::Text(json.into()))
-            .await
-            .map_err(|e| Error::WsSend(e.to_string()))?;
-        Ok(())
+        self.send(&WsRequest::subscribe(sub)).await
     }
 
     /// Unsubscribe from a feed (subscription object must match the original subscribe).
     pub async fn unsubscribe(&mut self, sub: Subscription) -> Result<()> {
-        let json = serde_json::to_string(&WsRequest::unsubscribe(sub))
-            .map_err(|e| Error::SerializationFailure(e.to_string()))?;
-        self.write
-            .send(Message::Text(json.into()))
-            .await
-            .map_err(|e| Error::WsSend(e.to_string()))?;
-        Ok(())
+        self.send(&WsRequest::unsubscribe(sub)).await
     }
 
     /// Send keepalive ping (server responds with `channel: "pong"`).
     pub async fn ping(&mut self) -> Result<()> {
-        let json = serde_json::to_string(&WsRequest::ping())
-            .map_err(|e| Error::SerializationFailure(e.to_string()))?;
-        self.write
-            .send(Message::Text(json.into()))
+        self.send(&WsRequest::ping()).await
+    }
+
+    /// Record every text frame sent and received from now on to `recorder`'s cassette, as a
+    /// new connection. Frames still unread (such as the connection greeting) are included.
+    pub fn with_recorder(mut self, recorder: Recorder) -> Self {
+        let connection = recorder.ws_connection();
+        self.recorder = Some((recorder, connection));
+        self
+    }
+
+    /// How long [`post_info`](Self::post_info) and [`post_action`](Self::post_action) wait
+    /// for their response (default [`DEFAULT_POST_TIMEOUT`]).
+    pub fn with_post_timeout(mut self, timeout: Duration) -> Self {
+        self.post_timeout = timeout;
+        self
+    }
+
+    /// Send an info request over the socket and parse the response into `T`, the type the
+    /// matching [`InfoClient`](crate::InfoClient) method returns.
+    ///
+    /// Subscription messages received while waiting are kept for
+    /// [`next_message`](Self::next_message).
+    pub async fn post_info<T: DeserializeOwned>(&mut self, request: InfoRequest) -> Result<T> {
+        self.post(PostRequest::info(&request)?).await?.into_info()
+    }
+
+    /// Send a signed action over the socket and parse the response like
+    /// [`ExchangeClient::send_signed_action`](crate::ExchangeClient::send_signed_action).
+    ///
+    /// Sign with [`ExchangeClient::sign_action`](crate::ExchangeClient::sign_action).
+    pub async fn post_action<A: Action + Serialize>(
+        &mut self,
+        signed_action: SignedAction<A>,
+    ) -> Result<A::Response> {
+        self.post(PostRequest::action(&signed_action)?)
+            .await?
+            .into_action()
+    }
+
+    async fn post(&mut self, request: PostRequest) -> Result<WsPostResponse> {
+        self.next_post_id += 1;
+        let id = self.next_post_id;
+        self.send(&WsRequest::post(id, request)).await?;
+        tokio::time::timeout(self.post_timeout, self.post_response(id))
             .await
-            .map_err(|e| Error::WsSend(e.to_string()))?;
-        Ok(())
+            .map_err(|_| Error::WsPostTimeout { id })?
+    }
+
+    async fn post_response(&mut self, id: u64) -> Result<WsPostResponse> {
+        loop {
+            match self.read_message().await {
+                Some(Ok(WsMessage::Post { data })) if data.id == id => return Ok(data),
+                // Late response to a post that already timed out.
+                Some(Ok(WsMessage::Post { .. })) => {}
+                Some(Err(Error::WsReceive(e))) => return Err(Error::WsReceive(e)),
+                Some(message) => self.buffered.push_back(message),
+                None => return Err(Error::WsReceive("connection closed".to_string())),
+            }
+        }
     }
 
     /// Receive the next JSON message as [`WsMessage`].
@@ -95,41 +169,87 @@ impl WsClient {
     /// Skips the plain-text `"Websocket connection established."` handshake line.
     /// Handles WebSocket ping frames by replying with pong.
     pub async fn next_message(&mut self) -> Option<Result<WsMessage>> {
+        match self.buffered.pop_front() {
+            Some(message) => Some(message),
+            None => self.read_message().await,
+        }
+    }
+
+    async fn send(&mut self, request: &WsRequest) -> Result<()> {
+        if let Some((recorder, connection)) = &self.recorder {
+            if let Ok(json) = serde_json::to_string(request) {
+                recorder.record_ws(*connection, WsDirection::Send, &json);
+            }
+        }
+        send_request(&mut self.write, request).await
+    }
+
+    async fn read_message(&mut self) -> Option<Result<WsMessage>> {
         while let Some(item) = self.read.next().await {
-            match item {
-                Ok(Message::Text(t)) => {
-                    if t == "Websocket connection established." {
-                        continue;
-                    }
-                    return Some(
-                        serde_json::from_str::<WsMessage>(&t)
-                            .map_err(|e| Error::JsonParse(e.to_string())),
-                    );
-                }
-                Ok(Message::Ping(payload)) => {
+            if let (Some((recorder, connection)), Ok(Message::Text(text))) = (&self.recorder, &item)
+            {
+                recorder.record_ws(*connection, WsDirection::Recv, text);
+            }
+            match Frame::from(item) {
+                Frame::Message(message) => return Some(message),
+                Frame::Ping(payload) => {
                     let _ = self.write.send(Message::Pong(payload)).await;
-                    continue;
                 }
-                Ok(Message::Close(_)) => return None,
-                Ok(Message::Pong(_)) | Ok(Message::Frame(_)) => continue,
-                Ok(Message::Binary(b)) => {
-                    let Ok(s) = String::from_utf8(b.to_vec()) else {
-                        return Some(Err(Error::JsonParse(
-                            "binary frame is not valid UTF-8".to_string(),
-                        )));
-                    };
-                    return Some(
-                        serde_json::from_str::<WsMessage>(&s)
-                            .map_err(|e| Error::JsonParse(e.to_string())),
-                    );
-                }
-                Err(e) => return Some(Err(Error::WsReceive(e.to_string()))),
+                Frame::Close => return None,
+                Frame::Skip => {}
             }
         }
         None
     }
 }
 
+type WsWrite = futures_util::stream::SplitSink<HlWsStream, Message>;
+
+async fn send_request(write: &mut WsWrite, request: &WsRequest) -> Result<()> {
+    let json =
+        serde_json::to_string(request).map_err(|e| Error::SerializationFailure(e.to_string()))?;
+    write
+        .send(Message::Text(json))
+        .await
+        .map_err(|e| Error::WsSend(e.to_string()))
+}
+
+/// A raw WebSocket frame, classified for the read loops.
+// Consumed right away; boxing every message would cost more than the padding.
+#[allow(clippy::large_enum_variant)]
+enum Frame {
+    Message(Result<WsMessage>),
+    Ping(Vec<u8>),
+    Close,
+    Skip,
+}
+
+impl From<std::result::Result<Message, tokio_tungstenite::tungstenite::Error>> for Frame {
+    fn from(item: std::result::Result<Message, tokio_tungstenite::tungstenite::Error>) -> Self {
+        let text = match item {
+            Ok(Message::Text(t)) => t,
+            Ok(Message::Binary(b)) => match String::from_utf8(b) {
+                Ok(s) => s,
+                Err(_) => {
+                    return Frame::Message(Err(Error::JsonParse(
+                        "binary frame is not valid UTF-8".to_string(),
+                    )))
+                }
+            },
+            Ok(Message::Ping(payload)) => return Frame::Ping(payload),
+            Ok(Message::Close(_)) => return Frame::Close,
+            Ok(Message::Pong(_)) | Ok(Message::Frame(_)) => return Frame::Skip,
+            Err(e) => return Frame::Message(Err(Error::WsReceive(e.to_string()))),
+        };
+        if text == "Websocket connection established." {
+            return Frame::Skip;
+        }
+        Frame::Message(
+            serde_json::from_str::<WsMessage>(&text).map_err(|e| Error::JsonParse(e.to_string())),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,328 @@
+//! In-memory L2 book for one coin, fed from `l2Book` / `bbo` messages or an info snapshot.
+
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use rust_decimal::Decimal;
+
+use super::responses::{Price, WsBbo, WsBook, WsLevel};
+use crate::{error::Error, info::types::L2SnapshotResponse, prelude::Result};
+
+/// Local L2 order book for a single coin.
+///
+/// `l2Book` messages and info snapshots replace the whole book; `bbo` messages only move the
+/// top of each side, dropping levels that the new best price has crossed. Updates older than
+/// the book's `time` are ignored.
+///
+/// # Example
+/// ```
+/// use hl_rs::{responses::WsBook, OrderBook};
+/// use rust_decimal_macros::dec;
+///
+/// let book: WsBook = serde_json::from_value(serde_json::json!({
+///     "coin": "ETH",
+///     "time": 1,
+///     "levels": [
+///         [{"px": "99", "sz": "1", "n": 1}],
+///         [{"px": "101", "sz": "1", "n": 1}, {"px": "102", "sz": "1", "n": 1}],
+///     ],
+/// }))
+/// .unwrap();
+///
+/// let mut ob = OrderBook::new("ETH");
+/// ob.apply_book(&book);
+/// assert_eq!(ob.mid(), Some(dec!(100)));
+/// assert_eq!(ob.vwap(true, dec!(2)), Some(dec!(101.5)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrderBook {
+    coin: String,
+    bids: BTreeMap<Price, WsLevel>,
+    asks: BTreeMap<Price, WsLevel>,
+    time: u64,
+}
+
+impl OrderBook {
+    /// Empty book for `coin`.
+    pub fn new(coin: impl Into<String>) -> Self {
+        Self {
+            coin: coin.into(),
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            time: 0,
+        }
+    }
+
+    /// Book built from an info `l2Book` snapshot.
+    pub fn from_snapshot(snapshot: &L2SnapshotResponse) -> Result<Self> {
+        let mut book = Self::new(&snapshot.coin);
+        book.apply_snapshot(snapshot)?;
+        Ok(book)
+    }
+
+    pub fn coin(&self) -> &str {
+        &self.coin
+    }
+
+    /// Exchange time (ms) of the last update applied.
+    pub fn time(&self) -> u64 {
+        self.time
+    }
+
+    /// Replace the book with an `l2Book` message. Returns `false` if the message is for
+    /// another coin or older than the book.
+    pub fn apply_book(&mut self, book: &WsBook) -> bool {
+        if !self.accepts(&book.coin, book.time) {
+            return false;
+        }
+        let [bids, asks] = &book.levels;
+        self.bids = bids.iter().map(|l| (l.px, l.clone())).collect();
+        self.asks = asks.iter().map(|l| (l.px, l.clone())).collect();
+        self.time = book.time;
+        true
+    }
+
+    /// Move the top of the book to a `bbo` message. Returns `false` if the message is for
+    /// another coin or older than the book.
+    ///
+    /// Levels behind the new best price are kept as they were.
+    pub fn apply_bbo(&mut self, bbo: &WsBbo) -> bool {
+        if !self.accepts(&bbo.coin, bbo.time) {
+            return false;
+        }
+        let [bid, ask] = &bbo.bbo;
+        match bid {
+            Some(level) => {
+                self.bids.retain(|px, _| *px < level.px);
+                self.bids.insert(level.px, level.clone());
+            }
+            None => self.bids.clear(),
+        }
+        match ask {
+            Some(level) => {
+                self.asks.retain(|px, _| *px > level.px);
+                self.asks.insert(level.px, level.clone());
+            }
+            None => self.asks.clear(),
+        }
+        self.time = bbo.time;
+        true
+    }
+
+    /// Replace the book with an info `l2Book` snapshot. Returns `Ok(false)` if the snapshot
+    /// is for another coin or older than the book.
+    pub fn apply_snapshot(&mut self, snapshot: &L2SnapshotResponse) -> Result<bool> {
+        let side = |i: usize| -> Result<Vec<WsLevel>> {
+            snapshot
+                .levels
+                .get(i)
+                .into_iter()
+                .flatten()
+                .map(|l| {
+                    Ok(WsLevel {
+                        px: parse_decimal(&l.px)?,
+                        sz: parse_decimal(&l.sz)?,
+                        n: l.n,
+                    })
+                })
+                .collect()
+        };
+        Ok(self.apply_book(&WsBook {
+            coin: snapshot.coin.clone(),
+            levels: [side(0)?, side(1)?],
+            time: snapshot.time,
+        }))
+    }
+
+    fn accepts(&self, coin: &str, time: u64) -> bool {
+        coin == self.coin && time >= self.time
+    }
+
+    /// Bid levels, best (highest) first.
+    pub fn bids(&self) -> impl Iterator<Item = &WsLevel> {
+        self.bids.values().rev()
+    }
+
+    /// Ask levels, best (lowest) first.
+    pub fn asks(&self) -> impl Iterator<Item = &WsLevel> {
+        self.asks.values()
+    }
+
+    pub fn best_bid(&self) -> Option<&WsLevel> {
+        self.bids().next()
+    }
+
+    pub fn best_ask(&self) -> Option<&WsLevel> {
+        self.asks().next()
+    }
+
+    /// Midpoint of best bid and ask, `None` if either side is empty.
+    pub fn mid(&self) -> Option<Price> {
+        Some((self.best_bid()?.px + self.best_ask()?.px) / Decimal::TWO)
+    }
+
+    /// Best ask minus best bid, `None` if either side is empty.
+    pub fn spread(&self) -> Option<Price> {
+        Some(self.best_ask()?.px - self.best_bid()?.px)
+    }
+
+    /// Size a taker gets for up to `notional` (in quote), walking asks for a buy and bids for
+    /// a sell. Less than requested if the visible book runs out.
+    pub fn depth(&self, is_buy: bool, notional: Decimal) -> Decimal {
+        let mut remaining = notional;
+        let mut size = Decimal::ZERO;
+        for level in self.taker_levels(is_buy) {
+            let level_notional = level.px * level.sz;
+            if level_notional >= remaining {
+                return size + remaining / level.px;
+            }
+            remaining -= level_notional;
+            size += level.sz;
+        }
+        size
+    }
+
+    /// Average price to take `size` immediately, walking asks for a buy and bids for a sell.
+    /// `None` if the visible book cannot fill `size`.
+    pub fn vwap(&self, is_buy: bool, size: Decimal) -> Option<Price> {
+        if size <= Decimal::ZERO {
+            return None;
+        }
+        let mut remaining = size;
+        let mut notional = Decimal::ZERO;
+        for level in self.taker_levels(is_buy) {
+            let take = remaining.min(level.sz);
+            notional += take * level.px;
+            remaining -= take;
+            if remaining.is_zero() {
+                return Some(notional / size);
+            }
+        }
+        None
+    }
+
+    fn taker_levels(&self, is_buy: bool) -> Box<dyn Iterator<Item = &WsLevel> + '_> {
+        if is_buy {
+            Box::new(self.asks())
+        } else {
+            Box::new(self.bids())
+        }
+    }
+
+    /// Whether the last update is older than `max_age`. An empty book is always stale.
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        self.is_stale_at(now.as_millis() as u64, max_age)
+    }
+
+    /// [`is_stale`](Self::is_stale) against a given clock (ms since the epoch).
+    pub fn is_stale_at(&self, now_ms: u64, max_age: Duration) -> bool {
+        self.time == 0 || now_ms.saturating_sub(self.time) > max_age.as_millis() as u64
+    }
+}
+
+fn parse_decimal(value: &str) -> Result<Decimal> {
+    value
+        .parse()
+        .map_err(|e| Error::GenericParse(format!("{value}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    use super::*;
+
+    fn ws_book(time: u64, bids: &[(&str, &str)], asks: &[(&str, &str)]) -> WsBook {
+        let side = |levels: &[(&str, &str)]| {
+            levels
+                .iter()
+                .map(|(px, sz)| json!({"px": px, "sz": sz, "n": 1}))
+                .collect::<Vec<_>>()
+        };
+        serde_json::from_value(json!({
+            "coin": "ETH",
+            "time": time,
+            "levels": [side(bids), side(asks)],
+        }))
+        .unwrap()
+    }
+
+    fn book() -> OrderBook {
+        let mut book = OrderBook::new("ETH");
+        book.apply_book(&ws_book(
+            1_000,
+            &[("99", "1"), ("98", "2")],
+            &[("101", "1"), ("102", "2"), ("104", "1")],
+        ));
+        book
+    }
+
+    #[test]
+    fn top_of_book() {
+        let book = book();
+        assert_eq!(book.best_bid().unwrap().px, dec!(99));
+        assert_eq!(book.best_ask().unwrap().px, dec!(101));
+        assert_eq!(book.mid(), Some(dec!(100)));
+        assert_eq!(book.spread(), Some(dec!(2)));
+        assert_eq!(
+            book.bids().map(|l| l.px).collect::<Vec<_>>(),
+            vec![dec!(99), dec!(98)]
+        );
+    }
+
+    #[test]
+    fn vwap_and_depth_walk_the_book() {
+        let book = book();
+        // 1 @ 101 + 2 @ 102
+        assert_eq!(book.vwap(true, dec!(3)), Some(dec!(305) / dec!(3)));
+        assert_eq!(book.vwap(false, dec!(2)), Some(dec!(98.5)));
+        assert_eq!(book.vwap(true, dec!(5)), None);
+
+        // 101 buys 1 @ 101, the remaining 51 buys 0.5 @ 102.
+        assert_eq!(book.depth(true, dec!(152)), dec!(1.5));
+        assert_eq!(book.depth(true, dec!(1000)), dec!(4));
+    }
+
+    #[test]
+    fn bbo_moves_top_and_drops_crossed_levels() {
+        let mut book = book();
+        let bbo: WsBbo = serde_json::from_value(json!({
+            "coin": "ETH",
+            "time": 2_000,
+            "bbo": [{"px": "98", "sz": "5", "n": 2}, {"px": "102", "sz": "1", "n": 1}],
+        }))
+        .unwrap();
+
+        assert!(book.apply_bbo(&bbo));
+        assert_eq!(book.best_bid().unwrap().sz, dec!(5));
+        assert_eq!(book.bids().count(), 1);
+        assert_eq!(book.best_ask().unwrap().px, dec!(102));
+        assert_eq!(book.asks().count(), 2);
+
+        // An older full book does not overwrite the newer top.
+        assert!(!book.apply_book(&ws_book(1_500, &[("99", "1")], &[("101", "1")])));
+        assert_eq!(book.time(), 2_000);
+    }
+
+    #[test]
+    fn snapshot_and_staleness() {
+        let snapshot: L2SnapshotResponse = serde_json::from_value(json!({
+            "coin": "ETH",
+            "time": 10_000,
+            "levels": [[{"px": "99.5", "sz": "1", "n": 1}], [{"px": "100.5", "sz": "1", "n": 1}]],
+        }))
+        .unwrap();
+        let book = OrderBook::from_snapshot(&snapshot).unwrap();
+
+        assert_eq!(book.mid(), Some(dec!(100)));
+        assert!(!book.is_stale_at(10_500, Duration::from_secs(1)));
+        assert!(book.is_stale_at(11_001, Duration::from_secs(1)));
+        assert!(OrderBook::new("ETH").is_stale(Duration::from_secs(60)));
+    }
+}
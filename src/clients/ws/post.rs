@@ -0,0 +1,161 @@
+//! Info and action requests sent over the socket (`"method": "post"`).
+//!
+//! See <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/websocket/post-requests>.
+//! Payloads are the same JSON bodies the HTTP `/info` and `/exchange` endpoints accept, and
+//! responses carry the same bodies those endpoints return.
+
+use std::time::Duration;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    actions::{Action, SignedAction},
+    clients::exchange::responses::ExchangeResponseStatusRaw,
+    error::{ApiError, Error},
+    info::types::InfoRequest,
+    prelude::Result,
+    ActionResponse,
+};
+
+/// How long a post waits for its response unless configured otherwise.
+pub const DEFAULT_POST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `request` field of an outgoing post.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "payload", rename_all = "lowercase")]
+pub(crate) enum PostRequest {
+    Info(Value),
+    Action(Value),
+}
+
+impl PostRequest {
+    pub fn info(request: &InfoRequest) -> Result<Self> {
+        to_value(request).map(Self::Info)
+    }
+
+    pub fn action<A: Action + Serialize>(signed_action: &SignedAction<A>) -> Result<Self> {
+        to_value(signed_action).map(Self::Action)
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<Value> {
+    serde_json::to_value(value).map_err(|e| Error::SerializationFailure(e.to_string()))
+}
+
+/// Response on the `post` channel, matched to its request by `id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WsPostResponse {
+    pub id: u64,
+    pub response: WsPostPayload,
+}
+
+/// Body of a [`WsPostResponse`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload", rename_all = "lowercase")]
+pub enum WsPostPayload {
+    /// `{"type": <info type>, "data": <same body as HTTP /info>}`.
+    Info(Value),
+    /// Same body as HTTP `/exchange` (`{"status": "ok" | "err", "response": ...}`).
+    Action(Value),
+    /// The request could not be processed at all.
+    Error(String),
+}
+
+impl WsPostResponse {
+    /// Parse an info response into the type the matching [`InfoClient`](crate::InfoClient)
+    /// method returns.
+    pub fn into_info<T: DeserializeOwned>(self) -> Result<T> {
+        match self.response {
+            WsPostPayload::Info(mut payload) => serde_json::from_value(payload["data"].take())
+                .map_err(|e| Error::JsonParse(e.to_string())),
+            WsPostPayload::Error(message) => Err(Error::Api(ApiError::classify(message))),
+            WsPostPayload::Action(_) => Err(Error::GenericParse(
+                "expected info response, got action response".to_string(),
+            )),
+        }
+    }
+
+    /// Parse an action response into the action's typed response, as
+    /// [`ExchangeClient::send_signed_action`](crate::ExchangeClient::send_signed_action) does.
+    pub fn into_action<R: ActionResponse>(self) -> Result<R> {
+        match self.response {
+            WsPostPayload::Action(payload) => {
+                let raw: ExchangeResponseStatusRaw =
+                    serde_json::from_value(payload).map_err(|e| Error::JsonParse(e.to_string()))?;
+                R::from_response(raw.into_result()?)
+            }
+            WsPostPayload::Error(message) => Err(Error::Api(ApiError::classify(message))),
+            WsPostPayload::Info(_) => Err(Error::GenericParse(
+                "expected action response, got info response".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{info::types::L2SnapshotResponse, ExchangeDataStatus, ExchangeDataStatuses};
+
+    fn response(value: Value) -> WsPostResponse {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn info_request_wraps_http_body() {
+        let request = PostRequest::info(&InfoRequest::L2Book {
+            coin: "ETH".to_string(),
+        })
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(request).unwrap(),
+            json!({"type": "info", "payload": {"type": "l2Book", "coin": "ETH"}})
+        );
+    }
+
+    #[test]
+    fn info_response_parses_like_http() {
+        let book: L2SnapshotResponse = response(json!({
+            "id": 3,
+            "response": {"type": "info", "payload": {"type": "l2Book", "data": {
+                "coin": "ETH",
+                "time": 1,
+                "levels": [[{"px": "1", "sz": "2", "n": 1}], []],
+            }}}
+        }))
+        .into_info()
+        .unwrap();
+        assert_eq!(book.coin, "ETH");
+    }
+
+    #[test]
+    fn action_response_parses_like_http() {
+        let statuses: ExchangeDataStatuses = response(json!({
+            "id": 4,
+            "response": {"type": "action", "payload": {
+                "status": "ok",
+                "response": {"type": "order", "data": {"statuses": [{"resting": {"oid": 9}}]}},
+            }}
+        }))
+        .into_action()
+        .unwrap();
+        assert!(matches!(
+            statuses.statuses[0],
+            ExchangeDataStatus::Resting(ref r) if r.oid == 9
+        ));
+
+        let err = response(json!({
+            "id": 5,
+            "response": {"type": "action", "payload": {
+                "status": "err",
+                "response": "Insufficient margin to place order.",
+            }}
+        }))
+        .into_action::<ExchangeDataStatuses>()
+        .unwrap_err();
+        assert!(matches!(err, Error::Api(_)));
+    }
+}
@@ -16,6 +16,9 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+pub use crate::info::types::Candle;
+use crate::{info::types::UserStateResponse, Cloid};
+
 /// Mid / last / mark / oracle / limit / OHLC **price** (per HL WS docs).
 pub type Price = Decimal;
 
@@ -152,35 +155,6 @@ pub struct WsBbo {
     pub bbo: [Option<WsLevel>; 2],
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Candle {
-    #[serde(rename = "t")]
-    pub open_ms: u64,
-    #[serde(rename = "T")]
-    pub close_ms: u64,
-    #[serde(rename = "s")]
-    pub coin: String,
-    #[serde(rename = "i")]
-    pub interval: String,
-    #[serde(rename = "o")]
-    #[serde(deserialize_with = "serde_decimal::de")]
-    pub open: Price,
-    #[serde(rename = "c")]
-    #[serde(deserialize_with = "serde_decimal::de")]
-    pub close: Price,
-    #[serde(rename = "h")]
-    #[serde(deserialize_with = "serde_decimal::de")]
-    pub high: Price,
-    #[serde(rename = "l")]
-    #[serde(deserialize_with = "serde_decimal::de")]
-    pub low: Price,
-    #[serde(rename = "v")]
-    #[serde(deserialize_with = "serde_decimal::de")]
-    pub volume: Decimal,
-    #[serde(rename = "n")]
-    pub num_trades: u64,
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WsBasicOrder {
@@ -194,7 +168,7 @@ pub struct WsBasicOrder {
     pub timestamp: u64,
     #[serde(deserialize_with = "serde_decimal::de")]
     pub orig_sz: Decimal,
-    pub cloid: Option<String>,
+    pub cloid: Option<Cloid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -241,6 +215,8 @@ pub struct WsFill {
     #[serde(default)]
     #[serde(deserialize_with = "serde_decimal::de_opt")]
     pub builder_fee: Option<Decimal>,
+    #[serde(default)]
+    pub cloid: Option<Cloid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -484,7 +460,18 @@ pub struct WsSpotState {
 #[serde(rename_all = "camelCase")]
 pub struct WsAllDexsClearinghouseState {
     pub user: String,
-    pub clearinghouse_states: HashMap<String, Value>,
+    /// Dex name (`""` for the default dex) → account state on that dex.
+    pub clearinghouse_states: HashMap<String, UserStateResponse>,
+}
+
+/// `clearinghouseState` payload: perp account state of `user` on `dex`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WsClearinghouseState {
+    #[serde(default)]
+    pub dex: String,
+    pub user: String,
+    pub clearinghouse_state: UserStateResponse,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -512,8 +499,26 @@ pub struct OpenOrders {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WebData3 {
+    /// Account metadata (agent, vault flag, server time, ...).
     pub user_state: Value,
-    pub perp_dex_states: Vec<Value>,
+    /// One entry per perp dex.
+    pub perp_dex_states: Vec<WsPerpDexState>,
+}
+
+/// Per-dex part of [`WebData3`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WsPerpDexState {
+    #[serde(default)]
+    pub clearinghouse_state: Option<UserStateResponse>,
+    #[serde(default)]
+    #[serde(deserialize_with = "serde_decimal::de_opt")]
+    pub total_vault_equity: Option<Decimal>,
+    #[serde(default)]
+    pub perps_at_open_interest_cap: Option<Vec<String>>,
+    /// Remaining fields (open orders, asset contexts, leading vaults, ...) as sent.
+    #[serde(flatten)]
+    pub other: HashMap<String, Value>,
 }
 
 #[cfg(test)]
@@ -544,4 +549,35 @@ mod tests {
         let e: WsUserEvent = serde_json::from_value(j).unwrap();
         assert!(matches!(e, WsUserEvent::Fills { .. }));
     }
+
+    #[test]
+    fn web_data3_perp_dex_states_are_typed() {
+        let j = serde_json::json!({
+            "userState": {"user": "0xabc", "serverTime": 1, "isVault": false},
+            "perpDexStates": [{
+                "clearinghouseState": {
+                    "marginSummary": {
+                        "accountValue": "1", "totalNtlPos": "0", "totalRawUsd": "1",
+                        "totalMarginUsed": "0",
+                    },
+                    "crossMarginSummary": {
+                        "accountValue": "1", "totalNtlPos": "0", "totalRawUsd": "1",
+                        "totalMarginUsed": "0",
+                    },
+                    "withdrawable": "1",
+                    "assetPositions": [],
+                },
+                "totalVaultEquity": "12.5",
+                "leadingVaults": [],
+            }],
+        });
+        let data: WebData3 = serde_json::from_value(j).unwrap();
+        let dex = &data.perp_dex_states[0];
+        assert_eq!(
+            dex.clearinghouse_state.as_ref().unwrap().withdrawable,
+            Decimal::ONE
+        );
+        assert_eq!(dex.total_vault_equity, Some(Decimal::new(125, 1)));
+        assert!(dex.other.contains_key("leadingVaults"));
+    }
 }
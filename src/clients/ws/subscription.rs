@@ -4,8 +4,11 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::WsMessage;
+use crate::info::types::CandleInterval;
+
 /// Subscription request for a specific feed.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum Subscription {
     #[serde(rename = "allMids")]
@@ -14,10 +17,14 @@ pub enum Subscription {
         dex: Option<String>,
     },
 
-    Notification { user: String },
+    Notification {
+        user: String,
+    },
 
     #[serde(rename = "webData3")]
-    WebData3 { user: String },
+    WebData3 {
+        user: String,
+    },
 
     #[serde(rename = "twapStates")]
     TwapStates {
@@ -34,11 +41,14 @@ pub enum Subscription {
     },
 
     #[serde(rename = "openOrders")]
-    OpenOrders { user: String, dex: Option<String> },
+    OpenOrders {
+        user: String,
+        dex: Option<String>,
+    },
 
     Candle {
         coin: String,
-        interval: String,
+        interval: CandleInterval,
     },
 
     #[serde(rename = "l2Book")]
@@ -50,13 +60,19 @@ pub enum Subscription {
         mantissa: Option<i32>,
     },
 
-    Trades { coin: String },
+    Trades {
+        coin: String,
+    },
 
     #[serde(rename = "orderUpdates")]
-    OrderUpdates { user: String },
+    OrderUpdates {
+        user: String,
+    },
 
     #[serde(rename = "userEvents")]
-    UserEvents { user: String },
+    UserEvents {
+        user: String,
+    },
 
     #[serde(rename = "userFills")]
     UserFills {
@@ -66,24 +82,39 @@ pub enum Subscription {
     },
 
     #[serde(rename = "userFundings")]
-    UserFundings { user: String },
+    UserFundings {
+        user: String,
+    },
 
     #[serde(rename = "userNonFundingLedgerUpdates")]
-    UserNonFundingLedgerUpdates { user: String },
+    UserNonFundingLedgerUpdates {
+        user: String,
+    },
 
     #[serde(rename = "activeAssetCtx")]
-    ActiveAssetCtx { coin: String },
+    ActiveAssetCtx {
+        coin: String,
+    },
 
     #[serde(rename = "activeAssetData")]
-    ActiveAssetData { user: String, coin: String },
+    ActiveAssetData {
+        user: String,
+        coin: String,
+    },
 
     #[serde(rename = "userTwapSliceFills")]
-    UserTwapSliceFills { user: String },
+    UserTwapSliceFills {
+        user: String,
+    },
 
     #[serde(rename = "userTwapHistory")]
-    UserTwapHistory { user: String },
+    UserTwapHistory {
+        user: String,
+    },
 
-    Bbo { coin: String },
+    Bbo {
+        coin: String,
+    },
 
     #[serde(rename = "spotState")]
     SpotState {
@@ -93,8 +124,198 @@ pub enum Subscription {
     },
 
     #[serde(rename = "allDexsClearinghouseState")]
-    AllDexsClearinghouseState { user: String },
+    AllDexsClearinghouseState {
+        user: String,
+    },
 
     #[serde(rename = "allDexsAssetCtxs")]
     AllDexsAssetCtxs,
 }
+
+impl Subscription {
+    /// Wire name of the subscription type, which is also the `channel` of its messages.
+    pub fn channel(&self) -> String {
+        let value = serde_json::to_value(self).unwrap_or_default();
+        value["type"].as_str().unwrap_or_default().to_string()
+    }
+
+    /// Whether `message` was produced by this subscription.
+    ///
+    /// Compares the channel and every identity field (coin, candle interval, user, dex) that
+    /// both the subscription and the payload carry, so two `l2Book` feeds for different coins
+    /// are told apart. Payloads that carry none of them (e.g. `orderUpdates`) match on the
+    /// channel alone. A subscription without a dex is for the default dex, so it only matches
+    /// payloads whose `dex` is `""`.
+    pub fn matches(&self, message: &WsMessage) -> bool {
+        self.key().matches(message)
+    }
+
+    /// Identity to match messages against, for callers matching many messages.
+    pub(crate) fn key(&self) -> FeedKey {
+        let mut key = FeedKey {
+            channel: self.channel(),
+            ..FeedKey::default()
+        };
+        match self {
+            Self::AllMids { dex } => key.dex = dex.clone(),
+            Self::Notification { user }
+            | Self::WebData3 { user }
+            | Self::OrderUpdates { user }
+            | Self::UserEvents { user }
+            | Self::UserFills { user, .. }
+            | Self::UserFundings { user }
+            | Self::UserNonFundingLedgerUpdates { user }
+            | Self::UserTwapSliceFills { user }
+            | Self::UserTwapHistory { user }
+            | Self::SpotState { user, .. }
+            | Self::AllDexsClearinghouseState { user } => key.user = Some(user.clone()),
+            Self::TwapStates { user, dex }
+            | Self::ClearinghouseState { user, dex }
+            | Self::OpenOrders { user, dex } => {
+                key.user = Some(user.clone());
+                // The default dex, `""` in payloads.
+                key.dex = Some(dex.clone().unwrap_or_default());
+            }
+            Self::Candle { coin, interval } => {
+                key.coin = Some(coin.clone());
+                key.interval = Some(*interval);
+            }
+            Self::L2Book { coin, .. }
+            | Self::Trades { coin }
+            | Self::ActiveAssetCtx { coin }
+            | Self::Bbo { coin } => key.coin = Some(coin.clone()),
+            Self::ActiveAssetData { user, coin } => {
+                key.user = Some(user.clone());
+                key.coin = Some(coin.clone());
+            }
+            Self::AllDexsAssetCtxs => {}
+        }
+        key
+    }
+}
+
+/// What a [`Subscription`] is for, extracted once so matching a message needs no
+/// serialization.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct FeedKey {
+    channel: String,
+    coin: Option<String>,
+    interval: Option<CandleInterval>,
+    user: Option<String>,
+    dex: Option<String>,
+}
+
+impl FeedKey {
+    /// See [`Subscription::matches`].
+    pub fn matches(&self, message: &WsMessage) -> bool {
+        if self.channel != message.channel() {
+            return false;
+        }
+        let id = message.identity();
+        // Addresses may differ in checksum casing.
+        let same = |expected: &Option<String>, actual: Option<&str>| match (expected, actual) {
+            (Some(expected), Some(actual)) => expected.eq_ignore_ascii_case(actual),
+            _ => true,
+        };
+        same(&self.coin, id.coin)
+            && same(&self.user, id.user)
+            && same(&self.dex, id.dex)
+            && match (self.interval, id.interval) {
+                (Some(expected), Some(actual)) => expected == actual,
+                _ => true,
+            }
+    }
+}
+
+/// Coin, candle interval, user and dex a payload carries, see [`WsMessage::identity`].
+#[derive(Debug, Default)]
+pub(crate) struct Identity<'a> {
+    pub coin: Option<&'a str>,
+    pub interval: Option<CandleInterval>,
+    pub user: Option<&'a str>,
+    pub dex: Option<&'a str>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn message(value: Value) -> WsMessage {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn matches_on_channel_and_identity() {
+        let book = message(json!({
+            "channel": "l2Book",
+            "data": {"coin": "ETH", "time": 1, "levels": [[], []]},
+        }));
+        let eth = Subscription::L2Book {
+            coin: "ETH".to_string(),
+            n_sig_figs: Some(5),
+            mantissa: None,
+        };
+        let btc = Subscription::L2Book {
+            coin: "BTC".to_string(),
+            n_sig_figs: None,
+            mantissa: None,
+        };
+        assert!(eth.matches(&book));
+        assert!(!btc.matches(&book));
+        assert!(!Subscription::Bbo {
+            coin: "ETH".to_string()
+        }
+        .matches(&book));
+    }
+
+    #[test]
+    fn matches_users_case_insensitively() {
+        let fills = message(json!({
+            "channel": "userFills",
+            "data": {"user": "0xabcdef", "fills": []},
+        }));
+        let user = |user: &str| Subscription::UserFills {
+            user: user.to_string(),
+            aggregate_by_time: None,
+        };
+        assert!(user("0xABCDEF").matches(&fills));
+        assert!(!user("0x123456").matches(&fills));
+    }
+
+    #[test]
+    fn default_dex_subscriptions_skip_hip3_payloads() {
+        let state = |dex: &str| {
+            message(json!({
+                "channel": "openOrders",
+                "data": {"dex": dex, "user": "0xabcdef", "orders": []},
+            }))
+        };
+        let sub = |dex: Option<&str>| Subscription::OpenOrders {
+            user: "0xabcdef".to_string(),
+            dex: dex.map(str::to_string),
+        };
+        assert!(sub(None).matches(&state("")));
+        assert!(!sub(None).matches(&state("xyz")));
+        assert!(sub(Some("xyz")).matches(&state("xyz")));
+        assert!(!sub(Some("xyz")).matches(&state("")));
+    }
+
+    #[test]
+    fn matches_candles_by_coin_and_interval() {
+        let candle = message(json!({
+            "channel": "candle",
+            "data": {
+                "t": 0, "T": 59_999, "s": "ETH", "i": "1m",
+                "o": "1", "c": "1", "h": "1", "l": "1", "v": "0", "n": 0,
+            },
+        }));
+        let sub = |interval| Subscription::Candle {
+            coin: "ETH".to_string(),
+            interval,
+        };
+        assert!(sub(CandleInterval::OneMinute).matches(&candle));
+        assert!(!sub(CandleInterval::FiveMinutes).matches(&candle));
+    }
+}
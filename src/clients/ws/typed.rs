@@ -0,0 +1,170 @@
+//! Typed per-subscription streams, see [`ManagedWsClient::subscribe_typed`].
+//!
+//! [`ManagedWsClient::subscribe_typed`]: super::ManagedWsClient::subscribe_typed
+
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{ready, Context, Poll},
+};
+
+use futures_util::Stream;
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+use super::responses::*;
+use super::subscription::FeedKey;
+use super::{Subscription, WsMessage};
+
+/// Buffer size of each typed stream unless configured otherwise.
+pub const DEFAULT_STREAM_BUFFER: usize = 1024;
+
+/// Concrete `data` type of one WebSocket channel.
+pub trait WsPayload: Sized + Send + 'static {
+    /// Channel (and subscription type) whose messages carry this payload.
+    const CHANNEL: &'static str;
+
+    /// The payload of `message`, `None` if it is from another channel.
+    fn from_message(message: WsMessage) -> Option<Self>;
+}
+
+macro_rules! impl_ws_payload {
+    ($($variant:ident($payload:ty) => $channel:literal,)*) => {
+        $(
+            impl WsPayload for $payload {
+                const CHANNEL: &'static str = $channel;
+
+                fn from_message(message: WsMessage) -> Option<Self> {
+                    match message {
+                        WsMessage::$variant { data, .. } => Some(data),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_ws_payload! {
+    AllMids(AllMids) => "allMids",
+    Notification(Notification) => "notification",
+    WebData3(WebData3) => "webData3",
+    TwapStates(TwapStates) => "twapStates",
+    ClearinghouseState(WsClearinghouseState) => "clearinghouseState",
+    OpenOrders(OpenOrders) => "openOrders",
+    Candle(Candle) => "candle",
+    L2Book(WsBook) => "l2Book",
+    Trades(Vec<WsTrade>) => "trades",
+    OrderUpdates(Vec<WsOrder>) => "orderUpdates",
+    UserEvents(WsUserEvent) => "userEvents",
+    UserFills(WsUserFills) => "userFills",
+    UserFundings(WsUserFundings) => "userFundings",
+    UserNonFundingLedgerUpdates(WsUserNonFundingLedgerUpdates) => "userNonFundingLedgerUpdates",
+    ActiveAssetCtx(WsActiveAssetCtx) => "activeAssetCtx",
+    ActiveAssetData(WsActiveAssetData) => "activeAssetData",
+    UserTwapSliceFills(WsUserTwapSliceFills) => "userTwapSliceFills",
+    UserTwapHistory(WsUserTwapHistory) => "userTwapHistory",
+    Bbo(WsBbo) => "bbo",
+    SpotState(WsSpotState) => "spotState",
+    AllDexsClearinghouseState(WsAllDexsClearinghouseState) => "allDexsClearinghouseState",
+    AllDexsAssetCtxs(WsAllDexsAssetCtxs) => "allDexsAssetCtxs",
+}
+
+/// Stream of one subscription's payloads.
+///
+/// Backed by a bounded buffer: when the consumer falls behind, new messages are dropped and
+/// counted in [`lagged`](Self::lagged) rather than stalling the socket for everyone else.
+/// After a reconnect, [`resynced`](Self::resynced) flags the first payload of the replayed
+/// subscription. Ends when the subscription is unsubscribed or the client is dropped. Dropping
+/// the stream unsubscribes once no other stream uses the same subscription.
+pub struct SubscriptionStream<T> {
+    subscription: Subscription,
+    receiver: mpsc::Receiver<(WsMessage, bool)>,
+    lagged: Arc<AtomicU64>,
+    resynced: bool,
+    _payload: PhantomData<fn() -> T>,
+}
+
+impl<T> SubscriptionStream<T> {
+    pub fn subscription(&self) -> &Subscription {
+        &self.subscription
+    }
+
+    /// Messages dropped so far because the buffer was full.
+    pub fn lagged(&self) -> u64 {
+        self.lagged.load(Ordering::Relaxed)
+    }
+
+    /// Whether the payload last returned is the first one after a reconnect, i.e. a fresh
+    /// snapshot that should replace local state rather than be applied on top of it.
+    pub fn resynced(&self) -> bool {
+        self.resynced
+    }
+}
+
+impl<T: WsPayload> Stream for SubscriptionStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        loop {
+            match ready!(self.receiver.poll_recv(cx)) {
+                Some((message, resync)) => {
+                    if let Some(payload) = T::from_message(message) {
+                        self.resynced = resync;
+                        return Poll::Ready(Some(payload));
+                    }
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// Sending half of a [`SubscriptionStream`], held by the connection task.
+pub(crate) struct Route {
+    pub subscription: Subscription,
+    /// `subscription`'s key, computed once rather than for every message.
+    pub key: FeedKey,
+    sender: mpsc::Sender<(WsMessage, bool)>,
+    lagged: Arc<AtomicU64>,
+}
+
+impl Route {
+    pub fn new<T>(subscription: Subscription, capacity: usize) -> (Self, SubscriptionStream<T>) {
+        let (sender, receiver) = mpsc::channel(capacity);
+        let lagged = Arc::new(AtomicU64::new(0));
+        let route = Self {
+            key: subscription.key(),
+            subscription: subscription.clone(),
+            sender,
+            lagged: lagged.clone(),
+        };
+        let stream = SubscriptionStream {
+            subscription,
+            receiver,
+            lagged,
+            resynced: false,
+            _payload: PhantomData,
+        };
+        (route, stream)
+    }
+
+    /// Hand `message` to the stream without waiting. Returns `false` once the stream is gone.
+    pub fn deliver(&self, message: &WsMessage, resync: bool) -> bool {
+        match self.sender.try_send((message.clone(), resync)) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                self.lagged.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+            Err(TrySendError::Closed(_)) => false,
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
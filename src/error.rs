@@ -1,11 +1,17 @@
+use std::str::FromStr;
+
+use alloy::primitives::Address;
+use rust_decimal::Decimal;
 use thiserror::Error;
 
+use crate::info::types::CandleInterval;
+
 #[derive(Error, Debug, Clone)]
 pub enum Error {
     #[error(transparent)]
     Api(#[from] ApiError),
 
-    #[error("Signer not set. Call `ExchangeClientV2::with_signer(...)` before sending actions.")]
+    #[error("Signer not set. Call `ExchangeClient::with_signer(...)` before sending actions.")]
     SignerNotSet,
 
     #[error(
@@ -39,6 +45,46 @@ pub enum Error {
         rust_type: &'static str,
         abi_type: String,
     },
+    /// Coin name not found in the asset registry.
+    #[error("Unknown coin: {0}")]
+    UnknownCoin(String),
+    /// Order price or size does not fit the asset's tick/lot rules.
+    #[error("Invalid order precision: {0}")]
+    InvalidPrecision(String),
+    /// Amount cannot be expressed in the integer units the wire field takes.
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+    /// Client order id is not `0x` followed by 32 hex digits.
+    #[error("Invalid cloid: {0}")]
+    InvalidCloid(String),
+    /// No open position to close for the given coin.
+    #[error("No open position for {0}")]
+    NoOpenPosition(String),
+    /// Agent wallets can only sign L1 actions.
+    #[error("`{0}` is user-signed and cannot be signed by an agent wallet")]
+    UserSignedByAgent(&'static str),
+    /// Client has no master address. Call `ExchangeClient::with_agent(...)` first.
+    #[error("Agent mode not configured. Call `ExchangeClient::with_agent(...)` first.")]
+    AgentNotConfigured,
+    /// Signature recovers to a different address than expected.
+    #[error("Signed by {recovered}, expected {expected}")]
+    SignerMismatch {
+        expected: Address,
+        recovered: Address,
+    },
+    /// Multi-sig signature is malformed, over the wrong action, or from an unauthorized user.
+    #[error("Invalid multi-sig signature: {0}")]
+    InvalidMultiSig(String),
+    /// Fewer valid multi-sig signatures than the user's threshold.
+    #[error("Multi-sig threshold not met: {collected} of {threshold} signatures")]
+    MultiSigThresholdNotMet { threshold: u32, collected: usize },
+    /// `userRole` does not link the agent to the expected master.
+    #[error("Agent {agent} is not approved for {master} (user role: {role})")]
+    AgentNotApproved {
+        agent: Address,
+        master: Address,
+        role: String,
+    },
     /// WebSocket connection failed.
     #[error("WebSocket connect error: {0}")]
     WsConnect(String),
@@ -48,24 +94,335 @@ pub enum Error {
     /// WebSocket receive failed.
     #[error("WebSocket receive error: {0}")]
     WsReceive(String),
+    /// No response to a WebSocket post within the configured timeout.
+    #[error("WebSocket post {id} timed out")]
+    WsPostTimeout { id: u64 },
+    /// A typed stream was requested for a subscription that produces another payload.
+    #[error("`{subscription}` subscription does not produce `{payload}` messages")]
+    WsPayloadMismatch {
+        subscription: String,
+        payload: &'static str,
+    },
+    /// Candles of one interval cannot be combined into bars of another.
+    #[error("{from} candles do not fit evenly into {to} candles")]
+    IncompatibleInterval {
+        from: CandleInterval,
+        to: CandleInterval,
+    },
+    /// A cassette could not be written or read.
+    #[error("Cassette error: {0}")]
+    Cassette(String),
+    /// A nonce outside the window the exchange accepts around its time.
+    #[error("Nonce {nonce} is too far from server time {server_time}")]
+    NonceOutOfWindow { nonce: u64, server_time: u64 },
+    /// A nonce file could not be read, locked or written.
+    #[error("Nonce store error: {0}")]
+    NonceStore(String),
+    /// Invalid dead man's switch settings.
+    #[error("Dead man's switch: {0}")]
+    DeadManSwitch(String),
 }
 
-#[derive(Error, Debug, Clone)]
+/// Exchange rejection, classified from the error string returned either at the top level
+/// (`{"status":"err"}`) or per order/cancel inside an `ok` response.
+///
+/// Use [`ApiError::classify`] to turn a raw message into a variant. The original message is
+/// always kept in `message`.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum ApiError {
     #[error(
         "Insufficient staked HYPE: {message}. You need to stake HYPE tokens to deploy perp assets."
     )]
     InsufficientStakedHype { message: String },
-    // #[error(
-    //     "Signature verification failed: {message}. This usually indicates a mismatch between the signed data and what the server expects. Check that you're using the correct wallet and network."
-    // )]
-    // When Hyperliquid computes a different signature compared to what you provided it answers "User or API Wallet 0x… does not exist" (with a different value every time). Most likely because the p and s are invalid, they don’t follow Hyperliquid’s formatting rules.
-    // SignatureMismatch { message: String },
-
-    // #[error(
-    //     "User or API wallet not found: {address}. This may indicate a signature mismatch - the server recovered a different address from your signature than expected."
-    // )]
-    // WalletNotFound { address: String },
+    /// Not enough margin (perps) or balance (spot) for the order.
+    #[error("Insufficient margin: {message}")]
+    InsufficientMargin { asset: Option<u32>, message: String },
+    /// Price is not on the asset's tick grid.
+    #[error("Invalid tick size: {message}")]
+    InvalidTickSize { asset: Option<u32>, message: String },
+    /// Size is zero or not on the asset's lot grid.
+    #[error("Invalid lot size: {message}")]
+    InvalidLotSize { asset: Option<u32>, message: String },
+    /// Order notional is below the exchange minimum.
+    #[error("Below minimum notional: {message}")]
+    MinNotional {
+        asset: Option<u32>,
+        /// Minimum order value in USD, e.g. `10` for `"minimum value of $10"`
+        min_value: Option<Decimal>,
+        message: String,
+    },
+    /// Reduce-only order would open or increase a position.
+    #[error("Reduce-only rejected: {message}")]
+    ReduceOnlyRejected { asset: Option<u32>, message: String },
+    /// Post-only (ALO) order would have crossed the book.
+    #[error("Post-only order would cross: {message}")]
+    PostOnlyWouldCross {
+        asset: Option<u32>,
+        best_bid: Option<Decimal>,
+        best_ask: Option<Decimal>,
+        message: String,
+    },
+    /// IOC order found nothing to match against.
+    #[error("IOC order did not match: {message}")]
+    IocNoMatch { asset: Option<u32>, message: String },
+    /// Nonce already used, or outside the accepted time window.
+    #[error("Invalid nonce: {message}")]
+    InvalidNonce { message: String },
+    /// Address-based or IP-based rate limit hit.
+    #[error("Rate limited: {message}")]
+    RateLimited { message: String },
+    /// The address recovered from the signature is not a known user or API wallet.
+    ///
+    /// Hyperliquid also answers this way when the signature itself is wrong (wrong chain,
+    /// wrong payload, malformed r/s): it recovers a different address every time, so a
+    /// changing `address` usually means a signing mismatch rather than a missing account.
+    #[error("User or API wallet not found: {message}")]
+    WalletNotFound {
+        address: Option<Address>,
+        message: String,
+    },
+    /// Acting on behalf of a vault that does not exist.
+    #[error("Vault not registered: {message}")]
+    VaultNotRegistered {
+        address: Option<Address>,
+        message: String,
+    },
     #[error("Exchange API error: {message}")]
     Other { message: String },
 }
+
+impl ApiError {
+    /// Classify a raw exchange error string.
+    pub fn classify(message: impl Into<String>) -> Self {
+        let message = message.into();
+        let lower = message.to_lowercase();
+        let asset = parse_asset(&message);
+
+        if lower.contains("insufficient staked") {
+            ApiError::InsufficientStakedHype { message }
+        } else if lower.contains("user or api wallet") {
+            ApiError::WalletNotFound {
+                address: parse_address(&message),
+                message,
+            }
+        } else if lower.contains("vault not registered") {
+            ApiError::VaultNotRegistered {
+                address: parse_address(&message),
+                message,
+            }
+        } else if lower.contains("insufficient margin")
+            || lower.contains("insufficient spot balance")
+        {
+            ApiError::InsufficientMargin { asset, message }
+        } else if lower.contains("tick size") || lower.contains("invalid price") {
+            ApiError::InvalidTickSize { asset, message }
+        } else if lower.contains("lot size")
+            || lower.contains("invalid size")
+            || lower.contains("zero size")
+        {
+            ApiError::InvalidLotSize { asset, message }
+        } else if lower.contains("minimum value") {
+            ApiError::MinNotional {
+                asset,
+                min_value: parse_min_value(&message),
+                message,
+            }
+        } else if lower.contains("reduce only") {
+            ApiError::ReduceOnlyRejected { asset, message }
+        } else if lower.contains("post only") {
+            let (best_bid, best_ask) = parse_bbo(&message);
+            ApiError::PostOnlyWouldCross {
+                asset,
+                best_bid,
+                best_ask,
+                message,
+            }
+        } else if lower.contains("could not immediately match") {
+            ApiError::IocNoMatch { asset, message }
+        } else if lower.contains("nonce") {
+            ApiError::InvalidNonce { message }
+        } else if lower.contains("too many") || lower.contains("rate limit") {
+            ApiError::RateLimited { message }
+        } else {
+            ApiError::Other { message }
+        }
+    }
+
+    /// The raw message returned by the exchange.
+    pub fn message(&self) -> &str {
+        match self {
+            ApiError::InsufficientStakedHype { message }
+            | ApiError::InsufficientMargin { message, .. }
+            | ApiError::InvalidTickSize { message, .. }
+            | ApiError::InvalidLotSize { message, .. }
+            | ApiError::MinNotional { message, .. }
+            | ApiError::ReduceOnlyRejected { message, .. }
+            | ApiError::PostOnlyWouldCross { message, .. }
+            | ApiError::IocNoMatch { message, .. }
+            | ApiError::InvalidNonce { message }
+            | ApiError::RateLimited { message }
+            | ApiError::WalletNotFound { message, .. }
+            | ApiError::VaultNotRegistered { message, .. }
+            | ApiError::Other { message } => message,
+        }
+    }
+
+    /// Whether resending the same request later (with a fresh nonce) can succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ApiError::InvalidNonce { .. } | ApiError::RateLimited { .. }
+        )
+    }
+}
+
+impl From<String> for ApiError {
+    fn from(message: String) -> Self {
+        ApiError::classify(message)
+    }
+}
+
+/// `asset=4` suffix carried by most order rejections.
+fn parse_asset(message: &str) -> Option<u32> {
+    let rest = &message[message.find("asset=")? + "asset=".len()..];
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+/// First `0x`-prefixed 20-byte hex address in the message.
+fn parse_address(message: &str) -> Option<Address> {
+    message.match_indices("0x").find_map(|(i, _)| {
+        let candidate = message.get(i..i + 42)?;
+        Address::from_str(candidate).ok()
+    })
+}
+
+/// `"Order must have minimum value of $10."` -> `10`.
+fn parse_min_value(message: &str) -> Option<Decimal> {
+    let rest = &message[message.find('$')? + 1..];
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(rest.len());
+    Decimal::from_str(rest[..end].trim_end_matches('.')).ok()
+}
+
+/// `"... bbo was 3150.1@3150.2."` -> `(3150.1, 3150.2)`.
+fn parse_bbo(message: &str) -> (Option<Decimal>, Option<Decimal>) {
+    let Some(start) = message.find("bbo was ") else {
+        return (None, None);
+    };
+    let rest = &message[start + "bbo was ".len()..];
+    let bbo = rest.split_whitespace().next().unwrap_or_default();
+    let mut sides = bbo
+        .trim_end_matches(['.', ','])
+        .split('@')
+        .map(|px| Decimal::from_str(px).ok());
+    (sides.next().flatten(), sides.next().flatten())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::address;
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn classifies_order_rejections_with_asset() {
+        assert_eq!(
+            ApiError::classify("Insufficient margin to place order. asset=4"),
+            ApiError::InsufficientMargin {
+                asset: Some(4),
+                message: "Insufficient margin to place order. asset=4".to_string(),
+            }
+        );
+        assert!(matches!(
+            ApiError::classify("Price must be divisible by tick size. asset=0"),
+            ApiError::InvalidTickSize { asset: Some(0), .. }
+        ));
+        assert!(matches!(
+            ApiError::classify("Order has zero size."),
+            ApiError::InvalidLotSize { asset: None, .. }
+        ));
+        assert!(matches!(
+            ApiError::classify("Reduce only order would increase position. asset=110000"),
+            ApiError::ReduceOnlyRejected {
+                asset: Some(110000),
+                ..
+            }
+        ));
+        assert!(matches!(
+            ApiError::classify(
+                "Order could not immediately match against any resting orders. asset=1"
+            ),
+            ApiError::IocNoMatch { asset: Some(1), .. }
+        ));
+    }
+
+    #[test]
+    fn parses_min_notional_and_bbo() {
+        assert!(matches!(
+            ApiError::classify("Order must have minimum value of $10. asset=4"),
+            ApiError::MinNotional {
+                asset: Some(4),
+                min_value: Some(v),
+                ..
+            } if v == dec!(10)
+        ));
+        assert!(matches!(
+            ApiError::classify("Post only order would have immediately matched, bbo was 3150.1@3150.2. asset=4"),
+            ApiError::PostOnlyWouldCross {
+                asset: Some(4),
+                best_bid: Some(bid),
+                best_ask: Some(ask),
+                ..
+            } if bid == dec!(3150.1) && ask == dec!(3150.2)
+        ));
+    }
+
+    #[test]
+    fn extracts_recovered_addresses() {
+        let err = ApiError::classify(
+            "User or API Wallet 0x0ef4b52b87ddcb520009ccb57cfe83b8c36b3955 does not exist.",
+        );
+        assert_eq!(
+            err,
+            ApiError::WalletNotFound {
+                address: Some(address!("0x0ef4b52b87ddcb520009ccb57cfe83b8c36b3955")),
+                message: err.message().to_string(),
+            }
+        );
+        assert!(matches!(
+            ApiError::classify("Vault not registered: 0x1234567890123456789012345678901234567890"),
+            ApiError::VaultNotRegistered {
+                address: Some(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn classifies_retryable_errors() {
+        let nonce = ApiError::classify("Invalid nonce: duplicate nonce 1700000000000");
+        assert!(matches!(nonce, ApiError::InvalidNonce { .. }));
+        assert!(nonce.is_retryable());
+
+        let rate = ApiError::classify(
+            "Too many cumulative requests sent (10240 > 10000) for cumulative volume traded ($0.0).",
+        );
+        assert!(matches!(rate, ApiError::RateLimited { .. }));
+        assert!(rate.is_retryable());
+
+        assert!(!ApiError::classify("Insufficient staked HYPE.").is_retryable());
+    }
+
+    #[test]
+    fn unknown_messages_fall_back_to_other() {
+        assert!(matches!(
+            ApiError::classify("Something new went wrong"),
+            ApiError::Other { .. }
+        ));
+    }
+}
@@ -1,8 +1,10 @@
-use reqwest::{Client, Response};
+use std::time::Instant;
+
+use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::{prelude::Result, Error};
+use crate::{cassette::Recorder, nonce, prelude::Result, Error};
 
 #[derive(Deserialize, Debug)]
 struct ErrorData {
@@ -15,15 +17,10 @@ struct ErrorData {
 pub struct HttpClient {
     pub client: Client,
     pub base_url: String,
+    pub recorder: Option<Recorder>,
 }
 
-async fn parse_response(response: Response) -> Result<String> {
-    let status_code = response.status().as_u16();
-    let text = response
-        .text()
-        .await
-        .map_err(|e| Error::GenericRequest(e.to_string()))?;
-
+fn parse_response(status_code: u16, text: String) -> Result<String> {
     if status_code < 400 {
         return Ok(text);
     }
@@ -63,7 +60,8 @@ impl HttpClient {
         let payload_json =
             serde_json::to_string(&data).map_err(|e| Error::SerializationFailure(e.to_string()))?;
         tracing::trace!(target: "hl_rs::http_client", url=full_url, payload=payload_json, "Sending POST request");
-        if let Ok(payload_value) = serde_json::from_str::<Value>(&payload_json) {
+        let payload_value = serde_json::from_str::<Value>(&payload_json).ok();
+        if let Some(payload_value) = &payload_value {
             let action = payload_value.get("action").cloned().unwrap_or(Value::Null);
             tracing::trace!(
                 target: "hl_rs::http_client",
@@ -75,6 +73,7 @@ impl HttpClient {
             );
         }
 
+        let started = Instant::now();
         let res = self
             .client
             .post(&full_url)
@@ -83,6 +82,22 @@ impl HttpClient {
             .await
             .map_err(|e| Error::GenericRequest(e.to_string()))?;
         tracing::trace!(target: "hl_rs::http_client", res=?res, "Raw Response");
-        parse_response(res).await
+        if let Some(date) = res
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|date| date.to_str().ok())
+        {
+            nonce::observe_server_date(date);
+        }
+        let status_code = res.status().as_u16();
+        let text = res
+            .text()
+            .await
+            .map_err(|e| Error::GenericRequest(e.to_string()))?;
+        if let Some(recorder) = &self.recorder {
+            let request = payload_value.unwrap_or(Value::Null);
+            recorder.record_http(url_path, &request, status_code, &text, started.elapsed());
+        }
+        parse_response(status_code, text)
     }
 }
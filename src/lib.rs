@@ -2,28 +2,41 @@ pub mod types;
 
 mod abi_value;
 pub mod actions;
+pub mod cassette;
 pub mod clients;
 mod consts;
 mod error;
 mod http;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod nonce;
+pub mod order_tracker;
 mod prelude;
+mod signer;
 
 pub use clients::{
-    exchange::responses::ExchangeResponse,
-    info::{self, InfoClient},
+    exchange::responses::{
+        ActionResponse, CancelStatus, CancelStatuses, ExchangeDataStatus, ExchangeDataStatuses,
+        ExchangeResponse, FillSummary, SetGlobalResponse, TwapCancelResponse, TwapOrderResponse,
+        TwapOrderStatus,
+    },
+    exchange::{DeadManMode, DeadManStatus, DeadManSwitch, DeadManSwitchConfig},
+    info::{self, AssetInfo, AssetKind, AssetRegistry, InfoClient},
     ExchangeClient,
 };
 
 pub use abi_value::{AbiResult, ToAbiValue};
 pub use alloy::dyn_abi::DynSolType;
 pub use consts::{MAINNET_API_URL, MAINNET_WS_URL, TESTNET_API_URL, TESTNET_WS_URL};
-pub use error::Error;
+pub use error::{ApiError, Error};
 pub use prelude::Result;
+pub use signer::{ActionSigner, MockSigner};
 pub use types::{BaseUrl, SigningChain};
 
 #[cfg(feature = "ws")]
 pub use clients::ws::{
-    responses, Price, Subscription, WsActiveAssetCtx, WsClient, WsMessage,
+    responses, CandleBuilder, ManagedWsClient, OrderBook, Price, ReconnectConfig, Subscription,
+    SubscriptionStream, WsActiveAssetCtx, WsClient, WsEvent, WsMessage, WsPayload, WsPostResponse,
 };
 
 pub use actions::*;
@@ -0,0 +1,170 @@
+//! In-process mock of the Hyperliquid API, for testing without testnet.
+//!
+//! [`MockServer`] serves `/info` and `/exchange` over HTTP and `/ws` on a local port. Signed
+//! actions are checked the way the exchange checks them: the signer is recovered with the
+//! crate's own hashing and must be a funded user or one of its approved agents, and nonces
+//! may not be reused. Accounts, orders and positions are tracked in memory and answered in
+//! the API's response shapes, including its error messages, so [`ApiError::classify`] works
+//! on them.
+//!
+//! The mock is deliberately simple:
+//! - only perps are listed (`BTC` and `ETH` to start with, see [`MockServer::add_perp`]);
+//! - orders trade against unlimited liquidity at the mark price, never against each other;
+//!   resting orders and triggers fill when [`MockServer::set_mark_price`] crosses them;
+//! - there is no funding, liquidation or spot balance;
+//! - actions other than orders, cancels, `updateLeverage` and `approveAgent` are
+//!   acknowledged without effect, and unsupported info requests get HTTP 422.
+//!
+//! WebSocket clients can `post`, and get `allMids`, `orderUpdates` and `userFills` pushes.
+//!
+//! [`ReplayServer`] serves a recorded [cassette](crate::cassette) instead, to reproduce a
+//! session against the real API.
+//!
+//! # Example
+//! ```no_run
+//! use alloy::signers::local::PrivateKeySigner;
+//! use hl_rs::{mock::MockServer, ExchangeClient};
+//! use rust_decimal_macros::dec;
+//!
+//! # async fn run() -> Result<(), hl_rs::Error> {
+//! let server = MockServer::start().await?;
+//! let wallet = PrivateKeySigner::random();
+//! server.fund(wallet.address(), dec!(10000));
+//!
+//! let client = ExchangeClient::new(server.base_url()).with_signer(wallet);
+//! let fill = client.market_open("ETH", true, dec!(1), dec!(0.01)).await?;
+//! assert_eq!(fill.avg_px, dec!(3000));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`ApiError::classify`]: crate::ApiError::classify
+
+mod replay;
+mod server;
+mod state;
+
+pub use replay::ReplayServer;
+
+use std::sync::{Arc, Mutex};
+
+use alloy::primitives::Address;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use tokio::{net::TcpListener, task::JoinHandle};
+
+use crate::{prelude::Result, BaseUrl, Error, SigningChain};
+use state::{Market, MockState};
+
+type SharedState = Arc<Mutex<MockState>>;
+
+/// Local mock of the Hyperliquid HTTP and WebSocket API. Stops when dropped.
+pub struct MockServer {
+    url: String,
+    state: SharedState,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Start on a free local port, with `BTC` (asset 0, mark 100000) and `ETH` (asset 1,
+    /// mark 3000) listed.
+    ///
+    /// Actions must be signed for [`SigningChain::Testnet`], which [`base_url`](Self::base_url)
+    /// selects.
+    pub async fn start() -> Result<Self> {
+        let (listener, url) = bind().await?;
+        let markets = vec![
+            perp("BTC", 5, 40, dec!(100000)),
+            perp("ETH", 4, 25, dec!(3000)),
+        ];
+        let state = Arc::new(Mutex::new(MockState::new(SigningChain::Testnet, markets)));
+        let task = tokio::spawn(server::serve(listener, state.clone()));
+
+        Ok(Self { url, state, task })
+    }
+
+    /// `http://127.0.0.1:<port>`.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Base URL for pointing [`ExchangeClient`](crate::ExchangeClient),
+    /// [`InfoClient`](crate::InfoClient) and the WebSocket clients at the mock.
+    pub fn base_url(&self) -> BaseUrl {
+        BaseUrl::Custom {
+            url: self.url.clone(),
+            signing_chain: SigningChain::Testnet,
+        }
+    }
+
+    /// `ws://127.0.0.1:<port>/ws`.
+    pub fn ws_url(&self) -> String {
+        self.base_url().ws_url()
+    }
+
+    /// List a perp and return its asset id. Clients that already loaded the asset registry
+    /// need to refresh it to see the new asset.
+    pub fn add_perp(
+        &self,
+        coin: impl Into<String>,
+        sz_decimals: u32,
+        max_leverage: u32,
+        mark_px: Decimal,
+    ) -> u32 {
+        self.state
+            .lock()
+            .unwrap()
+            .add_market(perp(coin, sz_decimals, max_leverage, mark_px))
+    }
+
+    /// Credit `usdc` to `user`'s perp account, creating the account if needed.
+    ///
+    /// Actions signed by addresses without an account (or an approval as agent) are rejected
+    /// with "User or API Wallet ... does not exist.".
+    pub fn fund(&self, user: Address, usdc: Decimal) {
+        self.state.lock().unwrap().fund(user, usdc);
+    }
+
+    /// Move the mark (and mid) price of `coin`.
+    ///
+    /// Fires the trigger orders the new price reaches and fills the resting orders it
+    /// crosses at their limit price, then pushes `allMids`.
+    pub fn set_mark_price(&self, coin: &str, mark_px: Decimal) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let asset = state
+            .asset(coin)
+            .ok_or_else(|| Error::UnknownCoin(coin.to_string()))?;
+        state.set_mark_price(asset, mark_px, now_ms());
+        Ok(())
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn perp(coin: impl Into<String>, sz_decimals: u32, max_leverage: u32, mark_px: Decimal) -> Market {
+    Market {
+        name: coin.into(),
+        sz_decimals,
+        max_leverage,
+        mark_px,
+    }
+}
+
+/// Listener on a free local port, and its `http://` URL.
+async fn bind() -> Result<(TcpListener, String)> {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|e| Error::GenericRequest(e.to_string()))?;
+    let address = listener
+        .local_addr()
+        .map_err(|e| Error::GenericRequest(e.to_string()))?;
+    Ok((listener, format!("http://{address}")))
+}
+
+fn now_ms() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
+}
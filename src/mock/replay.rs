@@ -0,0 +1,250 @@
+//! Playback of recorded [cassettes](crate::cassette).
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    future::Future,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use futures_util::{
+    stream::{SplitSink, SplitStream},
+    SinkExt, StreamExt,
+};
+use serde_json::{json, Value};
+use tokio::{net::TcpStream, task::JoinHandle};
+use tokio_tungstenite::{
+    tungstenite::{
+        protocol::{frame::coding::CloseCode, CloseFrame},
+        Message,
+    },
+    WebSocketStream,
+};
+
+use super::{bind, server};
+use crate::{
+    cassette::{mask, Cassette, CassetteEntry, WsDirection},
+    prelude::Result,
+    BaseUrl, SigningChain,
+};
+
+/// Local server that answers with the traffic of a [`Cassette`], for reproducing a recorded
+/// session with [`InfoClient`](crate::InfoClient), [`ExchangeClient`](crate::ExchangeClient)
+/// and [`WsClient`](crate::WsClient). Stops when dropped.
+///
+/// A POST gets the response of the first recording not played yet with the same path and
+/// the same request after [masking](crate::cassette::mask); requests that were not recorded
+/// get HTTP 404. WebSocket connections replay the recorded connections in order: received
+/// frames are sent as recorded, and each recorded client frame is waited for before going on.
+/// A client frame that doesn't match closes the connection. Keepalive pings are answered
+/// whenever they come, and recorded ones are skipped.
+///
+/// Replay doesn't check signatures and ignores the recorded timing.
+///
+/// # Example
+/// ```no_run
+/// use hl_rs::{mock::ReplayServer, InfoClient, SigningChain};
+///
+/// # async fn run() -> Result<(), hl_rs::Error> {
+/// let server = ReplayServer::load("incident.jsonl").await?;
+/// let info = InfoClient::builder(server.base_url(SigningChain::Mainnet)).build()?;
+/// let _meta = info.meta().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ReplayServer {
+    url: String,
+    replay: Arc<Replay>,
+    task: JoinHandle<()>,
+}
+
+impl ReplayServer {
+    /// Start on a free local port, serving `cassette`.
+    pub async fn start(cassette: Cassette) -> Result<Self> {
+        let (listener, url) = bind().await?;
+        let replay = Arc::new(Replay::new(cassette));
+        let task = tokio::spawn(server::serve(listener, replay.clone()));
+        Ok(Self { url, replay, task })
+    }
+
+    /// Start serving the cassette at `path`.
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::start(Cassette::load(path)?).await
+    }
+
+    /// `http://127.0.0.1:<port>`.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Base URL for pointing the clients at the replay.
+    ///
+    /// `signing_chain` must be the one the session was recorded with: user-signed actions
+    /// name their chain in the request body.
+    pub fn base_url(&self, signing_chain: SigningChain) -> BaseUrl {
+        BaseUrl::Custom {
+            url: self.url.clone(),
+            signing_chain,
+        }
+    }
+
+    /// `ws://127.0.0.1:<port>/ws`.
+    pub fn ws_url(&self) -> String {
+        self.base_url(SigningChain::Testnet).ws_url()
+    }
+
+    /// Recorded HTTP requests and WebSocket connections that have not been played yet.
+    pub fn unplayed(&self) -> usize {
+        let http = self.replay.http.lock().unwrap();
+        http.iter().flatten().count() + self.replay.connections.lock().unwrap().len()
+    }
+}
+
+impl Drop for ReplayServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+struct Recorded {
+    path: String,
+    request: Value,
+    status: u16,
+    response: String,
+}
+
+struct Replay {
+    /// `None` once played.
+    http: Mutex<Vec<Option<Recorded>>>,
+    connections: Mutex<VecDeque<Vec<(WsDirection, String)>>>,
+}
+
+impl Replay {
+    fn new(cassette: Cassette) -> Self {
+        let mut http = Vec::new();
+        let mut connections = BTreeMap::<u64, Vec<_>>::new();
+        for entry in cassette.entries {
+            match entry {
+                CassetteEntry::Http {
+                    path,
+                    request,
+                    status,
+                    response,
+                    ..
+                } => http.push(Some(Recorded {
+                    path,
+                    request,
+                    status,
+                    response,
+                })),
+                CassetteEntry::Ws {
+                    connection,
+                    direction,
+                    frame,
+                    ..
+                } => {
+                    let frames = connections.entry(connection).or_default();
+                    if !is_keepalive(&frame) {
+                        frames.push((direction, frame));
+                    }
+                }
+            }
+        }
+        Self {
+            http: Mutex::new(http),
+            connections: Mutex::new(connections.into_values().collect()),
+        }
+    }
+}
+
+impl server::Backend for Replay {
+    fn post(&self, path: &str, body: &[u8]) -> (u16, String) {
+        let mut request = serde_json::from_slice(body).unwrap_or(Value::Null);
+        mask(&mut request);
+        let mut http = self.http.lock().unwrap();
+        let recorded = http.iter_mut().find(|recorded| {
+            recorded
+                .as_ref()
+                .is_some_and(|recorded| recorded.path == path && recorded.request == request)
+        });
+        match recorded.and_then(Option::take) {
+            Some(recorded) => (recorded.status, recorded.response),
+            None => (404, format!("No recorded response for {path} {request}")),
+        }
+    }
+
+    fn serve_ws(
+        self: Arc<Self>,
+        ws: WebSocketStream<TcpStream>,
+    ) -> impl Future<Output = ()> + Send {
+        let frames = self.connections.lock().unwrap().pop_front();
+        play(ws, frames.unwrap_or_default())
+    }
+}
+
+type WsWrite = SplitSink<WebSocketStream<TcpStream>, Message>;
+type WsRead = SplitStream<WebSocketStream<TcpStream>>;
+
+async fn play(ws: WebSocketStream<TcpStream>, frames: Vec<(WsDirection, String)>) {
+    let (mut write, mut read) = ws.split();
+    for (direction, frame) in frames {
+        match direction {
+            WsDirection::Recv => {
+                if write.send(Message::Text(frame)).await.is_err() {
+                    return;
+                }
+            }
+            WsDirection::Send => {
+                let Some(sent) = next_request(&mut read, &mut write).await else {
+                    return;
+                };
+                if !same_frame(&frame, &sent) {
+                    tracing::warn!(target: "hl_rs::mock", expected = frame, sent, "frame does not match the cassette");
+                    let close = CloseFrame {
+                        code: CloseCode::Policy,
+                        reason: "frame does not match the cassette".into(),
+                    };
+                    let _ = write.send(Message::Close(Some(close))).await;
+                    return;
+                }
+            }
+        }
+    }
+    // Stay connected, like the server would, until the client leaves.
+    while next_request(&mut read, &mut write).await.is_some() {}
+}
+
+/// Next text frame from the client, answering keepalive pings on the way.
+async fn next_request(read: &mut WsRead, write: &mut WsWrite) -> Option<String> {
+    while let Some(Ok(message)) = read.next().await {
+        match message {
+            Message::Text(text) if is_keepalive(&text) => {
+                let pong = json!({"channel": "pong"}).to_string();
+                write.send(Message::Text(pong)).await.ok()?;
+            }
+            Message::Text(text) => return Some(text),
+            Message::Close(_) => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn is_keepalive(frame: &str) -> bool {
+    serde_json::from_str::<Value>(frame)
+        .is_ok_and(|frame| frame["method"] == "ping" || frame["channel"] == "pong")
+}
+
+/// Whether the client sent the recorded (masked) frame.
+fn same_frame(recorded: &str, sent: &str) -> bool {
+    match (
+        serde_json::from_str::<Value>(recorded),
+        serde_json::from_str::<Value>(sent),
+    ) {
+        (Ok(recorded), Ok(mut sent)) => {
+            mask(&mut sent);
+            recorded == sent
+        }
+        _ => recorded == sent,
+    }
+}
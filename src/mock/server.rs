@@ -0,0 +1,304 @@
+//! HTTP and WebSocket transport of [`MockServer`](super::MockServer) and
+//! [`ReplayServer`](super::ReplayServer).
+//!
+//! Just enough HTTP/1.1 for `reqwest`: POST bodies with `content-length`, keep-alive, and the
+//! WebSocket upgrade on `GET /ws`.
+
+use std::{future::Future, io, sync::Arc};
+
+use alloy::primitives::Address;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::broadcast::error::RecvError,
+};
+use tokio_tungstenite::{
+    tungstenite::{handshake::derive_accept_key, protocol::Role, Message},
+    WebSocketStream,
+};
+
+use super::{
+    now_ms,
+    state::{MockState, Push},
+    SharedState,
+};
+use crate::Subscription;
+
+/// What a server answers, behind the shared transport.
+pub(super) trait Backend: Send + Sync + 'static {
+    /// Status and body for a POST to `path`.
+    fn post(&self, path: &str, body: &[u8]) -> (u16, String);
+
+    /// Talk on an upgraded `/ws` connection until either side closes it.
+    fn serve_ws(self: Arc<Self>, ws: WebSocketStream<TcpStream>)
+        -> impl Future<Output = ()> + Send;
+}
+
+impl Backend for std::sync::Mutex<MockState> {
+    fn post(&self, path: &str, body: &[u8]) -> (u16, String) {
+        if path != "/info" && path != "/exchange" {
+            return (404, "Not Found".to_string());
+        }
+        let reply = match serde_json::from_slice::<Value>(body) {
+            Ok(body) => {
+                let mut state = self.lock().unwrap();
+                if path == "/info" {
+                    state.info(&body, now_ms())
+                } else {
+                    state.exchange(&body, now_ms())
+                }
+            }
+            Err(e) => Err(format!("Failed to parse the request body as JSON: {e}")),
+        };
+        match reply {
+            Ok(body) => (200, body.to_string()),
+            Err(message) => (422, message),
+        }
+    }
+
+    fn serve_ws(
+        self: Arc<Self>,
+        ws: WebSocketStream<TcpStream>,
+    ) -> impl Future<Output = ()> + Send {
+        serve_ws(ws, self)
+    }
+}
+
+pub(super) async fn serve<B: Backend>(listener: TcpListener, backend: Arc<B>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        let backend = backend.clone();
+        tokio::spawn(async move {
+            if let Err(err) = serve_connection(stream, backend).await {
+                tracing::debug!(target: "hl_rs::mock", %err, "mock connection closed");
+            }
+        });
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+async fn serve_connection<B: Backend>(stream: TcpStream, backend: Arc<B>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    while let Some(request) = read_request(&mut reader).await? {
+        if request.method == "GET" && request.path == "/ws" {
+            let Some(key) = request.header("sec-websocket-key") else {
+                return write_response(reader.get_mut(), 400, "Bad Request").await;
+            };
+            let accept = derive_accept_key(key.as_bytes());
+            let mut stream = reader.into_inner();
+            stream
+                .write_all(
+                    format!(
+                        "HTTP/1.1 101 Switching Protocols\r\nConnection: Upgrade\r\n\
+                         Upgrade: websocket\r\nSec-WebSocket-Accept: {accept}\r\n\r\n"
+                    )
+                    .as_bytes(),
+                )
+                .await?;
+            let ws = WebSocketStream::from_raw_socket(stream, Role::Server, None).await;
+            backend.serve_ws(ws).await;
+            return Ok(());
+        }
+
+        let (status, body) = match request.method.as_str() {
+            "POST" => backend.post(&request.path, &request.body),
+            _ => (404, "Not Found".to_string()),
+        };
+        write_response(reader.get_mut(), status, &body).await?;
+    }
+    Ok(())
+}
+
+/// Next request on the connection, `None` once the client closed it.
+async fn read_request(reader: &mut BufReader<TcpStream>) -> io::Result<Option<Request>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = header.split_once(':') {
+            headers.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let mut request = Request {
+        method,
+        path,
+        headers,
+        body: Vec::new(),
+    };
+    let length = request
+        .header("content-length")
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(0);
+    request.body.resize(length, 0);
+    reader.read_exact(&mut request.body).await?;
+    Ok(Some(request))
+}
+
+async fn write_response(stream: &mut TcpStream, status: u16, body: &str) -> io::Result<()> {
+    let reason = reqwest::StatusCode::from_u16(status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("Unknown");
+    let content_type = if status == 200 {
+        "application/json"
+    } else {
+        "text/plain"
+    };
+    let head = format!(
+        "HTTP/1.1 {status} {reason}\r\ncontent-type: {content_type}\r\ncontent-length: {}\r\n\r\n",
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await
+}
+
+async fn serve_ws(ws: WebSocketStream<TcpStream>, state: SharedState) {
+    let (mut write, mut read) = ws.split();
+    let mut pushes = state.lock().unwrap().subscribe();
+    let mut subscriptions = Vec::new();
+
+    let greeting = Message::Text("Websocket connection established.".to_string());
+    if write.send(greeting).await.is_err() {
+        return;
+    }
+
+    loop {
+        let frames = tokio::select! {
+            frame = read.next() => match frame {
+                Some(Ok(Message::Text(text))) => ws_request(&text, &mut subscriptions, &state),
+                Some(Ok(Message::Close(_)) | Err(_)) | None => return,
+                Some(Ok(_)) => continue,
+            },
+            push = pushes.recv() => match push {
+                Ok(push) if wants(&subscriptions, &push) => vec![push.frame],
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return,
+            },
+        };
+        for frame in frames {
+            if write.send(Message::Text(frame)).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Replies to one client request: `ping`, `subscribe`, `unsubscribe` or `post`.
+fn ws_request(
+    text: &str,
+    subscriptions: &mut Vec<Subscription>,
+    state: &SharedState,
+) -> Vec<String> {
+    let error = || {
+        vec![json!({
+            "channel": "error",
+            "data": format!("Error parsing JSON into valid websocket request: {text}"),
+        })
+        .to_string()]
+    };
+    let Ok(request) = serde_json::from_str::<Value>(text) else {
+        return error();
+    };
+
+    match request["method"].as_str() {
+        Some("ping") => vec![json!({"channel": "pong"}).to_string()],
+        Some(method @ ("subscribe" | "unsubscribe")) => {
+            let Ok(subscription) =
+                serde_json::from_value::<Subscription>(request["subscription"].clone())
+            else {
+                return error();
+            };
+            let ack = json!({
+                "channel": "subscriptionResponse",
+                "data": {"method": method, "subscription": request["subscription"]},
+            });
+            let mut frames = vec![ack.to_string()];
+            if method == "unsubscribe" {
+                subscriptions.retain(|sub| *sub != subscription);
+                return frames;
+            }
+
+            let user = subscribed_user(&subscription).and_then(|user| user.parse().ok());
+            let snapshot = state
+                .lock()
+                .unwrap()
+                .snapshot(&subscription.channel(), user);
+            frames.extend(snapshot.map(|snapshot| snapshot.to_string()));
+            if !subscriptions.contains(&subscription) {
+                subscriptions.push(subscription);
+            }
+            frames
+        }
+        Some("post") => {
+            let payload = &request["request"]["payload"];
+            let response = match request["request"]["type"].as_str() {
+                Some("info") => state
+                    .lock()
+                    .unwrap()
+                    .info(payload, now_ms())
+                    .map(|data| json!({"type": "info", "payload": {"type": payload["type"], "data": data}})),
+                Some("action") => state
+                    .lock()
+                    .unwrap()
+                    .exchange(payload, now_ms())
+                    .map(|body| json!({"type": "action", "payload": body})),
+                _ => return error(),
+            };
+            let response =
+                response.unwrap_or_else(|message| json!({"type": "error", "payload": message}));
+            vec![json!({
+                "channel": "post",
+                "data": {"id": request["id"], "response": response},
+            })
+            .to_string()]
+        }
+        _ => error(),
+    }
+}
+
+fn subscribed_user(subscription: &Subscription) -> Option<String> {
+    let value = serde_json::to_value(subscription).ok()?;
+    value["user"].as_str().map(str::to_string)
+}
+
+fn wants(subscriptions: &[Subscription], push: &Push) -> bool {
+    subscriptions.iter().any(|sub| {
+        sub.matches(&push.message)
+            && push.user.is_none_or(|user: Address| {
+                subscribed_user(sub).is_some_and(|subscribed| {
+                    subscribed.eq_ignore_ascii_case(&format!("{user:#x}"))
+                })
+            })
+    })
+}
@@ -0,0 +1,943 @@
+//! Accounts, orders and positions behind [`MockServer`](super::MockServer), and the
+//! `/info` and `/exchange` handlers that read and change them.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use alloy::primitives::Address;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+
+use crate::{
+    actions::{
+        ActionKind, Cloid, OrderPrecision, OrderType, OrderWire, RoundingMode, SignedActionKind,
+        Tif, TpSl, UpdateLeverage,
+    },
+    info::types::{InfoRequest, OrderId},
+    nonce::check_nonce_window,
+    SigningChain, WsMessage,
+};
+
+/// Smallest order value the exchange accepts, in USDC.
+const MIN_ORDER_VALUE: Decimal = dec!(10);
+const TAKER_FEE: Decimal = dec!(0.00045);
+const MAKER_FEE: Decimal = dec!(0.00015);
+/// Leverage of an asset until `updateLeverage` is sent for it (capped at the asset's maximum).
+const DEFAULT_LEVERAGE: u32 = 20;
+/// Pushes buffered per WebSocket connection before a slow reader starts missing them.
+const PUSH_BUFFER: usize = 1024;
+
+/// Perp market listed on the mock exchange. Its position in the list is its asset id.
+#[derive(Debug, Clone)]
+pub(crate) struct Market {
+    pub name: String,
+    pub sz_decimals: u32,
+    pub max_leverage: u32,
+    pub mark_px: Decimal,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Leverage {
+    is_cross: bool,
+    value: u32,
+}
+
+#[derive(Debug, Default)]
+struct Account {
+    /// Deposits plus realized PnL, minus fees.
+    usdc: Decimal,
+    positions: BTreeMap<u32, Position>,
+    leverage: HashMap<u32, Leverage>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Position {
+    szi: Decimal,
+    entry_px: Decimal,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Trigger {
+    px: Decimal,
+    is_market: bool,
+    tpsl: TpSl,
+}
+
+impl Trigger {
+    fn fires(&self, is_buy: bool, mark_px: Decimal) -> bool {
+        match (self.tpsl, is_buy) {
+            (TpSl::Tp, true) | (TpSl::Sl, false) => mark_px <= self.px,
+            (TpSl::Tp, false) | (TpSl::Sl, true) => mark_px >= self.px,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Order {
+    user: Address,
+    asset: u32,
+    is_buy: bool,
+    limit_px: Decimal,
+    /// Remaining size.
+    sz: Decimal,
+    orig_sz: Decimal,
+    reduce_only: bool,
+    tif: Option<Tif>,
+    trigger: Option<Trigger>,
+    cloid: Option<Cloid>,
+    timestamp: u64,
+    status: &'static str,
+    status_timestamp: u64,
+}
+
+impl Order {
+    fn is_open(&self) -> bool {
+        self.status == "open"
+    }
+}
+
+/// Message for WebSocket subscribers. `user` restricts it to that user's subscriptions, for
+/// channels whose payload does not name the user (e.g. `orderUpdates`).
+#[derive(Debug, Clone)]
+pub(crate) struct Push {
+    pub user: Option<Address>,
+    pub message: WsMessage,
+    pub frame: String,
+}
+
+pub(crate) struct MockState {
+    signing_chain: SigningChain,
+    markets: Vec<Market>,
+    accounts: HashMap<Address, Account>,
+    /// Approved agent -> master.
+    agents: HashMap<Address, Address>,
+    /// Nonces used so far, per signer.
+    nonces: HashMap<Address, HashSet<u64>>,
+    orders: BTreeMap<u64, Order>,
+    fills: Vec<(Address, Value)>,
+    next_oid: u64,
+    next_tid: u64,
+    pushes: broadcast::Sender<Push>,
+}
+
+impl MockState {
+    pub fn new(signing_chain: SigningChain, markets: Vec<Market>) -> Self {
+        Self {
+            signing_chain,
+            markets,
+            accounts: HashMap::new(),
+            agents: HashMap::new(),
+            nonces: HashMap::new(),
+            orders: BTreeMap::new(),
+            fills: Vec::new(),
+            next_oid: 1,
+            next_tid: 1,
+            pushes: broadcast::channel(PUSH_BUFFER).0,
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Push> {
+        self.pushes.subscribe()
+    }
+
+    pub fn add_market(&mut self, market: Market) -> u32 {
+        self.markets.push(market);
+        self.markets.len() as u32 - 1
+    }
+
+    pub fn fund(&mut self, user: Address, usdc: Decimal) {
+        self.accounts.entry(user).or_default().usdc += usdc;
+    }
+
+    pub fn asset(&self, coin: &str) -> Option<u32> {
+        self.markets
+            .iter()
+            .position(|market| market.name == coin)
+            .map(|asset| asset as u32)
+    }
+
+    /// Move the mark price of `asset`, then fire the trigger orders and fill the resting
+    /// orders it crosses.
+    pub fn set_mark_price(&mut self, asset: u32, mark_px: Decimal, now: u64) {
+        self.markets[asset as usize].mark_px = mark_px;
+
+        let due: Vec<u64> = self
+            .orders
+            .iter()
+            .filter(|(_, order)| order.asset == asset && order.is_open())
+            .filter(|(_, order)| match order.trigger {
+                Some(trigger) => trigger.fires(order.is_buy, mark_px),
+                None => crosses(order.is_buy, order.limit_px, mark_px),
+            })
+            .map(|(oid, _)| *oid)
+            .collect();
+
+        for oid in due {
+            let order = self.orders.get_mut(&oid).unwrap();
+            match order.trigger.take() {
+                // Triggered orders take liquidity at the mark, or rest at their limit.
+                Some(trigger)
+                    if trigger.is_market || crosses(order.is_buy, order.limit_px, mark_px) =>
+                {
+                    self.fill_order(oid, mark_px, true, now)
+                }
+                Some(_) => {
+                    order.status_timestamp = now;
+                    self.push_order_update(oid);
+                }
+                None => {
+                    let px = order.limit_px;
+                    self.fill_order(oid, px, false, now);
+                }
+            }
+        }
+
+        self.push(None, "allMids", json!({"mids": self.mids()}));
+    }
+
+    // ------------------------------------------------------------------------
+    // /info
+    // ------------------------------------------------------------------------
+
+    /// Body of an `/info` response, or why the request is rejected (HTTP 422).
+    pub fn info(&self, body: &Value, now: u64) -> Result<Value, String> {
+        let request: InfoRequest = serde_json::from_value(body.clone()).map_err(|e| {
+            format!("Failed to deserialize the JSON body into the target type: {e}")
+        })?;
+
+        Ok(match request {
+            InfoRequest::Meta { dex: None } => self.meta(),
+            InfoRequest::Meta { dex: Some(_) } => json!({"universe": []}),
+            InfoRequest::MetaAndAssetCtxs => json!([self.meta(), self.asset_ctxs()]),
+            InfoRequest::SpotMeta => spot_meta(),
+            InfoRequest::SpotMetaAndAssetCtxs => json!([spot_meta(), []]),
+            InfoRequest::PerpDexs => json!([null]),
+            InfoRequest::AllMids => json!(self.mids()),
+            InfoRequest::L2Book { coin } => self.l2_book(&coin, now),
+            InfoRequest::UserState { user, dex: None } => self.clearinghouse_state(user, now),
+            // There are no HIP-3 dexes, so nothing is held on one.
+            InfoRequest::UserState { dex: Some(_), .. } => {
+                self.clearinghouse_state(Address::ZERO, now)
+            }
+            InfoRequest::OpenOrders { user } => Value::Array(
+                self.orders
+                    .iter()
+                    .filter(|(_, order)| order.user == user && order.is_open())
+                    .map(|(oid, order)| self.order_json(*oid, order))
+                    .collect(),
+            ),
+            InfoRequest::OrderStatus { user, oid } => {
+                let found = self.orders.iter().find(|(id, order)| {
+                    order.user == user
+                        && match oid {
+                            OrderId::Oid(oid) => **id == oid,
+                            OrderId::Cloid(cloid) => order.cloid == Some(cloid),
+                        }
+                });
+                match found {
+                    Some((&oid, order)) => json!({
+                        "status": "order",
+                        "order": {
+                            "order": self.order_status_json(oid, order),
+                            "status": order.status,
+                            "statusTimestamp": order.status_timestamp,
+                        },
+                    }),
+                    None => json!({"status": "unknownOid"}),
+                }
+            }
+            InfoRequest::UserFills { user } => Value::Array(
+                self.fills
+                    .iter()
+                    .rev()
+                    .filter(|(fill_user, _)| *fill_user == user)
+                    .map(|(_, fill)| fill.clone())
+                    .collect(),
+            ),
+            InfoRequest::UserRole { user } => match self.agents.get(&user) {
+                Some(master) => json!({"role": "agent", "data": {"user": master}}),
+                None if self.accounts.contains_key(&user) => json!({"role": "user"}),
+                None => json!({"role": "missing"}),
+            },
+            other => {
+                let request = serde_json::to_value(&other).unwrap_or_default();
+                return Err(format!(
+                    "mock server does not serve `{}` requests",
+                    request["type"].as_str().unwrap_or_default()
+                ));
+            }
+        })
+    }
+
+    fn meta(&self) -> Value {
+        let universe: Vec<Value> = self
+            .markets
+            .iter()
+            .map(|market| {
+                json!({
+                    "name": market.name,
+                    "szDecimals": market.sz_decimals,
+                    "maxLeverage": market.max_leverage,
+                })
+            })
+            .collect();
+        json!({"universe": universe})
+    }
+
+    fn asset_ctxs(&self) -> Vec<Value> {
+        (0..self.markets.len() as u32)
+            .map(|asset| {
+                let mark = wire(self.markets[asset as usize].mark_px);
+                let open_interest: Decimal = self
+                    .accounts
+                    .values()
+                    .filter_map(|account| account.positions.get(&asset))
+                    .map(|position| position.szi.max(Decimal::ZERO))
+                    .sum();
+                json!({
+                    "dayNtlVlm": "0.0",
+                    "funding": "0.0",
+                    "impactPxs": [mark, mark],
+                    "markPx": mark,
+                    "midPx": mark,
+                    "openInterest": wire(open_interest),
+                    "oraclePx": mark,
+                    "premium": "0.0",
+                    "prevDayPx": mark,
+                })
+            })
+            .collect()
+    }
+
+    fn mids(&self) -> BTreeMap<String, String> {
+        self.markets
+            .iter()
+            .map(|market| (market.name.clone(), wire(market.mark_px)))
+            .collect()
+    }
+
+    /// Resting limit orders, aggregated by price.
+    fn l2_book(&self, coin: &str, now: u64) -> Value {
+        let asset = self.asset(coin);
+        let book_side = |is_buy: bool| {
+            let mut levels: BTreeMap<Decimal, (Decimal, u64)> = BTreeMap::new();
+            for order in self.orders.values() {
+                if Some(order.asset) == asset
+                    && order.is_open()
+                    && order.trigger.is_none()
+                    && order.is_buy == is_buy
+                {
+                    let level = levels.entry(order.limit_px).or_default();
+                    level.0 += order.sz;
+                    level.1 += 1;
+                }
+            }
+            let levels = levels
+                .into_iter()
+                .map(|(px, (sz, n))| json!({"px": wire(px), "sz": wire(sz), "n": n}));
+            if is_buy {
+                levels.rev().collect::<Vec<_>>()
+            } else {
+                levels.collect()
+            }
+        };
+        json!({"coin": coin, "time": now, "levels": [book_side(true), book_side(false)]})
+    }
+
+    fn clearinghouse_state(&self, user: Address, now: u64) -> Value {
+        let empty = Account::default();
+        let account = self.accounts.get(&user).unwrap_or(&empty);
+        let summary = self.margin_summary(user);
+
+        let positions: Vec<Value> = account
+            .positions
+            .iter()
+            .map(|(asset, position)| {
+                let market = &self.markets[*asset as usize];
+                let leverage = self.leverage(user, *asset);
+                let value = position.szi.abs() * market.mark_px;
+                let margin = value / Decimal::from(leverage.value);
+                let pnl = position.szi * (market.mark_px - position.entry_px);
+                let mut leverage_json = json!({
+                    "type": if leverage.is_cross { "cross" } else { "isolated" },
+                    "value": leverage.value,
+                });
+                if !leverage.is_cross {
+                    leverage_json["rawUsd"] = json!(usd(-position.szi * position.entry_px));
+                }
+                json!({
+                    "type": "oneWay",
+                    "position": {
+                        "coin": market.name,
+                        "entryPx": wire(position.entry_px),
+                        "leverage": leverage_json,
+                        "liquidationPx": null,
+                        "marginUsed": usd(margin),
+                        "positionValue": usd(value),
+                        "returnOnEquity": wire((pnl / margin).round_dp(8)),
+                        "szi": wire(position.szi),
+                        "unrealizedPnl": usd(pnl),
+                        "maxLeverage": market.max_leverage,
+                        "cumFunding": {"allTime": "0.0", "sinceOpen": "0.0", "sinceChange": "0.0"},
+                    },
+                })
+            })
+            .collect();
+
+        let summary_json = json!({
+            "accountValue": usd(summary.account_value),
+            "totalNtlPos": usd(summary.total_ntl_pos),
+            "totalRawUsd": usd(summary.account_value - summary.net_ntl_pos),
+            "totalMarginUsed": usd(summary.margin_used),
+        });
+        json!({
+            "assetPositions": positions,
+            "marginSummary": summary_json,
+            "crossMarginSummary": summary_json,
+            "crossMaintenanceMarginUsed": usd(summary.maintenance_margin),
+            "withdrawable": usd(summary.available().max(Decimal::ZERO)),
+            "time": now,
+        })
+    }
+
+    fn margin_summary(&self, user: Address) -> MarginSummary {
+        let mut summary = MarginSummary::default();
+        let Some(account) = self.accounts.get(&user) else {
+            return summary;
+        };
+        summary.account_value = account.usdc;
+        for (asset, position) in &account.positions {
+            let market = &self.markets[*asset as usize];
+            let value = position.szi.abs() * market.mark_px;
+            summary.account_value += position.szi * (market.mark_px - position.entry_px);
+            summary.total_ntl_pos += value;
+            summary.net_ntl_pos += position.szi * market.mark_px;
+            summary.margin_used += value / Decimal::from(self.leverage(user, *asset).value);
+            // Maintenance margin is half the initial margin at maximum leverage.
+            summary.maintenance_margin += value / Decimal::from(2 * market.max_leverage);
+        }
+        summary.order_margin = self
+            .orders
+            .values()
+            .filter(|order| order.user == user && order.is_open() && !order.reduce_only)
+            .map(|order| {
+                order.sz * order.limit_px / Decimal::from(self.leverage(user, order.asset).value)
+            })
+            .sum();
+        summary
+    }
+
+    fn leverage(&self, user: Address, asset: u32) -> Leverage {
+        self.accounts
+            .get(&user)
+            .and_then(|account| account.leverage.get(&asset))
+            .copied()
+            .unwrap_or(Leverage {
+                is_cross: true,
+                value: DEFAULT_LEVERAGE.min(self.markets[asset as usize].max_leverage),
+            })
+    }
+
+    fn order_json(&self, oid: u64, order: &Order) -> Value {
+        json!({
+            "coin": self.markets[order.asset as usize].name,
+            "side": side(order.is_buy),
+            "limitPx": wire(order.limit_px),
+            "sz": wire(order.sz),
+            "oid": oid,
+            "timestamp": order.timestamp,
+            "origSz": wire(order.orig_sz),
+            "cloid": order.cloid,
+        })
+    }
+
+    fn order_status_json(&self, oid: u64, order: &Order) -> Value {
+        let mut json = self.order_json(oid, order);
+        let (order_type, trigger_condition, trigger_px) = match order.trigger {
+            Some(trigger) => {
+                let kind = match (trigger.tpsl, trigger.is_market) {
+                    (TpSl::Tp, true) => "Take Profit Market",
+                    (TpSl::Tp, false) => "Take Profit Limit",
+                    (TpSl::Sl, true) => "Stop Market",
+                    (TpSl::Sl, false) => "Stop Limit",
+                };
+                let above = trigger.fires(order.is_buy, trigger.px + Decimal::ONE);
+                let condition = format!(
+                    "Price {} {}",
+                    if above { "above" } else { "below" },
+                    wire(trigger.px)
+                );
+                (kind, condition, wire(trigger.px))
+            }
+            None => ("Limit", "N/A".to_string(), "0.0".to_string()),
+        };
+        json["orderType"] = json!(order_type);
+        json["triggerCondition"] = json!(trigger_condition);
+        json["isTrigger"] = json!(order.trigger.is_some());
+        json["triggerPx"] = json!(trigger_px);
+        json["isPositionTpsl"] = json!(false);
+        json["reduceOnly"] = json!(order.reduce_only);
+        json["tif"] = json!(order.tif.map(|tif| format!("{tif:?}")));
+        json
+    }
+
+    // ------------------------------------------------------------------------
+    // /exchange
+    // ------------------------------------------------------------------------
+
+    /// Body of an `/exchange` response, or why the request is rejected (HTTP 422).
+    pub fn exchange(&mut self, body: &Value, now: u64) -> Result<Value, String> {
+        let signed = SignedActionKind::from_json(&body.to_string()).map_err(|e| {
+            format!("Failed to deserialize the JSON body into the target type: {e}")
+        })?;
+        let signer = signed.recover_signer(&self.signing_chain).map_err(|e| {
+            format!("Failed to deserialize the JSON body into the target type: {e}")
+        })?;
+
+        Ok(match self.execute(signed, signer, now) {
+            Ok(response) => json!({"status": "ok", "response": response}),
+            Err(message) => json!({"status": "err", "response": message}),
+        })
+    }
+
+    fn execute(
+        &mut self,
+        signed: SignedActionKind,
+        signer: Address,
+        now: u64,
+    ) -> Result<Value, String> {
+        let user = match self.agents.get(&signer) {
+            Some(master) => *master,
+            None if self.accounts.contains_key(&signer) => signer,
+            None => return Err(format!("User or API Wallet {signer:#x} does not exist.")),
+        };
+        self.use_nonce(signer, signed.nonce, now)?;
+        let account = match signed.vault_address {
+            Some(vault) if !self.accounts.contains_key(&vault) => {
+                return Err(format!("Vault not registered: {vault:#x}"))
+            }
+            Some(vault) => vault,
+            None => user,
+        };
+
+        match signed.action {
+            ActionKind::BatchOrder(action) => {
+                let statuses: Vec<Value> = action
+                    .orders
+                    .into_iter()
+                    .map(|order| self.place_order(account, order, now))
+                    .collect();
+                Ok(json!({"type": "order", "data": {"statuses": statuses}}))
+            }
+            ActionKind::BatchCancel(action) => {
+                let statuses: Vec<Value> = action
+                    .cancels
+                    .iter()
+                    .map(|cancel| self.cancel(account, cancel.a, now, |oid, _| oid == cancel.o))
+                    .collect();
+                Ok(json!({"type": "cancel", "data": {"statuses": statuses}}))
+            }
+            ActionKind::CancelByCloid(action) => {
+                let statuses: Vec<Value> = action
+                    .cancels
+                    .iter()
+                    .map(|cancel| {
+                        self.cancel(account, cancel.asset, now, |_, order| {
+                            order.cloid == Some(cancel.cloid)
+                        })
+                    })
+                    .collect();
+                Ok(json!({"type": "cancel", "data": {"statuses": statuses}}))
+            }
+            ActionKind::UpdateLeverage(action) => {
+                self.update_leverage(account, &action)?;
+                Ok(json!({"type": "default"}))
+            }
+            ActionKind::ApproveAgent(action) => {
+                self.agents.insert(action.agent_address, user);
+                Ok(json!({"type": "default"}))
+            }
+            // Everything else is acknowledged without changing any state.
+            _ => Ok(json!({"type": "default"})),
+        }
+    }
+
+    fn use_nonce(&mut self, signer: Address, nonce: u64, now: u64) -> Result<(), String> {
+        if check_nonce_window(nonce, now).is_err() {
+            return Err(format!(
+                "Invalid nonce: nonce {nonce} is too far from the current time {now}"
+            ));
+        }
+        if !self.nonces.entry(signer).or_default().insert(nonce) {
+            return Err(format!("Invalid nonce: duplicate nonce {nonce}"));
+        }
+        Ok(())
+    }
+
+    fn update_leverage(&mut self, user: Address, action: &UpdateLeverage) -> Result<(), String> {
+        let max = self
+            .markets
+            .get(action.asset as usize)
+            .map(|market| market.max_leverage)
+            .ok_or_else(|| format!("Invalid asset. asset={}", action.asset))?;
+        if action.leverage == 0 || action.leverage > max {
+            return Err(format!("Invalid leverage value. asset={}", action.asset));
+        }
+        self.accounts.entry(user).or_default().leverage.insert(
+            action.asset,
+            Leverage {
+                is_cross: action.is_cross,
+                value: action.leverage,
+            },
+        );
+        Ok(())
+    }
+
+    /// Status of one order of a batch: resting, filled, or the exchange's rejection.
+    fn place_order(&mut self, user: Address, wire_order: OrderWire, now: u64) -> Value {
+        let asset = wire_order.asset;
+        let reject = |message: &str| json!({"error": format!("{message} asset={asset}")});
+        let Some(market) = self.markets.get(asset as usize).cloned() else {
+            return reject("Invalid asset.");
+        };
+
+        let precision = OrderPrecision::perp(market.sz_decimals);
+        if wire_order.size.is_zero() {
+            return reject("Order has zero size.");
+        }
+        if precision
+            .round_price(wire_order.limit_px, wire_order.is_buy, RoundingMode::Strict)
+            .is_err()
+        {
+            return reject("Price must be divisible by tick size.");
+        }
+        if precision
+            .round_size(wire_order.size, RoundingMode::Strict)
+            .is_err()
+        {
+            return reject("Order has invalid size.");
+        }
+
+        let (tif, trigger) = match &wire_order.order_type {
+            OrderType::Limit(limit) => (Some(limit.tif), None),
+            OrderType::Trigger(trigger) => (
+                None,
+                Some(Trigger {
+                    px: trigger.trigger_px,
+                    is_market: trigger.is_market,
+                    tpsl: trigger.tpsl,
+                }),
+            ),
+        };
+
+        let szi = self.position(user, asset).szi;
+        let mut sz = wire_order.size;
+        if wire_order.reduce_only {
+            let reduces = if wire_order.is_buy {
+                szi.is_sign_negative() && !szi.is_zero()
+            } else {
+                szi.is_sign_positive() && !szi.is_zero()
+            };
+            if !reduces {
+                return reject("Reduce only order would increase position.");
+            }
+            sz = sz.min(szi.abs());
+        } else if sz * wire_order.limit_px < MIN_ORDER_VALUE {
+            return reject("Order must have minimum value of $10.");
+        }
+
+        let mark = market.mark_px;
+        let takes = trigger.is_none() && crosses(wire_order.is_buy, wire_order.limit_px, mark);
+        match tif {
+            Some(Tif::Alo) if takes => {
+                let message = format!(
+                    "Post only order would have immediately matched, bbo was {}@{}.",
+                    wire(mark),
+                    wire(mark)
+                );
+                return reject(&message);
+            }
+            Some(Tif::Ioc | Tif::FrontendMarket) if !takes => {
+                return reject("Order could not immediately match against any resting orders.");
+            }
+            _ => {}
+        }
+
+        if !wire_order.reduce_only {
+            let px = if takes { mark } else { wire_order.limit_px };
+            let signed = if wire_order.is_buy { sz } else { -sz };
+            let increase = ((szi + signed).abs() - szi.abs()).max(Decimal::ZERO);
+            let required = increase * px / Decimal::from(self.leverage(user, asset).value);
+            if required > self.margin_summary(user).available() {
+                return reject("Insufficient margin to place order.");
+            }
+        }
+
+        let oid = self.next_oid;
+        self.next_oid += 1;
+        self.orders.insert(
+            oid,
+            Order {
+                user,
+                asset,
+                is_buy: wire_order.is_buy,
+                limit_px: wire_order.limit_px,
+                sz,
+                orig_sz: sz,
+                reduce_only: wire_order.reduce_only,
+                tif,
+                trigger,
+                cloid: wire_order.client_order_id,
+                timestamp: now,
+                status: "open",
+                status_timestamp: now,
+            },
+        );
+
+        if takes {
+            self.fill_order(oid, mark, true, now);
+            json!({"filled": {"totalSz": wire(sz), "avgPx": wire(mark), "oid": oid}})
+        } else {
+            self.push_order_update(oid);
+            json!({"resting": {"oid": oid}})
+        }
+    }
+
+    fn cancel(
+        &mut self,
+        user: Address,
+        asset: u32,
+        now: u64,
+        matches: impl Fn(u64, &Order) -> bool,
+    ) -> Value {
+        let found = self
+            .orders
+            .iter()
+            .find(|(oid, order)| {
+                order.user == user
+                    && order.asset == asset
+                    && order.is_open()
+                    && matches(**oid, order)
+            })
+            .map(|(oid, _)| *oid);
+
+        match found {
+            Some(oid) => {
+                let order = self.orders.get_mut(&oid).unwrap();
+                order.status = "canceled";
+                order.status_timestamp = now;
+                self.push_order_update(oid);
+                json!("success")
+            }
+            None => json!({
+                "error": format!("Order was never placed, already canceled, or filled. asset={asset}")
+            }),
+        }
+    }
+
+    fn position(&self, user: Address, asset: u32) -> Position {
+        self.accounts
+            .get(&user)
+            .and_then(|account| account.positions.get(&asset))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Fill the rest of order `oid` at `px`, as taker when `crossed`.
+    fn fill_order(&mut self, oid: u64, px: Decimal, crossed: bool, now: u64) {
+        let order = self.orders.get_mut(&oid).unwrap();
+        let (user, asset, is_buy, sz, cloid) =
+            (order.user, order.asset, order.is_buy, order.sz, order.cloid);
+        order.sz = Decimal::ZERO;
+        order.status = "filled";
+        order.status_timestamp = now;
+
+        let coin = self.markets[asset as usize].name.clone();
+        let account = self.accounts.entry(user).or_default();
+        let start = account.positions.get(&asset).copied().unwrap_or_default();
+        let signed = if is_buy { sz } else { -sz };
+        let end = start.szi + signed;
+
+        let is_long = start.szi > Decimal::ZERO;
+        let closing = if !start.szi.is_zero() && is_long != is_buy {
+            sz.min(start.szi.abs())
+        } else {
+            Decimal::ZERO
+        };
+        let direction = if is_long {
+            Decimal::ONE
+        } else {
+            Decimal::NEGATIVE_ONE
+        };
+        let closed_pnl = closing * (px - start.entry_px) * direction;
+
+        let dir = if closing.is_zero() {
+            if is_buy {
+                "Open Long"
+            } else {
+                "Open Short"
+            }
+        } else if !end.is_zero() && (end > Decimal::ZERO) != is_long {
+            if is_buy {
+                "Short > Long"
+            } else {
+                "Long > Short"
+            }
+        } else if is_buy {
+            "Close Short"
+        } else {
+            "Close Long"
+        };
+
+        if end.is_zero() {
+            account.positions.remove(&asset);
+        } else {
+            let entry_px = if closing.is_zero() {
+                (start.szi.abs() * start.entry_px + sz * px) / end.abs()
+            } else if (end > Decimal::ZERO) != is_long {
+                px
+            } else {
+                start.entry_px
+            };
+            account.positions.insert(
+                asset,
+                Position {
+                    szi: end,
+                    entry_px: entry_px.round_dp(8),
+                },
+            );
+        }
+
+        let fee = (sz * px * if crossed { TAKER_FEE } else { MAKER_FEE }).round_dp(6);
+        account.usdc += closed_pnl - fee;
+
+        let tid = self.next_tid;
+        self.next_tid += 1;
+        let fill = json!({
+            "coin": coin,
+            "px": wire(px),
+            "sz": wire(sz),
+            "side": side(is_buy),
+            "time": now,
+            "startPosition": wire(start.szi),
+            "dir": dir,
+            "closedPnl": usd(closed_pnl),
+            "hash": format!("0x{tid:064x}"),
+            "oid": oid,
+            "crossed": crossed,
+            "fee": usd(fee),
+            "tid": tid,
+            "feeToken": "USDC",
+            "cloid": cloid,
+        });
+        self.fills.push((user, fill.clone()));
+
+        self.push_order_update(oid);
+        self.push(
+            None,
+            "userFills",
+            json!({"user": format!("{user:#x}"), "fills": [fill]}),
+        );
+    }
+
+    fn push_order_update(&self, oid: u64) {
+        let order = &self.orders[&oid];
+        let update = json!({
+            "order": self.order_json(oid, order),
+            "status": order.status,
+            "statusTimestamp": order.status_timestamp,
+        });
+        self.push(Some(order.user), "orderUpdates", json!([update]));
+    }
+
+    fn push(&self, user: Option<Address>, channel: &str, data: Value) {
+        let frame = json!({"channel": channel, "data": data});
+        let message =
+            serde_json::from_value(frame.clone()).expect("mock pushes are valid messages");
+        // No subscribers is not an error.
+        let _ = self.pushes.send(Push {
+            user,
+            message,
+            frame: frame.to_string(),
+        });
+    }
+
+    /// Messages sent right after subscribing to `channel` for `user`.
+    pub fn snapshot(&self, channel: &str, user: Option<Address>) -> Option<Value> {
+        match (channel, user) {
+            ("allMids", _) => Some(json!({"channel": "allMids", "data": {"mids": self.mids()}})),
+            ("userFills", Some(user)) => {
+                let fills: Vec<&Value> = self
+                    .fills
+                    .iter()
+                    .filter(|(fill_user, _)| *fill_user == user)
+                    .map(|(_, fill)| fill)
+                    .collect();
+                Some(json!({
+                    "channel": "userFills",
+                    "data": {"isSnapshot": true, "user": format!("{user:#x}"), "fills": fills},
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct MarginSummary {
+    account_value: Decimal,
+    total_ntl_pos: Decimal,
+    /// Signed notional, longs positive.
+    net_ntl_pos: Decimal,
+    margin_used: Decimal,
+    maintenance_margin: Decimal,
+    /// Margin reserved by open orders.
+    order_margin: Decimal,
+}
+
+impl MarginSummary {
+    fn available(&self) -> Decimal {
+        self.account_value - self.margin_used - self.order_margin
+    }
+}
+
+/// `spotMeta` with USDC as the only token.
+fn spot_meta() -> Value {
+    json!({
+        "universe": [],
+        "tokens": [{
+            "name": "USDC",
+            "szDecimals": 8,
+            "weiDecimals": 8,
+            "index": 0,
+            "tokenId": "0x6d1e7cde53ba9467b783cb7c530ce054",
+            "isCanonical": true,
+        }],
+    })
+}
+
+/// Whether a limit order at `limit_px` trades against liquidity at `mark_px`.
+fn crosses(is_buy: bool, limit_px: Decimal, mark_px: Decimal) -> bool {
+    if is_buy {
+        limit_px >= mark_px
+    } else {
+        limit_px <= mark_px
+    }
+}
+
+fn side(is_buy: bool) -> &'static str {
+    if is_buy {
+        "B"
+    } else {
+        "A"
+    }
+}
+
+fn wire(value: Decimal) -> String {
+    value.normalize().to_string()
+}
+
+fn usd(value: Decimal) -> String {
+    wire(value.round_dp(6))
+}
@@ -0,0 +1,413 @@
+//! Nonces for signed actions.
+//!
+//! Hyperliquid keeps the 100 highest nonces of each signer. A new nonce must be higher than
+//! the smallest of them, must not have been used, and must lie within
+//! (server time - 2 days, server time + 1 day). Nonces are millisecond timestamps, bumped
+//! when several actions are signed within the same millisecond.
+//!
+//! A [`NonceSource`] hands out those nonces. Every [`ExchangeClient`](crate::ExchangeClient),
+//! [`PreparedAction::new`](crate::PreparedAction::new) and
+//! [`MultiSigBuilder::new`](crate::MultiSigBuilder::new) use the process-wide [`shared`]
+//! source unless given another one, so clients in one process never collide. Processes that
+//! share a signer should share a [`FileNonceSource`], installed with [`set_shared`] or
+//! [`ExchangeClient::with_nonce_source`](crate::ExchangeClient::with_nonce_source).
+//!
+//! Nonces are checked against an estimate of the server clock: the local clock corrected by
+//! the offset measured from the `Date` header of the latest HTTP response (see
+//! [`server_time_ms`]). Until a response has been seen the estimate is the local clock.
+//!
+//! # Example
+//! ```no_run
+//! use alloy::signers::local::PrivateKeySigner;
+//! use hl_rs::{nonce::FileNonceSource, BaseUrl, ExchangeClient};
+//!
+//! # fn run(wallet: PrivateKeySigner) -> Result<(), hl_rs::Error> {
+//! let nonces = FileNonceSource::open(format!("/var/run/hl/{}.nonce", wallet.address()))?;
+//! let client = ExchangeClient::new(BaseUrl::Mainnet)
+//!     .with_signer(wallet)
+//!     .with_nonce_source(nonces);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    fmt,
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering},
+        Arc, LazyLock, RwLock,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{prelude::Result, Error};
+
+/// Nonces more than this far behind server time are rejected.
+pub const NONCE_MAX_AGE_MS: u64 = 2 * 24 * 60 * 60 * 1000;
+
+/// Nonces this far or further ahead of server time are rejected.
+pub const NONCE_MAX_AHEAD_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Check that `nonce` is within (server time - 2 days, server time + 1 day), the window the
+/// exchange accepts.
+pub fn check_nonce_window(nonce: u64, server_time_ms: u64) -> Result<()> {
+    // A bound past u64::MAX can't be reached.
+    let too_old = nonce
+        .checked_add(NONCE_MAX_AGE_MS)
+        .is_some_and(|oldest| oldest <= server_time_ms);
+    let too_new = server_time_ms
+        .checked_add(NONCE_MAX_AHEAD_MS)
+        .is_some_and(|limit| nonce >= limit);
+    if too_old || too_new {
+        return Err(Error::NonceOutOfWindow {
+            nonce,
+            server_time: server_time_ms,
+        });
+    }
+    Ok(())
+}
+
+/// Hands out nonces for one or more signers.
+pub trait NonceSource: Send + Sync {
+    /// A nonce higher than every nonce handed out before, within the accepted window.
+    fn next_nonce(&self) -> Result<u64>;
+
+    /// Estimate of the server time (ms) nonces are checked against; [`server_time_ms`] unless
+    /// the source is told otherwise.
+    fn server_time_ms(&self) -> u64 {
+        server_time_ms()
+    }
+}
+
+impl fmt::Debug for dyn NonceSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("NonceSource")
+    }
+}
+
+static SHARED: LazyLock<RwLock<Arc<dyn NonceSource>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(InMemoryNonceSource::new())));
+
+/// The process-wide source: an [`InMemoryNonceSource`] unless replaced with [`set_shared`].
+pub fn shared() -> Arc<dyn NonceSource> {
+    SHARED.read().unwrap().clone()
+}
+
+/// Replace the process-wide source used by every client, [`PreparedAction::new`] and
+/// [`MultiSigBuilder::new`] not given a source of their own. Set it before signing anything:
+/// nonces already handed out by the previous source are not carried over.
+///
+/// [`PreparedAction::new`]: crate::PreparedAction::new
+/// [`MultiSigBuilder::new`]: crate::MultiSigBuilder::new
+pub fn set_shared(source: Arc<dyn NonceSource>) {
+    *SHARED.write().unwrap() = source;
+}
+
+/// Server time minus local time as last measured, and whether it has been measured.
+static SERVER_OFFSET_MS: AtomicI64 = AtomicI64::new(0);
+static SERVER_OFFSET_MEASURED: AtomicBool = AtomicBool::new(false);
+
+/// Estimate of the exchange's clock (ms): the local clock corrected by the offset measured
+/// with [`observe_server_date`], or the local clock before any measurement.
+pub fn server_time_ms() -> u64 {
+    current_timestamp_ms().saturating_add_signed(SERVER_OFFSET_MS.load(Ordering::Relaxed))
+}
+
+/// Server time minus local time (ms) as last measured, `None` before any response was seen.
+pub fn server_clock_offset_ms() -> Option<i64> {
+    SERVER_OFFSET_MEASURED
+        .load(Ordering::Relaxed)
+        .then(|| SERVER_OFFSET_MS.load(Ordering::Relaxed))
+}
+
+/// Calibrate [`server_time_ms`] from the `Date` header of a response (`"Tue, 15 Nov 1994
+/// 08:12:31 GMT"`). The header has second resolution, so the estimate is good to about a
+/// second. Called for every HTTP response; headers that don't parse are ignored.
+pub fn observe_server_date(date: &str) {
+    if let Some(offset) = offset_from_date(date, current_timestamp_ms()) {
+        SERVER_OFFSET_MS.store(offset, Ordering::Relaxed);
+        SERVER_OFFSET_MEASURED.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Offset of the server clock given its `Date` header, read at local time `local_ms`.
+fn offset_from_date(date: &str, local_ms: u64) -> Option<i64> {
+    let server = chrono::DateTime::parse_from_rfc2822(date).ok()?;
+    // The header truncates to the second; assume the middle of it.
+    let server_ms = server.timestamp_millis().checked_add(500)?;
+    server_ms.checked_sub(i64::try_from(local_ms).ok()?)
+}
+
+/// Nonces from the estimated server clock, kept strictly increasing in memory. Lock-free.
+#[derive(Debug, Default)]
+pub struct InMemoryNonceSource {
+    last: AtomicU64,
+    clock_offset_ms: Option<i64>,
+}
+
+impl InMemoryNonceSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a fixed server time minus local time instead of the measured one.
+    pub fn with_clock_offset(mut self, offset_ms: i64) -> Self {
+        self.clock_offset_ms = Some(offset_ms);
+        self
+    }
+}
+
+impl NonceSource for InMemoryNonceSource {
+    fn next_nonce(&self) -> Result<u64> {
+        let now = self.server_time_ms();
+        loop {
+            let last = self.last.load(Ordering::Relaxed);
+            let next = following(last, now)?;
+            if self
+                .last
+                .compare_exchange(last, next, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok()
+            {
+                return Ok(next);
+            }
+        }
+    }
+
+    fn server_time_ms(&self) -> u64 {
+        match self.clock_offset_ms {
+            Some(offset) => current_timestamp_ms().saturating_add_signed(offset),
+            None => server_time_ms(),
+        }
+    }
+}
+
+/// Nonces from the estimated server clock, kept strictly increasing across processes through
+/// a file holding the last nonce.
+///
+/// Each call takes an exclusive lock on the file, so any number of processes (and sources)
+/// using the same path hand out distinct, increasing nonces.
+#[derive(Debug, Clone)]
+pub struct FileNonceSource {
+    path: PathBuf,
+    clock_offset_ms: Option<i64>,
+}
+
+impl FileNonceSource {
+    /// Use the file at `path`, creating it if it doesn't exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        open_file(&path)?;
+        Ok(Self {
+            path,
+            clock_offset_ms: None,
+        })
+    }
+
+    /// Use a fixed server time minus local time instead of the measured one.
+    pub fn with_clock_offset(mut self, offset_ms: i64) -> Self {
+        self.clock_offset_ms = Some(offset_ms);
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl NonceSource for FileNonceSource {
+    fn next_nonce(&self) -> Result<u64> {
+        let store_err =
+            |e: std::io::Error| Error::NonceStore(format!("{}: {e}", self.path.display()));
+
+        let mut file = open_file(&self.path)?;
+        // Released when the file is closed.
+        file.lock().map_err(store_err)?;
+
+        let mut text = String::new();
+        file.read_to_string(&mut text).map_err(store_err)?;
+        let last = match text.trim() {
+            "" => 0,
+            last => last.parse().map_err(|e| {
+                Error::NonceStore(format!("{}: `{last}`: {e}", self.path.display()))
+            })?,
+        };
+
+        let next = following(last, self.server_time_ms())?;
+        file.seek(SeekFrom::Start(0)).map_err(store_err)?;
+        file.set_len(0).map_err(store_err)?;
+        file.write_all(next.to_string().as_bytes())
+            .and_then(|()| file.sync_data())
+            .map_err(store_err)?;
+        Ok(next)
+    }
+
+    fn server_time_ms(&self) -> u64 {
+        match self.clock_offset_ms {
+            Some(offset) => current_timestamp_ms().saturating_add_signed(offset),
+            None => server_time_ms(),
+        }
+    }
+}
+
+pub(crate) fn current_timestamp_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+fn open_file(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .map_err(|e| Error::NonceStore(format!("{}: {e}", path.display())))
+}
+
+/// Nonce after `last`: the server time, or `last + 1` if that is not higher.
+fn following(last: u64, now: u64) -> Result<u64> {
+    let next = last
+        .checked_add(1)
+        .ok_or(Error::NonceOutOfWindow {
+            nonce: last,
+            server_time: now,
+        })?
+        .max(now);
+    check_nonce_window(next, now)?;
+    Ok(next)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, thread};
+
+    use super::*;
+
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("hl-rs-{name}-{}.nonce", std::process::id()))
+    }
+
+    #[test]
+    fn window_is_two_days_back_and_one_ahead() {
+        let now = 1_700_000_000_000;
+        assert!(check_nonce_window(now, now).is_ok());
+        assert!(check_nonce_window(now - 2 * DAY_MS + 1, now).is_ok());
+        assert!(check_nonce_window(now - 2 * DAY_MS, now).is_err());
+        assert!(check_nonce_window(now + DAY_MS - 1, now).is_ok());
+        assert!(matches!(
+            check_nonce_window(now + DAY_MS, now),
+            Err(Error::NonceOutOfWindow { nonce, server_time }) if nonce == now + DAY_MS && server_time == now
+        ));
+    }
+
+    #[test]
+    fn nonces_near_u64_max_are_checked_without_overflow() {
+        let now = 1_700_000_000_000;
+        assert!(check_nonce_window(u64::MAX, now).is_err());
+        assert!(check_nonce_window(u64::MAX - 1, u64::MAX).is_ok());
+        assert!(check_nonce_window(0, u64::MAX).is_err());
+        assert!(matches!(
+            following(u64::MAX, now),
+            Err(Error::NonceOutOfWindow {
+                nonce: u64::MAX,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn date_header_gives_the_server_offset() {
+        // 2023-11-14T22:13:20Z
+        let local = 1_700_000_000_000;
+        assert_eq!(
+            offset_from_date("Tue, 14 Nov 2023 22:13:20 GMT", local),
+            Some(500)
+        );
+        assert_eq!(
+            offset_from_date("Wed, 15 Nov 2023 22:13:20 GMT", local),
+            Some(DAY_MS as i64 + 500)
+        );
+        assert_eq!(offset_from_date("yesterday", local), None);
+    }
+
+    #[test]
+    fn in_memory_nonces_are_unique_across_threads() {
+        let source = Arc::new(InMemoryNonceSource::new());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let source = source.clone();
+                thread::spawn(move || {
+                    (0..500)
+                        .map(|_| source.next_nonce().unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let mut seen = HashSet::new();
+        for handle in handles {
+            let nonces = handle.join().unwrap();
+            assert!(nonces.windows(2).all(|pair| pair[0] < pair[1]));
+            seen.extend(nonces);
+        }
+        assert_eq!(seen.len(), 2000);
+    }
+
+    #[test]
+    fn clock_offset_shifts_nonces() {
+        let ahead = InMemoryNonceSource::new().with_clock_offset(DAY_MS as i64 / 2);
+        assert!(ahead.next_nonce().unwrap() > current_timestamp_ms() + DAY_MS / 4);
+    }
+
+    #[test]
+    fn file_sources_on_one_path_never_collide() {
+        let path = temp_path("shared");
+        let _ = std::fs::remove_file(&path);
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let source = FileNonceSource::open(&path).unwrap();
+                thread::spawn(move || {
+                    (0..100)
+                        .map(|_| source.next_nonce().unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let mut seen = HashSet::new();
+        for handle in handles {
+            seen.extend(handle.join().unwrap());
+        }
+        assert_eq!(seen.len(), 400);
+        let last: u64 = std::fs::read_to_string(&path).unwrap().parse().unwrap();
+        assert_eq!(Some(&last), seen.iter().max());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn file_source_refuses_a_counter_far_ahead() {
+        let path = temp_path("ahead");
+        let ahead = current_timestamp_ms() + 2 * DAY_MS;
+        std::fs::write(&path, ahead.to_string()).unwrap();
+
+        let source = FileNonceSource::open(&path).unwrap();
+        assert!(matches!(
+            source.next_nonce(),
+            Err(Error::NonceOutOfWindow { nonce, .. }) if nonce == ahead + 1
+        ));
+        // The refused nonce is not stored.
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), ahead.to_string());
+
+        std::fs::write(&path, "not a nonce").unwrap();
+        assert!(matches!(source.next_nonce(), Err(Error::NonceStore(_))));
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
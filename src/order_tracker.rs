@@ -0,0 +1,809 @@
+//! Order lifecycle tracking.
+//!
+//! What is known about an order is spread over the statuses of the `order` response,
+//! `orderUpdates` and fills pushed on the WebSocket, and `orderStatus`. [`OrderTracker`]
+//! folds all of them into one [`TrackedOrder`] per order, found by oid or cloid, and reports
+//! every change as an [`OrderEvent`].
+//!
+//! Feed it the statuses of every `order` sent with [`OrderTracker::apply_response`] and, with
+//! the `ws` feature, the events of a `ManagedWsClient` subscribed to `orderUpdates` and
+//! `userFills` of the account with `OrderTracker::apply_ws_event`. Sources may arrive in any
+//! order: states only move forward and fills are counted once by trade id. Anything pushed
+//! while the socket was down is lost, so a reconnect marks the tracker
+//! [stale](OrderTracker::is_stale) until [`OrderTracker::reconcile`] has polled `orderStatus`
+//! for every open order.
+
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use alloy::primitives::Address;
+use rust_decimal::Decimal;
+use tokio::sync::broadcast;
+
+use crate::{
+    actions::{Cloid, OrderWire},
+    clients::exchange::responses::{ExchangeDataStatus, ExchangeDataStatuses},
+    info::{
+        types::{OrderStatusResponse, UserFillsResponse},
+        InfoClient,
+    },
+    prelude::Result,
+};
+
+#[cfg(feature = "ws")]
+use crate::{
+    responses::{WsFill, WsOrder, WsUserEvent},
+    WsEvent, WsMessage,
+};
+
+/// Change events buffered per [`OrderTracker::subscribe`] receiver.
+const EVENT_BUFFER: usize = 1024;
+
+/// Where an order is in its life.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrderState {
+    /// Sent, or accepted without an oid yet (`waitingForFill`, `waitingForTrigger`).
+    Pending,
+    /// On the book, nothing filled.
+    Resting,
+    /// Trigger order whose trigger fired.
+    Triggered,
+    /// Part of the size filled, the rest still live.
+    PartiallyFilled,
+    Filled,
+    /// Canceled by the user or the exchange; see [`TrackedOrder::reason`].
+    Canceled,
+    /// Refused by the exchange; see [`TrackedOrder::reason`].
+    Rejected,
+}
+
+impl OrderState {
+    /// Whether the order can no longer change state.
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Filled | Self::Canceled | Self::Rejected)
+    }
+
+    /// Order of states along the lifecycle; states never move to a lower rank.
+    fn rank(self) -> u8 {
+        match self {
+            Self::Pending => 0,
+            Self::Resting => 1,
+            // A triggered order can rest, but is never waiting for its trigger again.
+            Self::Triggered => 2,
+            Self::PartiallyFilled => 3,
+            Self::Filled | Self::Canceled | Self::Rejected => 4,
+        }
+    }
+
+    /// State for an `orderUpdates` / `orderStatus` status string.
+    fn from_status(status: &str) -> Option<Self> {
+        match status {
+            "open" => Some(Self::Resting),
+            "triggered" => Some(Self::Triggered),
+            "filled" => Some(Self::Filled),
+            "canceled" | "scheduledCancel" => Some(Self::Canceled),
+            "rejected" => Some(Self::Rejected),
+            // `marginCanceled`, `reduceOnlyCanceled`, `tickRejected`, ...
+            status if status.ends_with("Canceled") => Some(Self::Canceled),
+            status if status.ends_with("Rejected") => Some(Self::Rejected),
+            _ => None,
+        }
+    }
+}
+
+/// Everything the tracker knows about one order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackedOrder {
+    pub oid: Option<u64>,
+    pub cloid: Option<Cloid>,
+    /// Coin name, once an update or status named it.
+    pub coin: Option<String>,
+    pub state: OrderState,
+    /// Original size, once known.
+    pub orig_sz: Option<Decimal>,
+    /// Cumulative filled size.
+    pub filled_sz: Decimal,
+    /// Average fill price, `None` before anything filled.
+    pub avg_px: Option<Decimal>,
+    /// Exchange status or error message behind [`OrderState::Canceled`] and
+    /// [`OrderState::Rejected`] (`"marginCanceled"`, `"Order must have minimum value of $10."`).
+    pub reason: Option<String>,
+    /// Filled size and average price as reported in aggregate (response, remaining size),
+    /// for when the individual fills have not been seen.
+    reported: Option<(Decimal, Option<Decimal>)>,
+    /// Size and notional of the fills seen.
+    fills: (Decimal, Decimal),
+    tids: HashSet<u64>,
+}
+
+impl TrackedOrder {
+    fn new() -> Self {
+        Self {
+            oid: None,
+            cloid: None,
+            coin: None,
+            state: OrderState::Pending,
+            orig_sz: None,
+            filled_sz: Decimal::ZERO,
+            avg_px: None,
+            reason: None,
+            reported: None,
+            fills: (Decimal::ZERO, Decimal::ZERO),
+            tids: HashSet::new(),
+        }
+    }
+
+    /// Size still live: original size minus filled, `None` while the original size is unknown.
+    pub fn remaining_sz(&self) -> Option<Decimal> {
+        self.orig_sz
+            .map(|orig| (orig - self.filled_sz).max(Decimal::ZERO))
+    }
+
+    /// Move to `state` unless that would go backwards.
+    fn advance(&mut self, state: OrderState, reason: Option<&str>) {
+        if self.state.is_terminal() || state.rank() < self.state.rank() {
+            return;
+        }
+        self.state = state;
+        if state.is_terminal() {
+            self.reason = reason.map(str::to_string);
+        }
+    }
+
+    fn report_filled(&mut self, filled_sz: Decimal, avg_px: Option<Decimal>) {
+        let known = self.reported.map_or(Decimal::ZERO, |(sz, _)| sz);
+        if filled_sz > known || (filled_sz == known && avg_px.is_some()) {
+            self.reported = Some((filled_sz, avg_px));
+        }
+        self.refresh_fill();
+    }
+
+    fn add_fill(&mut self, tid: u64, px: Decimal, sz: Decimal) {
+        if self.tids.insert(tid) {
+            self.fills.0 += sz;
+            self.fills.1 += px * sz;
+            self.refresh_fill();
+        }
+    }
+
+    /// Recompute the filled size and average price, and move to the state they imply.
+    fn refresh_fill(&mut self) {
+        let (fill_sz, fill_notional) = self.fills;
+        let (reported_sz, reported_px) = self.reported.unwrap_or((Decimal::ZERO, None));
+        let fill_px = (!fill_sz.is_zero()).then(|| fill_notional / fill_sz);
+        if fill_sz >= reported_sz {
+            self.filled_sz = fill_sz;
+            self.avg_px = fill_px;
+        } else {
+            self.filled_sz = reported_sz;
+            self.avg_px = reported_px.or(fill_px);
+        }
+
+        if self.filled_sz.is_zero() {
+            return;
+        }
+        match self.orig_sz {
+            Some(orig) if self.filled_sz >= orig => self.advance(OrderState::Filled, None),
+            _ => self.advance(OrderState::PartiallyFilled, None),
+        }
+    }
+
+    /// Fold `other`, a second record of the same order, into this one.
+    fn merge(&mut self, other: TrackedOrder) {
+        self.oid = self.oid.or(other.oid);
+        self.cloid = self.cloid.or(other.cloid);
+        self.coin = self.coin.take().or(other.coin);
+        self.orig_sz = self.orig_sz.or(other.orig_sz);
+        if other.state.rank() > self.state.rank() && !self.state.is_terminal() {
+            self.state = other.state;
+            self.reason = other.reason;
+        }
+        if let Some((sz, px)) = other.reported {
+            self.report_filled(sz, px);
+        }
+        for tid in other.tids {
+            self.tids.insert(tid);
+        }
+        self.fills.0 += other.fills.0;
+        self.fills.1 += other.fills.1;
+        self.refresh_fill();
+    }
+}
+
+/// A change to a tracked order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderEvent {
+    /// State before the change, `None` when the order was first seen.
+    pub previous: Option<OrderState>,
+    /// The order after the change.
+    pub order: TrackedOrder,
+}
+
+/// Order state machine fed by exchange responses, WebSocket pushes and `orderStatus`; see the
+/// [module docs](self).
+///
+/// Every `apply_*` method returns the changes it caused; [`subscribe`](Self::subscribe)
+/// delivers the same events to other tasks.
+#[derive(Debug)]
+pub struct OrderTracker {
+    orders: HashMap<u64, TrackedOrder>,
+    by_oid: HashMap<u64, u64>,
+    by_cloid: HashMap<Cloid, u64>,
+    next_id: u64,
+    stale: bool,
+    events: broadcast::Sender<OrderEvent>,
+}
+
+impl Default for OrderTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrderTracker {
+    pub fn new() -> Self {
+        Self {
+            orders: HashMap::new(),
+            by_oid: HashMap::new(),
+            by_cloid: HashMap::new(),
+            next_id: 0,
+            stale: false,
+            events: broadcast::channel(EVENT_BUFFER).0,
+        }
+    }
+
+    /// Receive every [`OrderEvent`] from now on. A receiver that falls more than 1024 events
+    /// behind misses the oldest ones.
+    pub fn subscribe(&self) -> broadcast::Receiver<OrderEvent> {
+        self.events.subscribe()
+    }
+
+    pub fn get_by_oid(&self, oid: u64) -> Option<&TrackedOrder> {
+        self.by_oid.get(&oid).map(|id| &self.orders[id])
+    }
+
+    pub fn get_by_cloid(&self, cloid: &Cloid) -> Option<&TrackedOrder> {
+        self.by_cloid.get(cloid).map(|id| &self.orders[id])
+    }
+
+    /// Orders that are not in a terminal state.
+    pub fn open_orders(&self) -> impl Iterator<Item = &TrackedOrder> {
+        self.orders
+            .values()
+            .filter(|order| !order.state.is_terminal())
+    }
+
+    /// Stop tracking orders in a terminal state.
+    pub fn remove_closed(&mut self) {
+        self.orders.retain(|_, order| !order.state.is_terminal());
+        self.by_oid.retain(|_, id| self.orders.contains_key(id));
+        self.by_cloid.retain(|_, id| self.orders.contains_key(id));
+    }
+
+    /// Whether updates may have been missed since the last [`reconcile`](Self::reconcile).
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+
+    /// Start tracking `order` as [`OrderState::Pending`] before it is sent, so fills and updates
+    /// that beat the response find it. Orders without a cloid can't be matched before the
+    /// response names their oid and are left to [`apply_response`](Self::apply_response).
+    pub fn track(&mut self, order: &OrderWire) -> Option<OrderEvent> {
+        let cloid = order.client_order_id?;
+        let orig_sz = order.size;
+        self.update(None, Some(cloid), |tracked| {
+            tracked.orig_sz.get_or_insert(orig_sz);
+        })
+    }
+
+    /// Apply the statuses of an `order` (or `batchModify`) response to the orders it was sent
+    /// with, matched by position. A status without an oid (e.g. a rejection) is skipped for an
+    /// order without a cloid, since nothing could ever match it.
+    pub fn apply_response(
+        &mut self,
+        orders: &[OrderWire],
+        response: &ExchangeDataStatuses,
+    ) -> Vec<OrderEvent> {
+        let mut events = Vec::new();
+        for (order, status) in orders.iter().zip(&response.statuses) {
+            let oid = match status {
+                ExchangeDataStatus::Resting(resting) => Some(resting.oid),
+                ExchangeDataStatus::Filled(filled) => Some(filled.oid),
+                ExchangeDataStatus::Success | ExchangeDataStatus::Unknown => continue,
+                _ => None,
+            };
+            if oid.is_none() && order.client_order_id.is_none() {
+                continue;
+            }
+            let orig_sz = order.size;
+            events.extend(self.update(oid, order.client_order_id, |tracked| {
+                tracked.orig_sz.get_or_insert(orig_sz);
+                match status {
+                    ExchangeDataStatus::Resting(_) => tracked.advance(OrderState::Resting, None),
+                    ExchangeDataStatus::Filled(filled) => tracked.report_filled(
+                        parse(&filled.total_sz).unwrap_or_default(),
+                        parse(&filled.avg_px),
+                    ),
+                    ExchangeDataStatus::Error(message) => {
+                        tracked.advance(OrderState::Rejected, Some(message))
+                    }
+                    _ => {}
+                }
+            }));
+        }
+        events
+    }
+
+    /// Apply an `orderStatus` answer. Unknown oids are ignored.
+    pub fn apply_order_status(&mut self, status: &OrderStatusResponse) -> Vec<OrderEvent> {
+        let Some(info) = &status.order else {
+            return Vec::new();
+        };
+        let order = &info.order;
+        if !self.knows(order.oid, order.cloid) {
+            return Vec::new();
+        }
+        self.apply_status(
+            order.oid,
+            order.cloid,
+            &order.coin,
+            &info.status,
+            parse(&order.orig_sz),
+            parse(&order.sz),
+        )
+        .into_iter()
+        .collect()
+    }
+
+    /// Apply fills from `userFills`. Fills of orders the tracker has not seen are ignored, as
+    /// the history covers far more than the live orders.
+    pub fn apply_user_fills(&mut self, fills: &[UserFillsResponse]) -> Vec<OrderEvent> {
+        fills
+            .iter()
+            .filter_map(|fill| {
+                let fill = Fill {
+                    oid: fill.oid,
+                    cloid: fill.cloid,
+                    coin: &fill.coin,
+                    tid: fill.tid,
+                    px: parse(&fill.px)?,
+                    sz: parse(&fill.sz)?,
+                };
+                self.apply_fill(fill, false)
+            })
+            .collect()
+    }
+
+    /// Poll `orderStatus` for every open order, by oid or else by cloid, and clear
+    /// [`is_stale`](Self::is_stale). Call after a reconnect, or periodically without a socket.
+    pub async fn reconcile(
+        &mut self,
+        info: &InfoClient,
+        user: &Address,
+    ) -> Result<Vec<OrderEvent>> {
+        let open: Vec<(Option<u64>, Option<Cloid>)> = self
+            .open_orders()
+            .map(|order| (order.oid, order.cloid))
+            .collect();
+        let mut events = Vec::new();
+        for ids in open {
+            let status = match ids {
+                (Some(oid), _) => info.order_status(user, oid).await?,
+                (None, Some(cloid)) => info.order_status_by_cloid(user, cloid).await?,
+                (None, None) => continue,
+            };
+            events.extend(self.apply_order_status(&status));
+        }
+        self.stale = false;
+        Ok(events)
+    }
+
+    /// Apply an event of a `ManagedWsClient`. A reconnect or disconnect marks the tracker
+    /// [stale](Self::is_stale).
+    #[cfg(feature = "ws")]
+    pub fn apply_ws_event(&mut self, event: &WsEvent) -> Vec<OrderEvent> {
+        match event {
+            WsEvent::Connected { reconnect } => {
+                self.stale |= *reconnect;
+                Vec::new()
+            }
+            WsEvent::Disconnected { .. } => {
+                self.stale = true;
+                Vec::new()
+            }
+            WsEvent::Message { message, .. } => self.apply_ws_message(message),
+        }
+    }
+
+    /// Apply `orderUpdates`, `userFills` and `userEvents` pushes; other messages are ignored.
+    ///
+    /// Fills in a `userFills` snapshot only update orders the tracker has seen.
+    #[cfg(feature = "ws")]
+    pub fn apply_ws_message(&mut self, message: &WsMessage) -> Vec<OrderEvent> {
+        match message {
+            WsMessage::OrderUpdates { data, .. } => self.apply_order_updates(data),
+            WsMessage::UserFills { data, .. } => {
+                let snapshot = data.is_snapshot.unwrap_or(false);
+                self.apply_ws_fills(&data.fills, !snapshot)
+            }
+            WsMessage::UserEvents { data, .. } => match data {
+                WsUserEvent::Fills { fills } => self.apply_ws_fills(fills, true),
+                WsUserEvent::NonUserCancel { non_user_cancel } => non_user_cancel
+                    .iter()
+                    .filter_map(|cancel| {
+                        self.apply_status(cancel.oid, None, &cancel.coin, "canceled", None, None)
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    /// Apply `orderUpdates` entries. Orders the tracker has not seen, e.g. placed by another
+    /// client of the account, are added.
+    #[cfg(feature = "ws")]
+    pub fn apply_order_updates(&mut self, updates: &[WsOrder]) -> Vec<OrderEvent> {
+        updates
+            .iter()
+            .filter_map(|update| {
+                let order = &update.order;
+                self.apply_status(
+                    order.oid,
+                    order.cloid,
+                    &order.coin,
+                    &update.status,
+                    Some(order.orig_sz),
+                    Some(order.sz),
+                )
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "ws")]
+    fn apply_ws_fills(&mut self, fills: &[WsFill], create: bool) -> Vec<OrderEvent> {
+        fills
+            .iter()
+            .filter_map(|fill| {
+                let fill = Fill {
+                    oid: fill.oid,
+                    cloid: fill.cloid,
+                    coin: &fill.coin,
+                    tid: fill.tid,
+                    px: fill.px,
+                    sz: fill.sz,
+                };
+                self.apply_fill(fill, create)
+            })
+            .collect()
+    }
+
+    /// Apply a status string with the original and remaining size it was reported with.
+    fn apply_status(
+        &mut self,
+        oid: u64,
+        cloid: Option<Cloid>,
+        coin: &str,
+        status: &str,
+        orig_sz: Option<Decimal>,
+        remaining_sz: Option<Decimal>,
+    ) -> Option<OrderEvent> {
+        let state = OrderState::from_status(status);
+        self.update(Some(oid), cloid, |tracked| {
+            tracked.coin.get_or_insert_with(|| coin.to_string());
+            if let Some(orig) = orig_sz {
+                tracked.orig_sz = Some(orig);
+                if let Some(remaining) = remaining_sz {
+                    tracked.report_filled(orig - remaining, None);
+                }
+            }
+            match state {
+                Some(OrderState::Resting) if !tracked.filled_sz.is_zero() => {
+                    tracked.advance(OrderState::PartiallyFilled, None)
+                }
+                Some(OrderState::Filled) => {
+                    // A filled order has no size left, whatever fills have been seen.
+                    if let Some(orig) = tracked.orig_sz {
+                        tracked.report_filled(orig, None);
+                    }
+                    tracked.advance(OrderState::Filled, None);
+                }
+                Some(state) => tracked.advance(state, Some(status)),
+                None => {}
+            }
+        })
+    }
+
+    fn apply_fill(&mut self, fill: Fill<'_>, create: bool) -> Option<OrderEvent> {
+        if !create && !self.knows(fill.oid, fill.cloid) {
+            return None;
+        }
+        self.update(Some(fill.oid), fill.cloid, |tracked| {
+            tracked.coin.get_or_insert_with(|| fill.coin.to_string());
+            tracked.add_fill(fill.tid, fill.px, fill.sz);
+        })
+    }
+
+    /// Whether an order with `oid` or `cloid` is tracked.
+    fn knows(&self, oid: u64, cloid: Option<Cloid>) -> bool {
+        self.by_oid.contains_key(&oid)
+            || cloid.is_some_and(|cloid| self.by_cloid.contains_key(&cloid))
+    }
+
+    /// Apply `change` to the order with `oid` or `cloid`, creating or merging records as
+    /// needed, and publish the event if anything visible changed.
+    fn update(
+        &mut self,
+        oid: Option<u64>,
+        cloid: Option<Cloid>,
+        change: impl FnOnce(&mut TrackedOrder),
+    ) -> Option<OrderEvent> {
+        let by_cloid = cloid.and_then(|cloid| self.by_cloid.get(&cloid).copied());
+        let by_oid = oid.and_then(|oid| self.by_oid.get(&oid).copied());
+        let id = match (by_cloid, by_oid) {
+            (Some(id), Some(other)) if id != other => {
+                let other = self.orders.remove(&other).unwrap();
+                self.orders.get_mut(&id).unwrap().merge(other);
+                id
+            }
+            (Some(id), _) | (None, Some(id)) => id,
+            (None, None) => {
+                let id = self.next_id;
+                self.next_id += 1;
+                self.orders.insert(id, TrackedOrder::new());
+                id
+            }
+        };
+        if let Some(oid) = oid {
+            self.by_oid.insert(oid, id);
+        }
+        if let Some(cloid) = cloid {
+            self.by_cloid.insert(cloid, id);
+        }
+
+        let order = self.orders.get_mut(&id).unwrap();
+        let previous = (by_cloid.is_some() || by_oid.is_some()).then(|| order.clone());
+        order.oid = order.oid.or(oid);
+        order.cloid = order.cloid.or(cloid);
+        change(order);
+
+        let unchanged = previous.as_ref().is_some_and(|previous| {
+            previous.state == order.state
+                && previous.filled_sz == order.filled_sz
+                && previous.avg_px == order.avg_px
+                && previous.oid == order.oid
+        });
+        if unchanged {
+            return None;
+        }
+        let event = OrderEvent {
+            previous: previous.map(|previous| previous.state),
+            order: order.clone(),
+        };
+        let _ = self.events.send(event.clone());
+        Some(event)
+    }
+}
+
+/// One fill, from `userFills` or the WebSocket.
+struct Fill<'a> {
+    oid: u64,
+    cloid: Option<Cloid>,
+    coin: &'a str,
+    tid: u64,
+    px: Decimal,
+    sz: Decimal,
+}
+
+fn parse(value: &str) -> Option<Decimal> {
+    Decimal::from_str(value).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::{
+        actions::Tif,
+        clients::exchange::responses::{FilledOrder, RestingOrder},
+    };
+
+    fn bid(cloid: Option<u128>) -> OrderWire {
+        let mut order = OrderWire::limit(1, true, dec!(3000), dec!(2), Tif::Gtc);
+        order.client_order_id = cloid.map(Cloid::from);
+        order
+    }
+
+    fn fill(oid: u64, tid: u64, px: Decimal, sz: Decimal) -> Fill<'static> {
+        Fill {
+            oid,
+            cloid: None,
+            coin: "ETH",
+            tid,
+            px,
+            sz,
+        }
+    }
+
+    fn statuses(statuses: Vec<ExchangeDataStatus>) -> ExchangeDataStatuses {
+        ExchangeDataStatuses { statuses }
+    }
+
+    #[test]
+    fn resting_then_partial_then_filled() {
+        let mut tracker = OrderTracker::new();
+        let mut events = tracker.subscribe();
+        let order = bid(None);
+
+        let changes = tracker.apply_response(
+            std::slice::from_ref(&order),
+            &statuses(vec![ExchangeDataStatus::Resting(RestingOrder { oid: 7 })]),
+        );
+        assert_eq!(changes[0].previous, None);
+        assert_eq!(changes[0].order.state, OrderState::Resting);
+
+        let change = tracker.apply_fill(fill(7, 100, dec!(3000), dec!(0.5)), true);
+        assert_eq!(change.unwrap().previous, Some(OrderState::Resting));
+        // The same fill from another feed is counted once.
+        assert!(tracker
+            .apply_fill(fill(7, 100, dec!(3000), dec!(0.5)), true)
+            .is_none());
+        let tracked = tracker.get_by_oid(7).unwrap();
+        assert_eq!(tracked.state, OrderState::PartiallyFilled);
+        assert_eq!(tracked.remaining_sz(), Some(dec!(1.5)));
+
+        tracker.apply_fill(fill(7, 101, dec!(2990), dec!(1.5)), true);
+        let tracked = tracker.get_by_oid(7).unwrap();
+        assert_eq!(tracked.state, OrderState::Filled);
+        assert_eq!(tracked.filled_sz, dec!(2));
+        assert_eq!(tracked.avg_px, Some(dec!(2992.5)));
+
+        // A late "open" can't reopen it.
+        assert!(tracker
+            .apply_status(7, None, "ETH", "open", Some(dec!(2)), Some(dec!(2)))
+            .is_none());
+
+        let published: Vec<_> = std::iter::from_fn(|| events.try_recv().ok()).collect();
+        assert_eq!(published.len(), 3);
+        assert_eq!(published[2].order.state, OrderState::Filled);
+    }
+
+    #[test]
+    fn fills_before_the_response_are_merged_by_cloid() {
+        let mut tracker = OrderTracker::new();
+        let order = bid(Some(1));
+        let cloid = Cloid::from(1u128);
+        tracker.track(&order);
+        assert_eq!(
+            tracker.get_by_cloid(&cloid).unwrap().state,
+            OrderState::Pending
+        );
+
+        // The fill names only the oid, which the response hasn't revealed yet.
+        tracker.apply_fill(fill(9, 1, dec!(3000), dec!(2)), true);
+        tracker.apply_response(
+            &[order],
+            &statuses(vec![ExchangeDataStatus::Filled(FilledOrder {
+                total_sz: "2".to_string(),
+                avg_px: "3000".to_string(),
+                oid: 9,
+            })]),
+        );
+
+        let tracked = tracker.get_by_cloid(&cloid).unwrap();
+        assert_eq!(tracked, tracker.get_by_oid(9).unwrap());
+        assert_eq!(tracked.state, OrderState::Filled);
+        assert_eq!(tracked.filled_sz, dec!(2));
+        assert_eq!(tracker.open_orders().count(), 0);
+        tracker.remove_closed();
+        assert!(tracker.get_by_oid(9).is_none());
+    }
+
+    #[test]
+    fn rejections_and_exchange_cancels_keep_their_reason() {
+        let mut tracker = OrderTracker::new();
+        let changes = tracker.apply_response(
+            &[bid(Some(2)), bid(None)],
+            &statuses(vec![
+                ExchangeDataStatus::Error("Order must have minimum value of $10.".to_string()),
+                ExchangeDataStatus::Resting(RestingOrder { oid: 3 }),
+            ]),
+        );
+        assert_eq!(changes.len(), 2);
+        let rejected = tracker.get_by_cloid(&Cloid::from(2u128)).unwrap();
+        assert_eq!(rejected.state, OrderState::Rejected);
+        assert_eq!(
+            rejected.reason.as_deref(),
+            Some("Order must have minimum value of $10.")
+        );
+
+        tracker.apply_status(3, None, "ETH", "open", Some(dec!(2)), Some(dec!(1.25)));
+        let partial = tracker.get_by_oid(3).unwrap();
+        assert_eq!(partial.state, OrderState::PartiallyFilled);
+        assert_eq!(partial.filled_sz, dec!(0.75));
+        assert_eq!(partial.avg_px, None);
+
+        tracker.apply_status(
+            3,
+            None,
+            "ETH",
+            "marginCanceled",
+            Some(dec!(2)),
+            Some(dec!(1.25)),
+        );
+        let canceled = tracker.get_by_oid(3).unwrap();
+        assert_eq!(canceled.state, OrderState::Canceled);
+        assert_eq!(canceled.reason.as_deref(), Some("marginCanceled"));
+        assert_eq!(canceled.coin.as_deref(), Some("ETH"));
+    }
+
+    #[test]
+    fn statuses_without_an_oid_or_cloid_are_skipped() {
+        let mut tracker = OrderTracker::new();
+        let changes = tracker.apply_response(
+            &[bid(None), bid(None), bid(None)],
+            &statuses(vec![
+                ExchangeDataStatus::WaitingForFill,
+                ExchangeDataStatus::WaitingForTrigger,
+                ExchangeDataStatus::Error("Insufficient margin to place order.".to_string()),
+            ]),
+        );
+        assert!(changes.is_empty());
+        assert_eq!(tracker.open_orders().count(), 0);
+    }
+
+    #[test]
+    fn triggered_orders_stay_triggered_and_unknown_statuses_are_ignored() {
+        let mut tracker = OrderTracker::new();
+        tracker.apply_response(
+            &[bid(None)],
+            &statuses(vec![ExchangeDataStatus::Resting(RestingOrder { oid: 4 })]),
+        );
+        tracker.apply_status(4, None, "ETH", "triggered", None, None);
+        assert!(tracker
+            .apply_status(4, None, "ETH", "open", Some(dec!(2)), Some(dec!(2)))
+            .is_none());
+        assert_eq!(tracker.get_by_oid(4).unwrap().state, OrderState::Triggered);
+
+        let status = |oid: u64| -> OrderStatusResponse {
+            serde_json::from_value(serde_json::json!({
+                "status": "order",
+                "order": {
+                    "order": {
+                        "coin": "ETH", "side": "B", "limitPx": "3000", "sz": "2", "oid": oid,
+                        "timestamp": 0, "triggerCondition": "N/A", "isTrigger": false,
+                        "triggerPx": "0", "isPositionTpsl": false, "reduceOnly": false,
+                        "orderType": "Limit", "origSz": "2", "tif": "Gtc", "cloid": null,
+                    },
+                    "status": "canceled",
+                    "statusTimestamp": 0,
+                },
+            }))
+            .unwrap()
+        };
+        assert!(tracker.apply_order_status(&status(5)).is_empty());
+        assert!(tracker.get_by_oid(5).is_none());
+        assert_eq!(tracker.apply_order_status(&status(4)).len(), 1);
+        assert_eq!(tracker.get_by_oid(4).unwrap().state, OrderState::Canceled);
+    }
+
+    #[test]
+    fn status_strings_map_to_states() {
+        assert_eq!(
+            OrderState::from_status("triggered"),
+            Some(OrderState::Triggered)
+        );
+        assert_eq!(
+            OrderState::from_status("reduceOnlyCanceled"),
+            Some(OrderState::Canceled)
+        );
+        assert_eq!(
+            OrderState::from_status("tickRejected"),
+            Some(OrderState::Rejected)
+        );
+        assert_eq!(OrderState::from_status("somethingNew"), None);
+    }
+}
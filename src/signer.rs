@@ -0,0 +1,176 @@
+// ============================================================================
+// Signers
+// ============================================================================
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use alloy::{
+    primitives::{Address, B256},
+    signers::local::PrivateKeySigner,
+};
+use alloy_signer::{Signature, SignerSync};
+use async_trait::async_trait;
+
+use crate::Error;
+
+/// Anything that can sign an action's signing hash.
+///
+/// Implemented for every `alloy` [`Signer`](alloy_signer::Signer) (local keys, Ledger, AWS KMS,
+/// ...). Implement it directly for signers that live outside the process, such as a remote
+/// custody service or an enclave, where the key never enters process memory.
+///
+/// L1 actions and user-signed actions are both signed as a prehashed 32-byte digest; see
+/// [`PreparedAction::signing_hash`](crate::PreparedAction::signing_hash).
+#[async_trait]
+pub trait ActionSigner: Send + Sync {
+    /// Address the exchange will recover from the signature.
+    fn address(&self) -> Address;
+
+    /// Sign a 32-byte digest without any further hashing or prefixing.
+    async fn sign_hash(&self, hash: &B256) -> Result<Signature, Error>;
+}
+
+#[async_trait]
+impl<S> ActionSigner for S
+where
+    S: alloy_signer::Signer + Send + Sync + ?Sized,
+{
+    fn address(&self) -> Address {
+        alloy_signer::Signer::address(self)
+    }
+
+    async fn sign_hash(&self, hash: &B256) -> Result<Signature, Error> {
+        alloy_signer::Signer::sign_hash(self, hash)
+            .await
+            .map_err(|e| Error::SignatureFailure(e.to_string()))
+    }
+}
+
+impl fmt::Debug for dyn ActionSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ActionSigner")
+            .field("address", &self.address())
+            .finish()
+    }
+}
+
+/// Deterministic signer for tests.
+///
+/// Signs with a fixed local key, records every hash it is asked to sign, and can be told to
+/// fail so error paths can be exercised. Clones share the recorded hashes.
+///
+/// # Example
+/// ```
+/// use hl_rs::{BaseUrl, ExchangeClient, MockSigner};
+///
+/// let signer = MockSigner::new();
+/// let client = ExchangeClient::new(BaseUrl::Testnet).with_signer(signer.clone());
+///
+/// // ... send actions, then inspect what was signed
+/// assert!(signer.signed_hashes().is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockSigner {
+    wallet: PrivateKeySigner,
+    failure: Option<String>,
+    signed: Arc<Mutex<Vec<B256>>>,
+}
+
+impl MockSigner {
+    /// Private key used by [`MockSigner::new`].
+    pub const DEFAULT_KEY: B256 = B256::repeat_byte(0x01);
+
+    /// Mock signing with [`MockSigner::DEFAULT_KEY`].
+    pub fn new() -> Self {
+        Self::from_wallet(
+            PrivateKeySigner::from_bytes(&Self::DEFAULT_KEY).expect("valid private key"),
+        )
+    }
+
+    /// Mock signing with the given key.
+    pub fn from_wallet(wallet: PrivateKeySigner) -> Self {
+        Self {
+            wallet,
+            failure: None,
+            signed: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Mock that rejects every signing request with [`Error::SignatureFailure`].
+    pub fn failing(message: impl Into<String>) -> Self {
+        Self {
+            failure: Some(message.into()),
+            ..Self::new()
+        }
+    }
+
+    /// Hashes signed so far, in order. Failed requests are recorded too.
+    pub fn signed_hashes(&self) -> Vec<B256> {
+        self.signed.lock().unwrap().clone()
+    }
+}
+
+impl Default for MockSigner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ActionSigner for MockSigner {
+    fn address(&self) -> Address {
+        self.wallet.address()
+    }
+
+    async fn sign_hash(&self, hash: &B256) -> Result<Signature, Error> {
+        self.signed.lock().unwrap().push(*hash);
+        if let Some(message) = &self.failure {
+            return Err(Error::SignatureFailure(message.clone()));
+        }
+        self.wallet
+            .sign_hash_sync(hash)
+            .map_err(|e| Error::SignatureFailure(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn alloy_signers_are_action_signers() {
+        let wallet = PrivateKeySigner::random();
+        let hash = B256::repeat_byte(0xab);
+
+        let signer: Arc<dyn ActionSigner> = Arc::new(wallet.clone());
+        let signature = signer.sign_hash(&hash).await.unwrap();
+
+        assert_eq!(signer.address(), wallet.address());
+        assert_eq!(signature, wallet.sign_hash_sync(&hash).unwrap());
+    }
+
+    #[tokio::test]
+    async fn mock_signer_is_deterministic_and_records_hashes() {
+        let signer = MockSigner::new();
+        let hash = B256::repeat_byte(0x42);
+
+        let signature = signer.sign_hash(&hash).await.unwrap();
+
+        assert_eq!(signature, MockSigner::new().sign_hash(&hash).await.unwrap());
+        assert_eq!(
+            signature.recover_address_from_prehash(&hash).unwrap(),
+            signer.address()
+        );
+        assert_eq!(signer.signed_hashes(), vec![hash]);
+    }
+
+    #[tokio::test]
+    async fn failing_mock_signer_returns_signature_failure() {
+        let signer = MockSigner::failing("hsm offline");
+        let err = signer.sign_hash(&B256::ZERO).await.unwrap_err();
+        assert!(matches!(err, Error::SignatureFailure(message) if message == "hsm offline"));
+    }
+}
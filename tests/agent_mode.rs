@@ -0,0 +1,122 @@
+//! Offline tests for trading through an approved agent (API wallet).
+
+mod support;
+
+use alloy::primitives::{address, Address, Signature, B256};
+use alloy::signers::local::PrivateKeySigner;
+use hl_rs::{
+    ActionKind, ActionSigner, Error, ExchangeClient, MockSigner, NoOp, PreparedAction,
+    SignedActionKind, SigningChain, UsdSend,
+};
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+
+use crate::support::StubServer;
+
+const OK_DEFAULT: &str = r#"{"status":"ok","response":{"type":"default"}}"#;
+
+/// Stub answering every exchange request with `ok` and `userRole` with `role`.
+fn server_with_role(role: Value) -> StubServer {
+    StubServer::start(move |path, body| match (path, body["type"].as_str()) {
+        ("/info", Some("userRole")) => role.to_string(),
+        _ => OK_DEFAULT.to_string(),
+    })
+}
+
+fn recover(body: &Value, hash: B256) -> Address {
+    let signature: Signature = serde_json::from_value(body["signature"].clone()).unwrap();
+    signature.recover_address_from_prehash(&hash).unwrap()
+}
+
+#[tokio::test]
+async fn approve_agent_then_trade_as_agent() {
+    let master = MockSigner::new();
+    let server = server_with_role(json!({"role": "agent", "data": {"user": master.address()}}));
+    let client = ExchangeClient::new(server.base_url());
+
+    let (agent_client, agent) = client.approve_agent(&master, Some("bot")).await.unwrap();
+    assert_eq!(agent_client.agent_master(), Some(master.address()));
+
+    // The approval is signed by the master and names the new agent.
+    let approval = &server.bodies("/exchange")[0];
+    let signed = SignedActionKind::from_json(&approval.to_string()).unwrap();
+    let ActionKind::ApproveAgent(action) = signed.action else {
+        panic!("expected approveAgent, got {:?}", signed.action);
+    };
+    assert_eq!(action.agent_address, agent.address());
+    let hash = PreparedAction::new(action, &SigningChain::Testnet, None, None)
+        .unwrap()
+        .signing_hash();
+    assert_eq!(recover(approval, hash), master.address());
+
+    // The role check asked about the agent.
+    let role_query = &server.bodies("/info")[0];
+    assert_eq!(role_query["user"], json!(agent.address()));
+
+    // L1 actions are signed by the agent.
+    agent_client
+        .send_action(NoOp::invalidate_nonce(42))
+        .await
+        .unwrap();
+    let hash = PreparedAction::new(
+        NoOp::invalidate_nonce(42),
+        &SigningChain::Testnet,
+        None,
+        None,
+    )
+    .unwrap()
+    .signing_hash();
+    assert_eq!(
+        recover(&server.bodies("/exchange")[1], hash),
+        agent.address()
+    );
+}
+
+#[tokio::test]
+async fn agent_refuses_user_signed_actions() {
+    let server = server_with_role(json!({"role": "user"}));
+    let client = ExchangeClient::new(server.base_url())
+        .with_agent(Address::repeat_byte(0x11), PrivateKeySigner::random());
+
+    let err = client
+        .send_action(UsdSend::new(Address::ZERO, dec!(1)))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::UserSignedByAgent("usdSend")));
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn verify_agent_rejects_other_masters() {
+    let other = address!("0x0d1d9635d0640821d15e323ac8adadfa9c111414");
+    let master = Address::repeat_byte(0x11);
+    let server = server_with_role(json!({"role": "agent", "data": {"user": other}}));
+    let client =
+        ExchangeClient::new(server.base_url()).with_agent(master, PrivateKeySigner::random());
+
+    let err = client.verify_agent().await.unwrap_err();
+
+    match err {
+        Error::AgentNotApproved {
+            master: expected,
+            role,
+            ..
+        } => {
+            assert_eq!(expected, master);
+            assert_eq!(role, format!("agent of {other}"));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn verify_agent_requires_agent_mode() {
+    let server = server_with_role(json!({"role": "missing"}));
+    let client = ExchangeClient::new(server.base_url()).with_signer(MockSigner::new());
+
+    assert!(matches!(
+        client.verify_agent().await,
+        Err(Error::AgentNotConfigured)
+    ));
+}
@@ -12,7 +12,7 @@ use alloy::signers::local::PrivateKeySigner;
 use serde::Serialize;
 
 use hl_rs::actions::Action;
-use hl_rs::{BaseUrl, Error, ExchangeClient, ExchangeResponse};
+use hl_rs::{BaseUrl, Error, ExchangeClient};
 
 /// Load the private key from the environment variable `PRIVATE_KEY`.
 pub fn load_private_key() -> PrivateKeySigner {
@@ -31,10 +31,10 @@ pub fn testnet_client() -> ExchangeClient {
     ExchangeClient::new(BaseUrl::Testnet).with_signer(load_private_key())
 }
 
-/// Send an action to the exchange and return the response.
+/// Send an action to the exchange and return its typed response.
 pub async fn send_action<T: Action + Serialize + std::fmt::Debug>(
     action: T,
-) -> Result<ExchangeResponse, Error> {
+) -> Result<T::Response, Error> {
     let client = testnet_client();
     client.send_action(action).await
 }
@@ -47,7 +47,7 @@ pub fn log_action<T: std::fmt::Debug>(name: &str, action: &T) {
 }
 
 /// Helper to print response after receiving.
-pub fn log_response(name: &str, result: &Result<ExchangeResponse, Error>) {
+pub fn log_response<R: std::fmt::Debug>(name: &str, result: &Result<R, Error>) {
     match result {
         Ok(response) => println!("{} response: {:#?}", name, response),
         Err(e) => {
@@ -0,0 +1,178 @@
+//! Offline tests for the typed per-action responses returned by `send_action`.
+
+mod support;
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use alloy::primitives::address;
+use alloy::signers::local::PrivateKeySigner;
+use hl_rs::{
+    nonce::FileNonceSource, BatchCancel, BatchOrder, CancelStatus, CancelWire, CreateSubAccount,
+    CreateVault, Error, ExchangeClient, ExchangeDataStatus, MicroUsd, NoOp, OrderWire,
+    SetOpenInterestCaps, Tif, TwapCancel, TwapOrder,
+};
+use rust_decimal_macros::dec;
+
+use crate::support::StubServer;
+
+fn client_answering(response: &'static str) -> (ExchangeClient, StubServer) {
+    let server = StubServer::start(move |_, _| response.to_string());
+    let client = ExchangeClient::new(server.base_url()).with_signer(PrivateKeySigner::random());
+    (client, server)
+}
+
+#[tokio::test]
+async fn order_returns_per_order_statuses() {
+    let (client, _server) = client_answering(
+        r#"{"status":"ok","response":{"type":"order","data":{"statuses":[
+            {"resting":{"oid":77738308}},
+            {"error":"Order must have minimum value of $10."}
+        ]}}}"#,
+    );
+    let order = OrderWire::limit(1, true, dec!(3000), dec!(0.01), Tif::Gtc);
+    let response = client
+        .send_action(BatchOrder::new(vec![order.clone(), order]))
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        &response.statuses[0],
+        ExchangeDataStatus::Resting(resting) if resting.oid == 77738308
+    ));
+    let errors: Vec<_> = response.errors().collect();
+    assert_eq!(errors, vec![(1, "Order must have minimum value of $10.")]);
+}
+
+#[tokio::test]
+async fn cancel_returns_per_cancel_statuses() {
+    let (client, _server) = client_answering(
+        r#"{"status":"ok","response":{"type":"cancel","data":{"statuses":[
+            "success",
+            {"error":"Order was never placed, already canceled, or filled. asset=1"}
+        ]}}}"#,
+    );
+    let response = client
+        .send_action(BatchCancel::new(vec![
+            CancelWire { a: 1, o: 1 },
+            CancelWire { a: 1, o: 2 },
+        ]))
+        .await
+        .unwrap();
+
+    assert_eq!(response.statuses[0], CancelStatus::Success);
+    assert_eq!(response.errors().count(), 1);
+}
+
+#[tokio::test]
+async fn create_sub_account_returns_address() {
+    let (client, _server) = client_answering(
+        r#"{"status":"ok","response":{"type":"createSubAccount","data":"0x7ae8c2aa4bb5a7b3a1e2b1e0f6c9d7a3e4d5c6b7"}}"#,
+    );
+    let sub_account = client
+        .send_action(CreateSubAccount::new("alpha"))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        sub_account,
+        address!("0x7ae8c2aa4bb5a7b3a1e2b1e0f6c9d7a3e4d5c6b7")
+    );
+}
+
+#[tokio::test]
+async fn create_vault_returns_address() {
+    let (client, _server) = client_answering(
+        r#"{"status":"ok","response":{"type":"createVault","data":"0xdfc24b077bc1425ad1dea75bcb6f8158e10df303"}}"#,
+    );
+    let vault = client
+        .send_action(CreateVault::new("mm", "majors", MicroUsd(100_000_000)))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        vault,
+        address!("0xdfc24b077bc1425ad1dea75bcb6f8158e10df303")
+    );
+}
+
+#[tokio::test]
+async fn twap_order_returns_twap_id() {
+    let (client, _server) = client_answering(
+        r#"{"status":"ok","response":{"type":"twapOrder","data":{"status":{"running":{"twapId":77738308}}}}}"#,
+    );
+    let response = client
+        .send_action(TwapOrder::new(1, true, dec!(2), 30))
+        .await
+        .unwrap();
+
+    assert_eq!(response.twap_id(), Some(77738308));
+    assert!(response.api_error().is_none());
+}
+
+#[tokio::test]
+async fn twap_cancel_reports_rejection() {
+    let (client, _server) = client_answering(
+        r#"{"status":"ok","response":{"type":"twapCancel","data":{"status":{"error":"TWAP was never placed, already canceled, or filled."}}}}"#,
+    );
+    let response = client
+        .send_action(TwapCancel::new(1, 77738308))
+        .await
+        .unwrap();
+
+    assert!(matches!(response.status, CancelStatus::Error(m) if m.contains("never placed")));
+}
+
+#[tokio::test]
+async fn perp_deploy_returns_set_global_messages() {
+    let (client, _server) = client_answering(
+        r#"{"status":"ok","response":{"type":"setGlobal","data":["oi caps updated"]}}"#,
+    );
+    let response = client
+        .send_action(SetOpenInterestCaps::new("km", vec![("BTC", 1_000_000)]))
+        .await
+        .unwrap();
+
+    assert_eq!(response.messages, vec!["oi caps updated".to_string()]);
+}
+
+#[tokio::test]
+async fn untyped_actions_keep_the_raw_response() {
+    let (client, _server) = client_answering(r#"{"status":"ok","response":{"type":"default"}}"#);
+    let response = client.send_action(NoOp::default()).await.unwrap();
+
+    assert_eq!(response.response_type, "default");
+}
+
+#[tokio::test]
+async fn mismatched_response_type_is_an_error() {
+    let (client, _server) = client_answering(r#"{"status":"ok","response":{"type":"default"}}"#);
+    let err = client
+        .send_action(CreateSubAccount::new("alpha"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::GenericParse(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn nonces_outside_the_window_are_refused() {
+    let (client, server) = client_answering(r#"{"status":"ok","response":{"type":"default"}}"#);
+
+    // A source whose counter ran two days ahead of the clock stops handing out nonces.
+    let source = FileNonceSource::open(
+        std::env::temp_dir().join(format!("hl-rs-exchange-{}.nonce", std::process::id())),
+    )
+    .unwrap();
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let ahead = (now + Duration::from_secs(2 * 24 * 60 * 60)).as_millis() as u64;
+    std::fs::write(source.path(), ahead.to_string()).unwrap();
+
+    let client = client.with_nonce_source(source.clone());
+    assert!(matches!(
+        client.send_action(NoOp::default()).await,
+        Err(Error::NonceOutOfWindow { nonce, .. }) if nonce == ahead + 1
+    ));
+    assert!(server.requests().is_empty());
+
+    std::fs::remove_file(source.path()).unwrap();
+}
@@ -0,0 +1,416 @@
+//! Offline tests for the typed [`InfoClient`] methods.
+//!
+//! Each test serves a recorded `/info` response from `tests/fixtures/info/` on a local
+//! socket, points the client at it via [`BaseUrl::Custom`], and checks both the request
+//! body that was sent and the typed response that came back.
+
+mod support;
+
+use std::sync::mpsc;
+
+use alloy::primitives::{address, Address};
+use hl_rs::{
+    info::types::{CandleInterval, DelegatorDelta, VaultRelationship},
+    Cloid, InfoClient, Wei,
+};
+use rust_decimal_macros::dec;
+use serde_json::Value;
+
+use crate::support::StubServer;
+
+const USER: Address = address!("0x5ac99df645f3414876c816caa18b2d234024b487");
+
+fn fixture(name: &str) -> String {
+    support::fixture(&format!("info/{name}"))
+}
+
+/// Serve `body` to every `/info` request and hand back the JSON request bodies.
+fn serve_fixture(body: String) -> (InfoClient, mpsc::Receiver<Value>) {
+    let (tx, rx) = mpsc::channel();
+    let tx = std::sync::Mutex::new(tx);
+    let server = StubServer::start(move |_, request| {
+        tx.lock().unwrap().send(request.clone()).unwrap();
+        body.clone()
+    });
+
+    let client = InfoClient::builder(server.base_url()).build().unwrap();
+    (client, rx)
+}
+
+#[tokio::test]
+async fn user_state_parses_clearinghouse_state() {
+    let (client, request) = serve_fixture(fixture("clearinghouse_state.json"));
+    let state = client.user_state(&USER, None).await.unwrap();
+
+    let request = request.recv().unwrap();
+    assert_eq!(request["type"], "clearinghouseState");
+    assert_eq!(request["user"], USER.to_string().to_lowercase());
+    assert!(request.get("dex").is_none());
+
+    assert_eq!(state.withdrawable, dec!(12945.189942));
+    assert_eq!(state.margin_summary.account_value, dec!(13104.514502));
+    assert_eq!(state.cross_maintenance_margin_used, dec!(79.66228));
+    assert_eq!(state.time, 1712764800000);
+    assert_eq!(state.asset_positions.len(), 1);
+    let position = &state.asset_positions[0].position;
+    assert_eq!(position.coin, "ETH");
+    assert_eq!(position.szi, dec!(0.5));
+    assert_eq!(position.entry_px, Some(dec!(3150.2)));
+    assert_eq!(position.cum_funding.all_time, dec!(-2.418));
+    assert_eq!(position.leverage.value, 10);
+    assert!(position.liquidation_px.is_none());
+}
+
+#[tokio::test]
+async fn user_states_parses_batch() {
+    let (client, request) = serve_fixture(fixture("batch_clearinghouse_states.json"));
+    let states = client.user_states(&[USER, Address::ZERO]).await.unwrap();
+
+    let request = request.recv().unwrap();
+    assert_eq!(request["type"], "batchClearinghouseStates");
+    assert_eq!(request["users"].as_array().unwrap().len(), 2);
+    assert_eq!(states.len(), 2);
+    assert!(states[1].asset_positions.is_empty());
+}
+
+#[tokio::test]
+async fn user_token_balances_parses_spot_state() {
+    let (client, request) = serve_fixture(fixture("spot_clearinghouse_state.json"));
+    let balances = client.user_token_balances(&USER).await.unwrap();
+
+    assert_eq!(request.recv().unwrap()["type"], "spotClearinghouseState");
+    assert_eq!(balances.balances.len(), 2);
+    assert_eq!(balances.balances[1].coin, "PURR");
+    assert_eq!(balances.balances[1].entry_ntl, "1234.56");
+}
+
+#[tokio::test]
+async fn user_fees_parses_fee_schedule() {
+    let (client, request) = serve_fixture(fixture("user_fees.json"));
+    let fees = client.user_fees(&USER).await.unwrap();
+
+    assert_eq!(request.recv().unwrap()["type"], "userFees");
+    assert_eq!(fees.user_cross_rate, "0.00045");
+    assert_eq!(fees.fee_schedule.tiers.vip.len(), 1);
+    assert_eq!(fees.fee_schedule.tiers.mm[0].add, "-0.00001");
+    assert_eq!(fees.daily_user_vlm[0].date, "2024-04-10");
+}
+
+#[tokio::test]
+async fn open_orders_parses_orders() {
+    let (client, request) = serve_fixture(fixture("open_orders.json"));
+    let orders = client.open_orders(&USER).await.unwrap();
+
+    assert_eq!(request.recv().unwrap()["type"], "openOrders");
+    assert_eq!(orders.len(), 2);
+    assert_eq!(orders[0].oid, 91490942);
+    assert!(orders[0].cloid.is_none());
+    assert_eq!(orders[1].cloid, Some(Cloid::from(1u128)));
+}
+
+#[tokio::test]
+async fn order_status_parses_known_order() {
+    let (client, request) = serve_fixture(fixture("order_status.json"));
+    let status = client.order_status(&USER, 91490943).await.unwrap();
+
+    let request = request.recv().unwrap();
+    assert_eq!(request["type"], "orderStatus");
+    assert_eq!(request["oid"], 91490943);
+
+    assert_eq!(status.status, "order");
+    let order = status.order.expect("order info");
+    assert_eq!(order.status, "open");
+    assert_eq!(order.order.orig_sz, "0.25");
+    assert_eq!(order.order.tif.as_deref(), Some("Gtc"));
+}
+
+#[tokio::test]
+async fn order_status_by_cloid_sends_the_cloid_as_oid() {
+    let (client, request) = serve_fixture(fixture("order_status.json"));
+    let cloid = Cloid::from(1u128);
+    let status = client.order_status_by_cloid(&USER, cloid).await.unwrap();
+
+    let request = request.recv().unwrap();
+    assert_eq!(request["type"], "orderStatus");
+    assert_eq!(request["oid"], "0x00000000000000000000000000000001");
+    assert_eq!(status.order.unwrap().order.cloid, Some(cloid));
+}
+
+#[tokio::test]
+async fn order_status_parses_unknown_oid() {
+    let (client, _request) = serve_fixture(fixture("order_status_unknown.json"));
+    let status = client.order_status(&USER, 1).await.unwrap();
+
+    assert_eq!(status.status, "unknownOid");
+    assert!(status.order.is_none());
+}
+
+#[tokio::test]
+async fn meta_and_asset_ctxs_parses_pair() {
+    let (client, request) = serve_fixture(fixture("meta_and_asset_ctxs.json"));
+    let (meta, ctxs) = client.meta_and_asset_ctxs().await.unwrap();
+
+    assert_eq!(request.recv().unwrap()["type"], "metaAndAssetCtxs");
+    assert_eq!(meta.universe.len(), 3);
+    assert_eq!(meta.universe[2].only_isolated, Some(true));
+    assert_eq!(ctxs.len(), 3);
+    assert_eq!(ctxs[0].mark_px, "14.3161");
+    assert!(ctxs[1].mid_px.is_none());
+}
+
+#[tokio::test]
+async fn spot_meta_and_asset_ctxs_parses_pair() {
+    let (client, request) = serve_fixture(fixture("spot_meta_and_asset_ctxs.json"));
+    let (spot_meta, ctxs) = client.spot_meta_and_asset_ctxs().await.unwrap();
+
+    assert_eq!(request.recv().unwrap()["type"], "spotMetaAndAssetCtxs");
+    assert_eq!(spot_meta.tokens.len(), 2);
+    assert_eq!(spot_meta.universe[0].name, "PURR/USDC");
+    assert_eq!(ctxs[0].coin, "PURR/USDC");
+}
+
+#[tokio::test]
+async fn all_mids_parses_map() {
+    let (client, request) = serve_fixture(fixture("all_mids.json"));
+    let mids = client.all_mids().await.unwrap();
+
+    assert_eq!(request.recv().unwrap()["type"], "allMids");
+    assert_eq!(mids.get("ETH").map(String::as_str), Some("3151.25"));
+    assert_eq!(mids.get("@1").map(String::as_str), Some("0.209265"));
+}
+
+#[tokio::test]
+async fn user_fills_parses_fills() {
+    let (client, request) = serve_fixture(fixture("user_fills.json"));
+    let fills = client.user_fills(&USER).await.unwrap();
+
+    assert_eq!(request.recv().unwrap()["type"], "userFills");
+    assert_eq!(fills.len(), 1);
+    assert_eq!(fills[0].dir, "Open Long");
+    assert_eq!(fills[0].tid, 118906512037719);
+    assert!(fills[0].twap_id.is_none());
+}
+
+#[tokio::test]
+async fn funding_history_sends_time_range() {
+    let (client, request) = serve_fixture(fixture("funding_history.json"));
+    let history = client
+        .funding_history("ETH", 1683849600000, Some(1683936000000))
+        .await
+        .unwrap();
+
+    let request = request.recv().unwrap();
+    assert_eq!(request["type"], "fundingHistory");
+    assert_eq!(request["coin"], "ETH");
+    assert_eq!(request["startTime"], 1683849600000u64);
+    assert_eq!(request["endTime"], 1683936000000u64);
+    assert_eq!(history[0].funding_rate, "-0.00022196");
+}
+
+#[tokio::test]
+async fn user_funding_parses_deltas() {
+    let (client, request) = serve_fixture(fixture("user_funding.json"));
+    let funding = client
+        .user_funding(&USER, 1681222254710, None)
+        .await
+        .unwrap();
+
+    let request = request.recv().unwrap();
+    assert_eq!(request["type"], "userFunding");
+    assert_eq!(request["startTime"], 1681222254710u64);
+    assert_eq!(funding[0].delta.type_string, "funding");
+    assert_eq!(funding[0].delta.usdc, "-3.625312");
+}
+
+#[tokio::test]
+async fn l2_book_parses_levels() {
+    let (client, request) = serve_fixture(fixture("l2_book.json"));
+    let book = client.l2_book("BTC").await.unwrap();
+
+    let request = request.recv().unwrap();
+    assert_eq!(request["type"], "l2Book");
+    assert_eq!(request["coin"], "BTC");
+    assert_eq!(book.levels.len(), 2);
+    assert_eq!(book.levels[0][0].px, "113377.0");
+    assert_eq!(book.levels[1][0].n, 3);
+}
+
+#[tokio::test]
+async fn recent_trades_parses_trades() {
+    let (client, request) = serve_fixture(fixture("recent_trades.json"));
+    let trades = client.recent_trades("BTC").await.unwrap();
+
+    assert_eq!(request.recv().unwrap()["type"], "recentTrades");
+    assert_eq!(trades[0].px, "113390.0");
+}
+
+#[tokio::test]
+async fn candle_snapshot_sends_nested_request() {
+    let (client, request) = serve_fixture(fixture("candle_snapshot.json"));
+    let candles = client
+        .candle_snapshot(
+            "BTC",
+            CandleInterval::FifteenMinutes,
+            1681923600000,
+            1681924499999,
+        )
+        .await
+        .unwrap();
+
+    let request = request.recv().unwrap();
+    assert_eq!(request["type"], "candleSnapshot");
+    assert_eq!(request["req"]["coin"], "BTC");
+    assert_eq!(request["req"]["interval"], "15m");
+    assert_eq!(request["req"]["startTime"], 1681923600000u64);
+    assert_eq!(candles[0].num_trades, 189);
+    assert_eq!(candles[0].interval, CandleInterval::FifteenMinutes);
+    assert_eq!(candles[0].close, dec!(29258.0));
+}
+
+#[tokio::test]
+async fn referral_parses_ready_state() {
+    let (client, request) = serve_fixture(fixture("referral.json"));
+    let referral = client.referral(&USER).await.unwrap();
+
+    assert_eq!(request.recv().unwrap()["type"], "referral");
+    assert_eq!(referral.referred_by.unwrap().code, "TESTNET");
+    assert_eq!(referral.referrer_state.stage, "ready");
+    assert_eq!(referral.referrer_state.data.code.as_deref(), Some("TEST"));
+    assert!(referral.referrer_state.data.required.is_none());
+}
+
+#[tokio::test]
+async fn historical_orders_parses_orders() {
+    let (client, request) = serve_fixture(fixture("historical_orders.json"));
+    let orders = client.historical_orders(&USER).await.unwrap();
+
+    assert_eq!(request.recv().unwrap()["type"], "historicalOrders");
+    assert_eq!(orders[0].status, "filled");
+    assert_eq!(orders[0].order.order_type, "Market");
+    assert!(orders[0].order.reduce_only);
+}
+
+#[tokio::test]
+async fn active_asset_data_parses_leverage() {
+    let (client, request) = serve_fixture(fixture("active_asset_data.json"));
+    let data = client.active_asset_data(&USER, "APT").await.unwrap();
+
+    let request = request.recv().unwrap();
+    assert_eq!(request["type"], "activeAssetData");
+    assert_eq!(request["coin"], "APT");
+    assert_eq!(data.leverage.type_string, "cross");
+    assert_eq!(data.max_trade_szs.len(), 2);
+    assert_eq!(data.mark_px, "4.4716");
+}
+
+#[tokio::test]
+async fn vault_details_parses_followers_and_portfolio() {
+    let (client, request) = serve_fixture(fixture("vault_details.json"));
+    let vault = address!("0xdfc24b077bc1425ad1dea75bcb6f8158e10df303");
+    let details = client.vault_details(&vault, Some(&USER)).await.unwrap();
+
+    let request = request.recv().unwrap();
+    assert_eq!(request["type"], "vaultDetails");
+    assert_eq!(request["vaultAddress"], vault.to_string().to_lowercase());
+    assert_eq!(request["user"], USER.to_string().to_lowercase());
+
+    assert_eq!(details.leader, USER);
+    assert_eq!(details.apr, dec!(0.1834));
+    assert_eq!(details.max_distributable, dec!(5305.6));
+    assert_eq!(details.portfolio[0].0, "day");
+    assert_eq!(
+        details.portfolio[0].1.pnl_history[1],
+        (1712786400000, dec!(112.55))
+    );
+    assert_eq!(details.followers[0].user, "Leader");
+    assert!(details.followers[0].lockup_until.is_none());
+    assert_eq!(details.follower_state.unwrap().vault_equity, dec!(1061.28));
+    assert!(matches!(
+        details.relationship,
+        VaultRelationship::Parent { child_addresses } if child_addresses.len() == 1
+    ));
+    assert!(details.allow_deposits && !details.always_close_on_withdraw);
+}
+
+#[tokio::test]
+async fn user_vault_equities_parses_equity() {
+    let (client, request) = serve_fixture(fixture("user_vault_equities.json"));
+    let equities = client.user_vault_equities(&USER).await.unwrap();
+
+    assert_eq!(request.recv().unwrap()["type"], "userVaultEquities");
+    assert_eq!(equities[0].equity, dec!(1061.280413));
+    assert_eq!(equities[0].locked_until_timestamp, Some(1704153600000));
+}
+
+#[tokio::test]
+async fn delegations_parse_amounts() {
+    let (client, request) = serve_fixture(fixture("delegations.json"));
+    let delegations = client.delegations(&USER).await.unwrap();
+
+    let request = request.recv().unwrap();
+    assert_eq!(request["type"], "delegations");
+    assert_eq!(request["user"], USER.to_string().to_lowercase());
+    assert_eq!(delegations.len(), 2);
+    assert_eq!(delegations[0].validator, USER);
+    assert_eq!(delegations[0].amount, dec!(12060.16529862));
+    assert_eq!(delegations[1].locked_until_timestamp, 1735380381353);
+}
+
+#[tokio::test]
+async fn delegator_rewards_parse_sources() {
+    let (client, request) = serve_fixture(fixture("delegator_rewards.json"));
+    let rewards = client.delegator_rewards(&USER).await.unwrap();
+
+    assert_eq!(request.recv().unwrap()["type"], "delegatorRewards");
+    assert_eq!(rewards.len(), 3);
+    assert_eq!(rewards[1].source, "commission");
+    assert_eq!(rewards[1].total_amount, dec!(130.76445876));
+}
+
+#[tokio::test]
+async fn delegator_history_parses_deltas() {
+    let (client, request) = serve_fixture(fixture("delegator_history.json"));
+    let history = client.delegator_history(&USER).await.unwrap();
+
+    assert_eq!(request.recv().unwrap()["type"], "delegatorHistory");
+    assert_eq!(
+        history[0].delta,
+        DelegatorDelta::Delegate {
+            validator: USER,
+            amount: dec!(10000.0),
+            is_undelegate: false,
+        }
+    );
+    assert_eq!(
+        history[1].delta,
+        DelegatorDelta::CDeposit {
+            amount: dec!(10000.0)
+        }
+    );
+    assert!(matches!(
+        &history[2].delta,
+        DelegatorDelta::Withdrawal { amount, phase } if *amount == dec!(25.5) && phase == "initiated"
+    ));
+    // Deltas this crate doesn't model are kept as JSON.
+    assert!(matches!(
+        &history[3].delta,
+        DelegatorDelta::Other(raw) if raw["rewardsClaim"]["amount"] == "1.2"
+    ));
+}
+
+#[tokio::test]
+async fn validator_summaries_parse_stake_and_stats() {
+    let (client, request) = serve_fixture(fixture("validator_summaries.json"));
+    let validators = client.validator_summaries().await.unwrap();
+
+    assert_eq!(request.recv().unwrap()["type"], "validatorSummaries");
+    assert_eq!(validators.len(), 2);
+    assert_eq!(validators[0].stake, Wei(3946328196245370));
+    assert_eq!(validators[0].stake.to_hype(), dec!(39463281.9624537));
+    assert_eq!(validators[0].commission, dec!(0.04));
+    assert_eq!(validators[0].stats[1].0, "week");
+    assert_eq!(validators[0].stats[1].1.n_samples, 10080);
+    assert!(validators[0].unjailable_after.is_none());
+    assert!(validators[1].is_jailed && !validators[1].is_active);
+    assert_eq!(validators[1].unjailable_after, Some(1736726400000));
+}
@@ -0,0 +1,145 @@
+//! Record a session against the [`MockServer`] and replay it from the cassette.
+
+#[path = "../support/mod.rs"]
+mod support;
+
+use std::{collections::HashMap, path::PathBuf};
+
+use alloy::signers::local::PrivateKeySigner;
+use hl_rs::{
+    cassette::{Cassette, CassetteEntry, Recorder, WsDirection, MASK},
+    info::types::InfoRequest,
+    mock::{MockServer, ReplayServer},
+    BatchCancel, BatchOrder, CancelStatus, Error, ExchangeClient, ExchangeDataStatus, InfoClient,
+    OrderWire, SigningChain, Subscription, Tif, WsClient, WsMessage,
+};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::support::mock::ETH;
+
+#[derive(Debug, PartialEq)]
+struct Outcome {
+    oid: u64,
+    cancel: Vec<CancelStatus>,
+    avg_px: Decimal,
+    mids: HashMap<String, String>,
+}
+
+/// Rest, cancel and fill an order, then read mids over the socket.
+async fn session(client: &ExchangeClient, mut ws: WsClient) -> Outcome {
+    let bid = OrderWire::limit(ETH, true, dec!(2900), dec!(1), Tif::Gtc);
+    let mut response = client
+        .send_action(BatchOrder::new(vec![bid]))
+        .await
+        .unwrap();
+    let ExchangeDataStatus::Resting(resting) = response.statuses.remove(0) else {
+        panic!("expected a resting order");
+    };
+    let cancel = client
+        .send_action(BatchCancel::single(ETH, resting.oid))
+        .await
+        .unwrap()
+        .statuses;
+    let fill = client
+        .market_open("ETH", true, dec!(1), dec!(0.01))
+        .await
+        .unwrap();
+
+    ws.subscribe(Subscription::AllMids { dex: None })
+        .await
+        .unwrap();
+    let mids = ws.post_info(InfoRequest::AllMids).await.unwrap();
+    // The subscription snapshot came in while waiting for the post.
+    loop {
+        match ws.next_message().await.unwrap().unwrap() {
+            WsMessage::AllMids { .. } => break,
+            _ => continue,
+        }
+    }
+
+    Outcome {
+        oid: resting.oid,
+        cancel,
+        avg_px: fill.avg_px,
+        mids,
+    }
+}
+
+fn cassette_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("hl-rs-{name}-{}.jsonl", std::process::id()))
+}
+
+#[tokio::test]
+async fn recorded_session_replays() {
+    let path = cassette_path("session");
+    let wallet = PrivateKeySigner::random();
+
+    let recorded = {
+        let server = MockServer::start().await.unwrap();
+        server.fund(wallet.address(), dec!(10000));
+        let recorder = Recorder::create(&path).unwrap();
+        let client = ExchangeClient::new(server.base_url())
+            .with_signer(wallet.clone())
+            .with_recorder(recorder.clone());
+        let ws = WsClient::connect(&server.ws_url())
+            .await
+            .unwrap()
+            .with_recorder(recorder);
+        session(&client, ws).await
+    };
+
+    let cassette = Cassette::load(&path).unwrap();
+    let actions: Vec<_> = cassette
+        .entries
+        .iter()
+        .filter_map(|entry| match entry {
+            CassetteEntry::Http { path, request, .. } if path == "/exchange" => Some(request),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(actions.len(), 3);
+    for request in actions {
+        assert_eq!(request["nonce"], MASK);
+        assert_eq!(request["signature"], MASK);
+    }
+    assert!(cassette.entries.iter().any(|entry| matches!(
+        entry,
+        CassetteEntry::Ws { direction: WsDirection::Recv, frame, .. }
+            if frame == "Websocket connection established."
+    )));
+
+    // Nothing is listening at the recorded URL any more; the replay answers alone.
+    let server = ReplayServer::load(&path).await.unwrap();
+    let client = ExchangeClient::new(server.base_url(SigningChain::Testnet)).with_signer(wallet);
+    let ws = WsClient::connect(&server.ws_url()).await.unwrap();
+    assert_eq!(session(&client, ws).await, recorded);
+    assert_eq!(server.unplayed(), 0);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn unrecorded_requests_are_refused() {
+    let cassette = Cassette::from_jsonl(
+        r#"{"type":"http","atMs":0,"path":"/info","request":{"type":"allMids"},"status":200,"response":"{\"ETH\":\"3000\"}","elapsedMs":4}"#,
+    )
+    .unwrap();
+    let server = ReplayServer::start(cassette).await.unwrap();
+    let info = InfoClient::builder(server.base_url(SigningChain::Mainnet))
+        .build()
+        .unwrap();
+
+    assert_eq!(info.all_mids().await.unwrap()["ETH"], "3000");
+    // Each recording is played once.
+    let err = info.all_mids().await.unwrap_err();
+    assert!(matches!(
+        err,
+        Error::ClientRequest {
+            status_code: 404,
+            ..
+        }
+    ));
+    assert!(info.meta().await.is_err());
+    assert_eq!(server.unplayed(), 0);
+}
@@ -0,0 +1,91 @@
+//! [`DeadManSwitch`] renewing and disarming against the in-process [`MockServer`].
+
+#[path = "../support/mod.rs"]
+mod support;
+
+use std::time::Duration;
+
+use alloy::signers::local::PrivateKeySigner;
+use hl_rs::{
+    mock::MockServer, DeadManMode, DeadManSwitch, DeadManSwitchConfig, Error, ExchangeClient,
+};
+use tokio::time::{sleep, timeout};
+
+use crate::support::{mock::client_with_resting_bid, now_ms};
+
+#[tokio::test]
+async fn renewals_push_the_cancel_time_forward() {
+    let server = MockServer::start().await.unwrap();
+    let (client, _wallet) = client_with_resting_bid(&server).await;
+    let config = DeadManSwitchConfig {
+        timeout: Duration::from_secs(5),
+        renew_interval: Duration::from_millis(50),
+        ..Default::default()
+    };
+
+    let started = now_ms();
+    let switch = DeadManSwitch::start(client, config).await.unwrap();
+    let status = switch.status();
+    assert_eq!(status.mode, DeadManMode::Armed);
+    assert_eq!(status.triggers_today, 0);
+    let first = status.cancel_at.unwrap();
+    assert!(first >= started + 5_000);
+
+    sleep(Duration::from_millis(200)).await;
+    let status = switch.status();
+    assert!(status.cancel_at.unwrap() > first);
+    assert!(status.last_error.is_none());
+}
+
+#[tokio::test]
+async fn shutdown_cancels_open_orders() {
+    let server = MockServer::start().await.unwrap();
+    let (client, wallet) = client_with_resting_bid(&server).await;
+
+    let switch = DeadManSwitch::start(client.clone(), DeadManSwitchConfig::default())
+        .await
+        .unwrap();
+    switch.shutdown().await.unwrap();
+
+    let open = client.info().open_orders(&wallet.address()).await.unwrap();
+    assert!(open.is_empty());
+}
+
+#[tokio::test]
+async fn dropping_cancels_open_orders() {
+    let server = MockServer::start().await.unwrap();
+    let (client, wallet) = client_with_resting_bid(&server).await;
+
+    let switch = DeadManSwitch::start(client.clone(), DeadManSwitchConfig::default())
+        .await
+        .unwrap();
+    drop(switch);
+
+    timeout(Duration::from_secs(5), async {
+        while !client
+            .info()
+            .open_orders(&wallet.address())
+            .await
+            .unwrap()
+            .is_empty()
+        {
+            sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("orders should be canceled after the switch is dropped");
+}
+
+#[tokio::test]
+async fn invalid_timing_is_refused() {
+    let server = MockServer::start().await.unwrap();
+    let client = ExchangeClient::new(server.base_url()).with_signer(PrivateKeySigner::random());
+    let config = DeadManSwitchConfig {
+        timeout: Duration::from_secs(10),
+        renew_interval: Duration::from_secs(10),
+        ..Default::default()
+    };
+
+    let err = DeadManSwitch::start(client, config).await.unwrap_err();
+    assert!(matches!(err, Error::DeadManSwitch(_)), "{err:?}");
+}
@@ -0,0 +1,285 @@
+//! Order, position and WebSocket flows against the in-process [`MockServer`].
+
+#[path = "../support/mod.rs"]
+mod support;
+
+use std::time::Duration;
+
+use alloy::signers::local::PrivateKeySigner;
+use hl_rs::{
+    info::types::{InfoRequest, UserRoleResponse},
+    mock::MockServer,
+    ApiError, BatchCancel, BatchOrder, CancelStatus, Error, ExchangeClient, ExchangeDataStatus,
+    NoOp, OrderWire, Subscription, Tif, TpSl, WsClient, WsMessage,
+};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use tokio::time::timeout;
+
+use crate::support::mock::{funded_client, ETH};
+
+async fn place(client: &ExchangeClient, order: OrderWire) -> ExchangeDataStatus {
+    let mut response = client
+        .send_action(BatchOrder::new(vec![order]))
+        .await
+        .unwrap();
+    response.statuses.remove(0)
+}
+
+async fn rejection(client: &ExchangeClient, order: OrderWire) -> ApiError {
+    place(client, order)
+        .await
+        .api_error()
+        .expect("order should be rejected")
+}
+
+async fn eth_position(client: &ExchangeClient, wallet: &PrivateKeySigner) -> Decimal {
+    let state = client
+        .info()
+        .user_state(&wallet.address(), None)
+        .await
+        .unwrap();
+    state
+        .asset_positions
+        .iter()
+        .find(|p| p.position.coin == "ETH")
+        .map_or(Decimal::ZERO, |p| p.position.szi)
+}
+
+#[tokio::test]
+async fn orders_rest_fill_and_cancel() {
+    let server = MockServer::start().await.unwrap();
+    let (client, wallet) = funded_client(&server);
+
+    let bid = OrderWire::limit(ETH, true, dec!(2900), dec!(1), Tif::Gtc);
+    let ExchangeDataStatus::Resting(resting) = place(&client, bid).await else {
+        panic!("expected a resting order");
+    };
+    let open = client.info().open_orders(&wallet.address()).await.unwrap();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].oid, resting.oid);
+    assert_eq!(open[0].limit_px, "2900");
+
+    let cancel = BatchCancel::single(ETH, resting.oid);
+    let statuses = client.send_action(cancel.clone()).await.unwrap().statuses;
+    assert_eq!(statuses, vec![CancelStatus::Success]);
+    let statuses = client.send_action(cancel).await.unwrap().statuses;
+    assert!(matches!(&statuses[0], CancelStatus::Error(m) if m.contains("never placed")));
+
+    let fill = client
+        .market_open("ETH", true, dec!(1), dec!(0.01))
+        .await
+        .unwrap();
+    assert_eq!(fill.avg_px, dec!(3000));
+    assert_eq!(eth_position(&client, &wallet).await, dec!(1));
+
+    server.set_mark_price("ETH", dec!(3100)).unwrap();
+    let state = client
+        .info()
+        .user_state(&wallet.address(), None)
+        .await
+        .unwrap();
+    assert_eq!(state.asset_positions[0].position.unrealized_pnl, dec!(100));
+
+    let close = client.market_close("ETH", None, dec!(0.01)).await.unwrap();
+    assert_eq!(close.avg_px, dec!(3100));
+    assert_eq!(eth_position(&client, &wallet).await, Decimal::ZERO);
+
+    // 100 profit, minus taker fees on 3000 and 3100 of notional.
+    let state = client
+        .info()
+        .user_state(&wallet.address(), None)
+        .await
+        .unwrap();
+    assert_eq!(state.margin_summary.account_value, dec!(10097.255));
+    let fills = client.info().user_fills(&wallet.address()).await.unwrap();
+    assert_eq!(fills[0].dir, "Close Long");
+    assert_eq!(fills[0].closed_pnl, "100");
+}
+
+#[tokio::test]
+async fn rejections_match_the_exchange() {
+    let server = MockServer::start().await.unwrap();
+    let (client, _wallet) = funded_client(&server);
+
+    let order = |px, sz, tif| OrderWire::limit(ETH, true, px, sz, tif);
+    assert!(matches!(
+        rejection(&client, order(dec!(2900), dec!(1), Tif::Ioc)).await,
+        ApiError::IocNoMatch {
+            asset: Some(ETH),
+            ..
+        }
+    ));
+    assert!(matches!(
+        rejection(&client, order(dec!(3100), dec!(1), Tif::Alo)).await,
+        ApiError::PostOnlyWouldCross { best_bid: Some(bid), .. } if bid == dec!(3000)
+    ));
+    assert!(matches!(
+        rejection(&client, order(dec!(2900), dec!(0.001), Tif::Gtc)).await,
+        ApiError::MinNotional { .. }
+    ));
+    assert!(matches!(
+        rejection(&client, order(dec!(2900.123), dec!(1), Tif::Gtc)).await,
+        ApiError::InvalidTickSize { .. }
+    ));
+    // 100 ETH at 20x needs 15000 of margin.
+    assert!(matches!(
+        rejection(&client, order(dec!(3000), dec!(100), Tif::Ioc)).await,
+        ApiError::InsufficientMargin { .. }
+    ));
+    assert!(matches!(
+        rejection(
+            &client,
+            order(dec!(3100), dec!(1), Tif::Ioc).with_reduce_only(true)
+        )
+        .await,
+        ApiError::ReduceOnlyRejected { .. }
+    ));
+}
+
+#[tokio::test]
+async fn actions_need_a_known_signer_and_fresh_nonce() {
+    let server = MockServer::start().await.unwrap();
+
+    let stranger = PrivateKeySigner::random();
+    let client = ExchangeClient::new(server.base_url()).with_signer(stranger.clone());
+    let err = client.send_action(NoOp::default()).await.unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Api(ApiError::WalletNotFound { address: Some(address), .. })
+            if address == stranger.address()
+    ));
+
+    let (client, _wallet) = funded_client(&server);
+    let nonce = chrono::Utc::now().timestamp_millis() as u64;
+    client
+        .send_action(NoOp::invalidate_nonce(nonce))
+        .await
+        .unwrap();
+    let err = client
+        .send_action(NoOp::invalidate_nonce(nonce))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Api(ApiError::InvalidNonce { .. })));
+
+    // Nonces at the edge of u64 are refused without taking the server down.
+    let err = client
+        .send_action(NoOp::invalidate_nonce(u64::MAX))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Api(ApiError::InvalidNonce { .. })));
+    client.send_action(NoOp::default()).await.unwrap();
+}
+
+#[tokio::test]
+async fn agent_trades_for_master() {
+    let server = MockServer::start().await.unwrap();
+    let (client, master) = funded_client(&server);
+
+    let (agent_client, agent) = client.approve_agent(&master, Some("bot")).await.unwrap();
+    let role = client.info().user_role(&agent.address()).await.unwrap();
+    assert!(matches!(role, UserRoleResponse::Agent(data) if data.user == master.address()));
+
+    agent_client
+        .market_open("ETH", false, dec!(0.5), dec!(0.01))
+        .await
+        .unwrap();
+    assert_eq!(eth_position(&client, &master).await, dec!(-0.5));
+}
+
+#[tokio::test]
+async fn mark_moves_fill_resting_and_trigger_orders() {
+    let server = MockServer::start().await.unwrap();
+    let (client, wallet) = funded_client(&server);
+
+    let bid = OrderWire::limit(ETH, true, dec!(2900), dec!(1), Tif::Gtc);
+    place(&client, bid).await;
+    let stop = OrderWire::trigger(ETH, false, dec!(2700), dec!(1), dec!(2800), true, TpSl::Sl)
+        .with_reduce_only(true);
+    place(
+        &client,
+        OrderWire::limit(ETH, true, dec!(3000), dec!(1), Tif::Ioc),
+    )
+    .await;
+    assert!(matches!(
+        place(&client, stop).await,
+        ExchangeDataStatus::Resting(_)
+    ));
+
+    // Fills the bid at its limit as maker: long 2.
+    server.set_mark_price("ETH", dec!(2850)).unwrap();
+    assert_eq!(eth_position(&client, &wallet).await, dec!(2));
+
+    // Fires the stop, which sells 1 at the mark.
+    server.set_mark_price("ETH", dec!(2790)).unwrap();
+    assert_eq!(eth_position(&client, &wallet).await, dec!(1));
+    let fills = client.info().user_fills(&wallet.address()).await.unwrap();
+    assert_eq!(fills[0].px, "2790");
+    assert!(client
+        .info()
+        .open_orders(&wallet.address())
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn websocket_posts_and_pushes() {
+    let server = MockServer::start().await.unwrap();
+    let (client, wallet) = funded_client(&server);
+    let user = format!("{:#x}", wallet.address());
+
+    let mut ws = WsClient::connect(&server.ws_url()).await.unwrap();
+    ws.subscribe(Subscription::OrderUpdates { user: user.clone() })
+        .await
+        .unwrap();
+    ws.subscribe(Subscription::UserFills {
+        user,
+        aggregate_by_time: None,
+    })
+    .await
+    .unwrap();
+
+    let mids: std::collections::HashMap<String, String> =
+        ws.post_info(InfoRequest::AllMids).await.unwrap();
+    assert_eq!(mids["ETH"], "3000");
+
+    let signed = client
+        .sign_action(
+            BatchOrder::new(vec![OrderWire::limit(
+                ETH,
+                true,
+                dec!(2900),
+                dec!(1),
+                Tif::Gtc,
+            )]),
+            &wallet,
+        )
+        .await
+        .unwrap();
+    let response = ws.post_action(signed).await.unwrap();
+    assert!(matches!(
+        response.statuses[0],
+        ExchangeDataStatus::Resting(_)
+    ));
+    server.set_mark_price("ETH", dec!(2890)).unwrap();
+
+    let mut statuses = Vec::new();
+    let mut filled_at = None;
+    while filled_at.is_none() || statuses.len() < 2 {
+        let message = timeout(Duration::from_secs(5), ws.next_message())
+            .await
+            .expect("timed out waiting for pushes")
+            .unwrap()
+            .unwrap();
+        match message {
+            WsMessage::OrderUpdates { data, .. } => statuses.push(data[0].status.clone()),
+            WsMessage::UserFills { data, .. } if !data.fills.is_empty() => {
+                filled_at = Some(data.fills[0].px)
+            }
+            _ => {}
+        }
+    }
+    assert_eq!(statuses, ["open", "filled"]);
+    assert_eq!(filled_at.unwrap(), dec!(2900));
+}
@@ -0,0 +1,162 @@
+//! [`OrderTracker`] following orders through responses, pushes and `orderStatus` of the
+//! in-process [`MockServer`].
+
+#[path = "../support/mod.rs"]
+mod support;
+
+use std::time::Duration;
+
+use hl_rs::{
+    mock::MockServer,
+    order_tracker::{OrderState, OrderTracker},
+    BatchCancel, BatchOrder, CancelByCloid, CancelStatus, Cloid, ExchangeClient, OrderWire,
+    Subscription, Tif, WsClient,
+};
+use rust_decimal_macros::dec;
+use tokio::time::timeout;
+
+use crate::support::mock::{funded_client, ETH};
+
+/// Place `order` and feed the response to `tracker`.
+async fn place(client: &ExchangeClient, tracker: &mut OrderTracker, order: OrderWire) -> u64 {
+    tracker.track(&order);
+    let response = client
+        .send_action(BatchOrder::new(vec![order.clone()]))
+        .await
+        .unwrap();
+    let events = tracker.apply_response(&[order], &response);
+    events[0].order.oid.expect("order should be on the book")
+}
+
+#[tokio::test]
+async fn pushes_carry_an_order_to_filled() {
+    let server = MockServer::start().await.unwrap();
+    let (client, wallet) = funded_client(&server);
+    let user = format!("{:#x}", wallet.address());
+
+    let mut ws = WsClient::connect(&server.ws_url()).await.unwrap();
+    ws.subscribe(Subscription::OrderUpdates { user: user.clone() })
+        .await
+        .unwrap();
+    ws.subscribe(Subscription::UserFills {
+        user,
+        aggregate_by_time: None,
+    })
+    .await
+    .unwrap();
+
+    let mut tracker = OrderTracker::new();
+    let mut events = tracker.subscribe();
+    let cloid = Cloid::from_strategy("order-tracker-test", 1);
+    let bid =
+        OrderWire::limit(ETH, true, dec!(2900), dec!(1), Tif::Gtc).with_client_order_id(cloid);
+    let oid = place(&client, &mut tracker, bid).await;
+    assert_eq!(tracker.get_by_oid(oid).unwrap().state, OrderState::Resting);
+
+    server.set_mark_price("ETH", dec!(2890)).unwrap();
+    while tracker.get_by_oid(oid).unwrap().state != OrderState::Filled
+        || tracker.get_by_oid(oid).unwrap().avg_px.is_none()
+    {
+        let message = timeout(Duration::from_secs(5), ws.next_message())
+            .await
+            .expect("timed out waiting for pushes")
+            .unwrap()
+            .unwrap();
+        tracker.apply_ws_message(&message);
+    }
+
+    let order = tracker.get_by_cloid(&cloid).unwrap();
+    assert_eq!(order.oid, Some(oid));
+    assert_eq!(order.coin.as_deref(), Some("ETH"));
+    assert_eq!(order.filled_sz, dec!(1));
+    assert_eq!(order.avg_px, Some(dec!(2900)));
+
+    let states: Vec<_> = std::iter::from_fn(|| events.try_recv().ok())
+        .map(|event| event.order.state)
+        .collect();
+    assert_eq!(states.first(), Some(&OrderState::Pending));
+    assert_eq!(states.last(), Some(&OrderState::Filled));
+}
+
+#[tokio::test]
+async fn reconcile_catches_up_with_missed_updates() {
+    let server = MockServer::start().await.unwrap();
+    let (client, wallet) = funded_client(&server);
+    let mut tracker = OrderTracker::new();
+
+    let filled = place(
+        &client,
+        &mut tracker,
+        OrderWire::limit(ETH, true, dec!(2900), dec!(1), Tif::Gtc),
+    )
+    .await;
+    let canceled = place(
+        &client,
+        &mut tracker,
+        OrderWire::limit(ETH, true, dec!(2800), dec!(2), Tif::Gtc),
+    )
+    .await;
+    assert_eq!(tracker.open_orders().count(), 2);
+
+    // Neither change reaches the tracker until it polls.
+    client
+        .send_action(BatchCancel::single(ETH, canceled))
+        .await
+        .unwrap();
+    server.set_mark_price("ETH", dec!(2850)).unwrap();
+
+    let events = tracker
+        .reconcile(client.info(), &wallet.address())
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(!tracker.is_stale());
+
+    let order = tracker.get_by_oid(filled).unwrap();
+    assert_eq!(order.state, OrderState::Filled);
+    assert_eq!(order.filled_sz, dec!(1));
+    let order = tracker.get_by_oid(canceled).unwrap();
+    assert_eq!(order.state, OrderState::Canceled);
+    assert_eq!(order.reason.as_deref(), Some("canceled"));
+    assert_eq!(order.filled_sz, dec!(0));
+    assert_eq!(tracker.open_orders().count(), 0);
+
+    // Nothing left to poll.
+    let events = tracker
+        .reconcile(client.info(), &wallet.address())
+        .await
+        .unwrap();
+    assert!(events.is_empty());
+}
+
+#[tokio::test]
+async fn orders_whose_response_was_lost_are_found_by_cloid() {
+    let server = MockServer::start().await.unwrap();
+    let (client, wallet) = funded_client(&server);
+    let mut tracker = OrderTracker::new();
+
+    let cloid = Cloid::random();
+    let bid =
+        OrderWire::limit(ETH, true, dec!(2800), dec!(1), Tif::Gtc).with_client_order_id(cloid);
+    tracker.track(&bid);
+    // The order lands but its response never reaches the tracker.
+    client
+        .send_action(BatchOrder::new(vec![bid]))
+        .await
+        .unwrap();
+    let statuses = client
+        .send_action(CancelByCloid::single(ETH, cloid))
+        .await
+        .unwrap()
+        .statuses;
+    assert_eq!(statuses, vec![CancelStatus::Success]);
+
+    tracker
+        .reconcile(client.info(), &wallet.address())
+        .await
+        .unwrap();
+    let order = tracker.get_by_cloid(&cloid).unwrap();
+    assert_eq!(order.state, OrderState::Canceled);
+    assert!(order.oid.is_some());
+    assert_eq!(tracker.get_by_oid(order.oid.unwrap()), Some(order));
+}
@@ -0,0 +1,127 @@
+//! Info and action posts over the socket, against a local WebSocket server.
+
+mod ws_server;
+
+use std::time::Duration;
+
+use hl_rs::{
+    info::types::{InfoRequest, L2SnapshotResponse},
+    BaseUrl, Error, ExchangeClient, ExchangeDataStatus, ExchangeResponse, ManagedWsClient,
+    MockSigner, NoOp, WsClient, WsMessage,
+};
+use serde_json::{json, Value};
+
+use crate::ws_server::WsTestServer;
+
+fn info_response(id: &Value) -> Value {
+    json!({
+        "channel": "post",
+        "data": {"id": id, "response": {"type": "info", "payload": {"type": "l2Book", "data": {
+            "coin": "ETH",
+            "time": 1,
+            "levels": [[{"px": "100", "sz": "1", "n": 1}], []],
+        }}}},
+    })
+}
+
+fn action_response(id: &Value, oid: u64) -> Value {
+    json!({
+        "channel": "post",
+        "data": {"id": id, "response": {"type": "action", "payload": {
+            "status": "ok",
+            "response": {"type": "order", "data": {"statuses": [{"resting": {"oid": oid}}]}},
+        }}},
+    })
+}
+
+fn l2_book_request() -> InfoRequest {
+    InfoRequest::L2Book {
+        coin: "ETH".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn post_info_buffers_subscription_messages() {
+    let server = WsTestServer::start().await;
+    let (client, mut conn) = tokio::join!(WsClient::connect(&server.url), server.accept());
+    let mut client = client.unwrap();
+
+    let (book, request) = tokio::join!(
+        client.post_info::<L2SnapshotResponse>(l2_book_request()),
+        async {
+            let request = conn.request().await;
+            conn.send(json!({"channel": "allMids", "data": {"mids": {"ETH": "100"}}}))
+                .await;
+            conn.send(info_response(&request["id"])).await;
+            request
+        }
+    );
+
+    assert_eq!(book.unwrap().coin, "ETH");
+    assert_eq!(request["method"], "post");
+    assert_eq!(
+        request["request"],
+        json!({"type": "info", "payload": {"type": "l2Book", "coin": "ETH"}})
+    );
+    // The subscription message that arrived first is still delivered.
+    assert!(matches!(
+        client.next_message().await,
+        Some(Ok(WsMessage::AllMids { .. }))
+    ));
+}
+
+#[tokio::test]
+async fn post_times_out_without_response() {
+    let server = WsTestServer::start().await;
+    let (client, _conn) = tokio::join!(WsClient::connect(&server.url), server.accept());
+    let mut client = client.unwrap().with_post_timeout(Duration::from_millis(50));
+
+    let err = client
+        .post_info::<L2SnapshotResponse>(l2_book_request())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::WsPostTimeout { id: 1 }));
+}
+
+#[tokio::test]
+async fn concurrent_action_posts_are_matched_by_id() {
+    let server = WsTestServer::start().await;
+    let (client, mut conn) = tokio::join!(ManagedWsClient::connect(&server.url), server.accept());
+    let client = client.unwrap();
+    let signer = MockSigner::new();
+    let exchange = ExchangeClient::new(BaseUrl::Testnet);
+    let first = exchange
+        .sign_action(NoOp::invalidate_nonce(1), &signer)
+        .await
+        .unwrap();
+    let second = exchange
+        .sign_action(NoOp::invalidate_nonce(2), &signer)
+        .await
+        .unwrap();
+
+    let (first, second, ()) = tokio::join!(
+        client.post_action(first),
+        client.post_action(second),
+        async {
+            let a = conn.request().await;
+            let b = conn.request().await;
+            assert_eq!(a["request"]["type"], "action");
+            // Answer out of order; each response carries the action's nonce as its oid.
+            for request in [b, a] {
+                let nonce = request["request"]["payload"]["nonce"].as_u64().unwrap();
+                conn.send(action_response(&request["id"], nonce)).await;
+            }
+        }
+    );
+
+    assert_eq!(resting_oid(first.unwrap()), 1);
+    assert_eq!(resting_oid(second.unwrap()), 2);
+}
+
+fn resting_oid(response: ExchangeResponse) -> u64 {
+    match &response.order_data().unwrap().statuses[0] {
+        ExchangeDataStatus::Resting(resting) => resting.oid,
+        other => panic!("unexpected status: {other:?}"),
+    }
+}
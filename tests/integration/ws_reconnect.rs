@@ -0,0 +1,194 @@
+//! [`ManagedWsClient`] against a local WebSocket server that drops connections on purpose.
+
+mod ws_server;
+
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use hl_rs::{
+    responses::WsBook, ManagedWsClient, ReconnectConfig, Subscription, SubscriptionStream, WsEvent,
+    WsMessage,
+};
+use serde_json::{json, Value};
+use tokio::time::timeout;
+
+use crate::ws_server::WsTestServer;
+
+fn config() -> ReconnectConfig {
+    ReconnectConfig {
+        ping_interval: Duration::from_secs(60),
+        pong_timeout: Duration::from_secs(60),
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_millis(50),
+    }
+}
+
+fn eth_book() -> Subscription {
+    Subscription::L2Book {
+        coin: "ETH".to_string(),
+        n_sig_figs: None,
+        mantissa: None,
+    }
+}
+
+fn book_message(coin: &str) -> Value {
+    json!({
+        "channel": "l2Book",
+        "data": {"coin": coin, "time": 1, "levels": [[], []]},
+    })
+}
+
+async fn next_event(client: &mut ManagedWsClient) -> WsEvent {
+    timeout(Duration::from_secs(5), client.next_event())
+        .await
+        .expect("timed out waiting for event")
+        .expect("client stopped")
+}
+
+async fn next_book(books: &mut SubscriptionStream<WsBook>) -> WsBook {
+    timeout(Duration::from_secs(5), books.next())
+        .await
+        .expect("timed out waiting for book")
+        .expect("stream ended")
+}
+
+#[tokio::test]
+async fn reconnect_replays_subscriptions_and_flags_resync() {
+    let server = WsTestServer::start().await;
+    let url = server.url.clone();
+    let btc_trades = Subscription::Trades {
+        coin: "BTC".to_string(),
+    };
+
+    let server = tokio::spawn(async move {
+        let mut conn = server.accept().await;
+        assert_eq!(conn.request().await["subscription"]["coin"], "ETH");
+        assert_eq!(conn.request().await["subscription"]["coin"], "BTC");
+        let unsubscribe = conn.request().await;
+        assert_eq!(unsubscribe["method"], "unsubscribe");
+        assert_eq!(unsubscribe["subscription"]["type"], "trades");
+        conn.send(book_message("ETH")).await;
+        drop(conn);
+
+        // Only the remaining subscription is replayed.
+        let mut conn = server.accept().await;
+        let replayed = conn.request().await;
+        assert_eq!(replayed["method"], "subscribe");
+        assert_eq!(replayed["subscription"]["type"], "l2Book");
+        conn.send(book_message("ETH")).await;
+        conn.send(book_message("ETH")).await;
+        conn
+    });
+
+    let mut client = ManagedWsClient::connect_with_config(&url, config())
+        .await
+        .unwrap();
+    client.subscribe(eth_book()).unwrap();
+    assert!(matches!(
+        next_event(&mut client).await,
+        WsEvent::Connected { reconnect: false }
+    ));
+    client.subscribe(btc_trades.clone()).unwrap();
+    client.unsubscribe(btc_trades).unwrap();
+    assert_eq!(client.subscriptions(), vec![eth_book()]);
+
+    assert!(matches!(
+        next_event(&mut client).await,
+        WsEvent::Message {
+            message: WsMessage::L2Book { .. },
+            resync: false
+        }
+    ));
+    assert!(matches!(
+        next_event(&mut client).await,
+        WsEvent::Disconnected { .. }
+    ));
+    assert!(matches!(
+        next_event(&mut client).await,
+        WsEvent::Connected { reconnect: true }
+    ));
+    assert!(matches!(
+        next_event(&mut client).await,
+        WsEvent::Message { resync: true, .. }
+    ));
+    assert!(matches!(
+        next_event(&mut client).await,
+        WsEvent::Message { resync: false, .. }
+    ));
+
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn typed_streams_flag_the_snapshot_after_a_reconnect() {
+    let server = WsTestServer::start().await;
+    let url = server.url.clone();
+
+    let server = tokio::spawn(async move {
+        let mut conn = server.accept().await;
+        assert_eq!(conn.request().await["method"], "subscribe");
+        conn.send(book_message("ETH")).await;
+        drop(conn);
+
+        let mut conn = server.accept().await;
+        assert_eq!(conn.request().await["subscription"]["coin"], "ETH");
+        conn.send(book_message("ETH")).await;
+        conn.send(book_message("ETH")).await;
+        conn
+    });
+
+    let client = ManagedWsClient::connect_with_config(&url, config())
+        .await
+        .unwrap();
+    let mut books = client.subscribe_typed::<WsBook>(eth_book()).unwrap();
+    next_book(&mut books).await;
+    assert!(!books.resynced());
+    next_book(&mut books).await;
+    assert!(books.resynced());
+    next_book(&mut books).await;
+    assert!(!books.resynced());
+
+    let _conn = server.await.unwrap();
+}
+
+#[tokio::test]
+async fn missed_pong_triggers_reconnect() {
+    let server = WsTestServer::start().await;
+    let url = server.url.clone();
+
+    let server = tokio::spawn(async move {
+        // Swallow everything, including pings, so the connection looks dead.
+        let mut conn = server.accept().await;
+        assert_eq!(conn.request().await["method"], "subscribe");
+        assert_eq!(conn.request().await["method"], "ping");
+
+        let mut conn2 = server.accept().await;
+        assert_eq!(conn2.request().await["subscription"]["coin"], "ETH");
+        (conn, conn2)
+    });
+
+    let config = ReconnectConfig {
+        ping_interval: Duration::from_millis(50),
+        pong_timeout: Duration::from_millis(50),
+        ..config()
+    };
+    let mut client = ManagedWsClient::connect_with_config(&url, config)
+        .await
+        .unwrap();
+    client.subscribe(eth_book()).unwrap();
+
+    assert!(matches!(
+        next_event(&mut client).await,
+        WsEvent::Connected { reconnect: false }
+    ));
+    match next_event(&mut client).await {
+        WsEvent::Disconnected { error } => assert!(error.to_string().contains("no pong")),
+        other => panic!("expected disconnect, got {other:?}"),
+    }
+    assert!(matches!(
+        next_event(&mut client).await,
+        WsEvent::Connected { reconnect: true }
+    ));
+
+    let _connections = server.await.unwrap();
+}
@@ -0,0 +1,56 @@
+//! Local WebSocket server for exercising the WS clients without network access.
+
+#![allow(dead_code)]
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{accept_async, tungstenite::Message, WebSocketStream};
+
+/// Listener handing out one [`ServerConnection`] per accepted client connection.
+pub struct WsTestServer {
+    pub url: String,
+    listener: TcpListener,
+}
+
+impl WsTestServer {
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        Self { url, listener }
+    }
+
+    /// Accept the next connection and send the greeting the API sends.
+    pub async fn accept(&self) -> ServerConnection {
+        let (stream, _) = self.listener.accept().await.unwrap();
+        let mut ws = accept_async(stream).await.unwrap();
+        ws.send(Message::Text(
+            "Websocket connection established.".to_string(),
+        ))
+        .await
+        .unwrap();
+        ServerConnection { ws }
+    }
+}
+
+pub struct ServerConnection {
+    ws: WebSocketStream<TcpStream>,
+}
+
+impl ServerConnection {
+    /// Next JSON request sent by the client.
+    pub async fn request(&mut self) -> Value {
+        loop {
+            if let Message::Text(text) = self.ws.next().await.unwrap().unwrap() {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    pub async fn send(&mut self, frame: Value) {
+        self.ws
+            .send(Message::Text(frame.to_string()))
+            .await
+            .unwrap();
+    }
+}
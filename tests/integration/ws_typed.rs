@@ -0,0 +1,281 @@
+//! Typed subscription streams on [`ManagedWsClient`], against a local WebSocket server.
+
+mod ws_server;
+
+use std::time::Duration;
+
+use futures_util::{FutureExt, StreamExt};
+use hl_rs::{
+    responses::{WsBbo, WsBook, WsClearinghouseState},
+    Error, ManagedWsClient, ReconnectConfig, Subscription, SubscriptionStream, WsEvent, WsMessage,
+};
+use serde_json::{json, Value};
+use tokio::time::timeout;
+
+use crate::ws_server::{ServerConnection, WsTestServer};
+
+fn book(coin: &str) -> Subscription {
+    Subscription::L2Book {
+        coin: coin.to_string(),
+        n_sig_figs: None,
+        mantissa: None,
+    }
+}
+
+fn book_message(coin: &str, time: u64) -> Value {
+    json!({
+        "channel": "l2Book",
+        "data": {"coin": coin, "time": time, "levels": [[], []]},
+    })
+}
+
+fn all_mids_message() -> Value {
+    json!({"channel": "allMids", "data": {"mids": {"ETH": "100"}}})
+}
+
+async fn connect(server: &WsTestServer, buffer: usize) -> (ManagedWsClient, ServerConnection) {
+    let config = ReconnectConfig {
+        ping_interval: Duration::from_secs(60),
+        ..ReconnectConfig::default()
+    };
+    let (client, conn) = tokio::join!(
+        ManagedWsClient::connect_with_config(&server.url, config),
+        server.accept()
+    );
+    (client.unwrap().with_stream_buffer(buffer), conn)
+}
+
+async fn next_book(stream: &mut SubscriptionStream<WsBook>) -> WsBook {
+    timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("timed out waiting for book")
+        .expect("stream ended")
+}
+
+/// Wait for the `allMids` message, which the server sends after everything under test.
+async fn wait_for_mids(client: &mut ManagedWsClient) {
+    loop {
+        let event = timeout(Duration::from_secs(5), client.next_event())
+            .await
+            .expect("timed out waiting for event")
+            .expect("client stopped");
+        match event {
+            WsEvent::Message {
+                message: WsMessage::AllMids { .. },
+                ..
+            } => return,
+            WsEvent::Message { message, .. } => panic!("unrouted message: {message:?}"),
+            _ => {}
+        }
+    }
+}
+
+#[tokio::test]
+async fn streams_are_routed_by_coin() {
+    let server = WsTestServer::start().await;
+    let (mut client, mut conn) = connect(&server, 16).await;
+
+    let mut eth = client.subscribe_typed::<WsBook>(book("ETH")).unwrap();
+    let mut btc = client.subscribe_typed::<WsBook>(book("BTC")).unwrap();
+    assert_eq!(conn.request().await["subscription"]["coin"], "ETH");
+    assert_eq!(conn.request().await["subscription"]["coin"], "BTC");
+
+    conn.send(book_message("BTC", 1)).await;
+    conn.send(book_message("ETH", 2)).await;
+    conn.send(book_message("BTC", 3)).await;
+    conn.send(all_mids_message()).await;
+
+    assert_eq!(next_book(&mut eth).await.time, 2);
+    assert_eq!(next_book(&mut btc).await.time, 1);
+    assert_eq!(next_book(&mut btc).await.time, 3);
+    // Messages without a stream still arrive as events.
+    wait_for_mids(&mut client).await;
+}
+
+#[tokio::test]
+async fn account_streams_are_routed_by_dex() {
+    let server = WsTestServer::start().await;
+    let (mut client, mut conn) = connect(&server, 16).await;
+
+    let user = "0x5ac99df645f3414876c816caa18b2d234024b487";
+    let state_sub = |dex: Option<&str>| Subscription::ClearinghouseState {
+        user: user.to_string(),
+        dex: dex.map(str::to_string),
+    };
+    let mut default_dex = client
+        .subscribe_typed::<WsClearinghouseState>(state_sub(None))
+        .unwrap();
+    let mut hip3 = client
+        .subscribe_typed::<WsClearinghouseState>(state_sub(Some("xyz")))
+        .unwrap();
+    conn.request().await;
+    conn.request().await;
+
+    let state: Value = serde_json::from_str(
+        &std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/info/clearinghouse_state.json"
+        ))
+        .unwrap(),
+    )
+    .unwrap();
+    for dex in ["xyz", ""] {
+        conn.send(json!({
+            "channel": "clearinghouseState",
+            "data": {"dex": dex, "user": user, "clearinghouseState": state},
+        }))
+        .await;
+    }
+    conn.send(all_mids_message()).await;
+    wait_for_mids(&mut client).await;
+
+    let next = |stream: &mut SubscriptionStream<WsClearinghouseState>| stream.next().now_or_never();
+    assert_eq!(next(&mut hip3).flatten().unwrap().dex, "xyz");
+    assert_eq!(next(&mut default_dex).flatten().unwrap().dex, "");
+    assert!(next(&mut hip3).is_none());
+    assert!(next(&mut default_dex).is_none());
+}
+
+#[tokio::test]
+async fn slow_stream_drops_and_counts_messages() {
+    let server = WsTestServer::start().await;
+    let (mut client, mut conn) = connect(&server, 1).await;
+
+    let mut eth = client.subscribe_typed::<WsBook>(book("ETH")).unwrap();
+    conn.request().await;
+    for time in 1..=3 {
+        conn.send(book_message("ETH", time)).await;
+    }
+    conn.send(all_mids_message()).await;
+    wait_for_mids(&mut client).await;
+
+    assert_eq!(eth.lagged(), 2);
+    assert_eq!(next_book(&mut eth).await.time, 1);
+}
+
+#[tokio::test]
+async fn unread_events_drop_the_oldest() {
+    let server = WsTestServer::start().await;
+    let (client, mut conn) = connect(&server, 16).await;
+    let mut client = client.with_event_buffer(2);
+
+    let mut eth = client.subscribe_typed::<WsBook>(book("ETH")).unwrap();
+    conn.request().await;
+    for _ in 0..3 {
+        conn.send(all_mids_message()).await;
+    }
+    // Routed after the mids, so they have all been queued once the book arrives.
+    conn.send(book_message("ETH", 1)).await;
+    next_book(&mut eth).await;
+
+    // `Connected` and the first mids made room for the last two.
+    assert_eq!(client.events_lagged(), 2);
+    for _ in 0..2 {
+        assert!(matches!(
+            client.next_event().await,
+            Some(WsEvent::Message {
+                message: WsMessage::AllMids { .. },
+                ..
+            })
+        ));
+    }
+    assert!(client.next_event().now_or_never().is_none());
+}
+
+#[tokio::test]
+async fn dropping_last_stream_unsubscribes() {
+    let server = WsTestServer::start().await;
+    let (client, mut conn) = connect(&server, 16).await;
+
+    let first = client.subscribe_typed::<WsBook>(book("ETH")).unwrap();
+    assert_eq!(conn.request().await["method"], "subscribe");
+    let mut second = client.subscribe_typed::<WsBook>(book("ETH")).unwrap();
+    // Both streams share one subscription, renewed for the second stream's snapshot.
+    assert_eq!(conn.request().await["method"], "unsubscribe");
+    assert_eq!(conn.request().await["method"], "subscribe");
+    assert_eq!(client.subscriptions(), vec![book("ETH")]);
+
+    drop(first);
+    conn.send(book_message("ETH", 1)).await;
+    assert_eq!(next_book(&mut second).await.time, 1);
+
+    drop(second);
+    conn.send(book_message("ETH", 2)).await;
+    let unsubscribe = timeout(Duration::from_secs(5), conn.request())
+        .await
+        .expect("timed out waiting for unsubscribe");
+    assert_eq!(unsubscribe["method"], "unsubscribe");
+    assert_eq!(unsubscribe["subscription"]["coin"], "ETH");
+    assert!(client.subscriptions().is_empty());
+}
+
+#[tokio::test]
+async fn joining_stream_gets_a_snapshot() {
+    let server = WsTestServer::start().await;
+    let (client, mut conn) = connect(&server, 16).await;
+
+    let mut first = client.subscribe_typed::<WsBook>(book("ETH")).unwrap();
+    conn.request().await;
+    conn.send(book_message("ETH", 1)).await;
+    assert_eq!(next_book(&mut first).await.time, 1);
+
+    let mut second = client.subscribe_typed::<WsBook>(book("ETH")).unwrap();
+    let unsubscribe = conn.request().await;
+    assert_eq!(unsubscribe["method"], "unsubscribe");
+    assert_eq!(unsubscribe["subscription"]["coin"], "ETH");
+    let subscribe = conn.request().await;
+    assert_eq!(subscribe["method"], "subscribe");
+    assert_eq!(subscribe["subscription"]["coin"], "ETH");
+
+    // The snapshot answering the renewed subscription reaches both streams.
+    conn.send(book_message("ETH", 2)).await;
+    assert_eq!(next_book(&mut second).await.time, 2);
+    assert_eq!(next_book(&mut first).await.time, 2);
+}
+
+#[tokio::test]
+async fn explicit_subscription_outlives_its_streams() {
+    let server = WsTestServer::start().await;
+    let (mut client, mut conn) = connect(&server, 16).await;
+
+    client.subscribe(book("ETH")).unwrap();
+    assert_eq!(conn.request().await["method"], "subscribe");
+    let stream = client.subscribe_typed::<WsBook>(book("ETH")).unwrap();
+    assert_eq!(conn.request().await["method"], "unsubscribe");
+    assert_eq!(conn.request().await["method"], "subscribe");
+
+    drop(stream);
+    // The message finds the stream dropped and releases it; the feed stays subscribed.
+    conn.send(book_message("ETH", 1)).await;
+    loop {
+        let event = timeout(Duration::from_secs(5), client.next_event())
+            .await
+            .expect("timed out waiting for event")
+            .expect("client stopped");
+        if let WsEvent::Message { message, .. } = event {
+            assert!(matches!(message, WsMessage::L2Book { .. }), "{message:?}");
+            break;
+        }
+    }
+    assert_eq!(client.subscriptions(), vec![book("ETH")]);
+
+    // No unsubscribe went out before this one.
+    client.unsubscribe(book("ETH")).unwrap();
+    let request = conn.request().await;
+    assert_eq!(request["method"], "unsubscribe");
+    assert_eq!(request["subscription"]["coin"], "ETH");
+    assert!(client.subscriptions().is_empty());
+}
+
+#[tokio::test]
+async fn payload_must_match_subscription() {
+    let server = WsTestServer::start().await;
+    let (client, _conn) = connect(&server, 16).await;
+
+    let err = client.subscribe_typed::<WsBbo>(book("ETH")).err().unwrap();
+    assert!(matches!(
+        err,
+        Error::WsPayloadMismatch { payload: "bbo", .. }
+    ));
+    assert!(client.subscriptions().is_empty());
+}
@@ -14,8 +14,8 @@ use alloy::primitives::Address;
 use rust_decimal_macros::dec;
 
 use hl_rs::actions::{
-    AgentEnableDexAbstraction, CreateSubAccount, NoOp, SetReferrer, SubAccountSpotTransfer,
-    SubAccountTransfer, ToggleBigBlocks, VaultTransfer,
+    AgentEnableDexAbstraction, CreateSubAccount, MicroUsd, NoOp, SetReferrer,
+    SubAccountSpotTransfer, SubAccountTransfer, ToggleBigBlocks, VaultTransfer,
 };
 
 use crate::common::{log_action, log_response, send_action, test_addresses};
@@ -109,7 +109,7 @@ async fn test_sub_account_transfer_deposit() {
     // Use a test sub-account address (would need to be created first)
     let sub_account = test_addresses::test_destination();
 
-    let action = SubAccountTransfer::deposit(sub_account, 10_000); // $.01
+    let action = SubAccountTransfer::deposit(sub_account, MicroUsd(10_000)); // $.01
     log_action("SubAccountTransfer (deposit)", &action);
 
     let result = send_action(action).await;
@@ -120,7 +120,7 @@ async fn test_sub_account_transfer_deposit() {
 async fn test_sub_account_transfer_withdraw() {
     let sub_account = test_addresses::test_destination();
 
-    let action = SubAccountTransfer::withdraw(sub_account, 500_000); // $0.50
+    let action = SubAccountTransfer::withdraw(sub_account, MicroUsd(500_000)); // $0.50
     log_action("SubAccountTransfer (withdraw)", &action);
 
     let result = send_action(action).await;
@@ -162,7 +162,7 @@ async fn test_vault_transfer_deposit() {
     // Use a test vault address
     let vault = Address::repeat_byte(0x55);
 
-    let action = VaultTransfer::deposit(vault, 1_000_000); // $1
+    let action = VaultTransfer::deposit(vault, MicroUsd(1_000_000)); // $1
     log_action("VaultTransfer (deposit)", &action);
 
     let result = send_action(action).await;
@@ -175,7 +175,7 @@ async fn test_vault_transfer_deposit() {
 async fn test_vault_transfer_withdraw() {
     let vault = Address::repeat_byte(0x55);
 
-    let action = VaultTransfer::withdraw(vault, 500_000); // $0.50
+    let action = VaultTransfer::withdraw(vault, MicroUsd(500_000)); // $0.50
     log_action("VaultTransfer (withdraw)", &action);
 
     let result = send_action(action).await;
@@ -11,9 +11,9 @@
 mod common;
 
 use hl_rs::actions::{
-    BatchCancel, BatchModify, BatchOrder, CancelByCloid, CancelByCloidWire, CancelWire, Grouping,
-    LimitOrderType, ModifyWire, OrderType, OrderWire, ScheduleCancel, Tif, TpSl, TriggerOrderType,
-    UpdateIsolatedMargin, UpdateLeverage,
+    BatchCancel, BatchModify, BatchOrder, CancelByCloid, CancelByCloidWire, CancelWire, Cloid,
+    Grouping, LimitOrderType, ModifyWire, OrderType, OrderWire, ScheduleCancel, Tif, TpSl,
+    TriggerOrderType, UpdateIsolatedMargin, UpdateLeverage,
 };
 
 use crate::common::{log_action, log_response, send_action};
@@ -81,6 +81,11 @@ fn make_limit_order(asset: u32, is_buy: bool, price: &str, size: &str) -> OrderW
     }
 }
 
+/// Cloid shared by the cloid order and cancel tests.
+fn test_cloid() -> Cloid {
+    Cloid::from_strategy("hl-rs-integration-tests", 12345)
+}
+
 fn make_trigger_order(
     asset: u32,
     is_buy: bool,
@@ -131,7 +136,7 @@ async fn test_batch_order_multiple_limits() {
 #[tokio::test]
 async fn test_batch_order_with_cloid() {
     let mut order = make_limit_order(TEST_ASSET, true, "100.0", "0.01");
-    order.client_order_id = Some("test-cloid-12345".to_string());
+    order.client_order_id = Some(test_cloid());
 
     let action = BatchOrder::new(vec![order]);
     log_action("BatchOrder (with cloid)", &action);
@@ -219,7 +224,7 @@ async fn test_batch_cancel_multiple() {
 async fn test_cancel_by_cloid() {
     let cancels = vec![CancelByCloidWire {
         asset: TEST_ASSET,
-        cloid: "test-cloid-12345".to_string(),
+        cloid: test_cloid(),
     }];
     let action = CancelByCloid::new(cancels);
     log_action("CancelByCloid", &action);
@@ -283,7 +288,7 @@ async fn test_schedule_cancel_at_time() {
 
 #[tokio::test]
 async fn test_schedule_cancel_now() {
-    let action = ScheduleCancel::now(); // Cancel all orders immediately
+    let action = ScheduleCancel::now(); // Remove the scheduled cancel
     log_action("ScheduleCancel (now)", &action);
 
     let result = send_action(action).await;
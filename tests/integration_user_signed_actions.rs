@@ -14,8 +14,9 @@ use alloy::primitives::Address;
 use rust_decimal_macros::dec;
 
 use hl_rs::actions::{
-    ApproveAgent, ApproveBuilderFee, ConvertToMultiSigUser, MultiSigSigners, SendAsset,
-    SpotTransfer, TokenDelegate, UsdClassTransfer, UsdSend, UserDexAbstraction, Withdraw,
+    ApproveAgent, ApproveBuilderFee, CDeposit, CWithdraw, ConvertToMultiSigUser, MultiSigSigners,
+    SendAsset, SpotTransfer, TokenDelegate, UsdClassTransfer, UsdSend, UserDexAbstraction, Wei,
+    Withdraw,
 };
 
 use crate::common::{log_action, log_response, send_action, signer_address, test_addresses};
@@ -192,7 +193,7 @@ async fn test_approve_builder_fee() {
 async fn test_token_delegate() {
     let validator = Address::repeat_byte(0xBB);
 
-    let action = TokenDelegate::delegate(validator, 1_000_000_000_000_000_000); // 1 token
+    let action = TokenDelegate::delegate(validator, Wei::from_hype(dec!(1)).unwrap());
     log_action("TokenDelegate (delegate)", &action);
 
     let result = send_action(action).await;
@@ -203,7 +204,7 @@ async fn test_token_delegate() {
 async fn test_token_undelegate() {
     let validator = Address::repeat_byte(0xBB);
 
-    let action = TokenDelegate::undelegate(validator, 500_000_000_000_000_000); // 0.5 token
+    let action = TokenDelegate::undelegate(validator, Wei::from_hype(dec!(0.5)).unwrap());
     log_action("TokenDelegate (undelegate)", &action);
 
     let result = send_action(action).await;
@@ -216,7 +217,7 @@ async fn test_token_delegate_builder() {
 
     let action = TokenDelegate::builder()
         .validator(validator)
-        .wei(1_000_000_000_000_000_000u64)
+        .wei(Wei::from_hype(dec!(1)).unwrap())
         .is_undelegate(false)
         .build()
         .unwrap();
@@ -226,6 +227,28 @@ async fn test_token_delegate_builder() {
     log_response("TokenDelegate (builder)", &result);
 }
 
+// ============================================================================
+// CDeposit / CWithdraw
+// ============================================================================
+
+#[tokio::test]
+async fn test_c_deposit() {
+    let action = CDeposit::new(Wei::from_hype(dec!(1)).unwrap());
+    log_action("CDeposit", &action);
+
+    let result = send_action(action).await;
+    log_response("CDeposit", &result);
+}
+
+#[tokio::test]
+async fn test_c_withdraw() {
+    let action = CWithdraw::new(Wei::from_hype(dec!(0.5)).unwrap());
+    log_action("CWithdraw", &action);
+
+    let result = send_action(action).await;
+    log_response("CWithdraw", &result);
+}
+
 // ============================================================================
 // UserDexAbstraction
 // ============================================================================
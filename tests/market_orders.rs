@@ -0,0 +1,157 @@
+//! Offline tests for [`ExchangeClient::market_open`] and [`ExchangeClient::market_close`].
+
+mod support;
+
+use alloy::signers::local::PrivateKeySigner;
+use hl_rs::{ApiError, Error, ExchangeClient};
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+
+use crate::support::{fixture, StubServer};
+
+const FILLED: &str = r#"{"status":"ok","response":{"type":"order","data":{"statuses":[{"filled":{"totalSz":"0.1234","avgPx":"3152.1","oid":77738308}}]}}}"#;
+const NO_MATCH: &str = r#"{"status":"ok","response":{"type":"order","data":{"statuses":[{"error":"Order could not immediately match against any resting orders. asset=1"}]}}}"#;
+
+/// HIP-3 perp dex `mydex`, listing `mydex:BTC`.
+const PERP_DEXS: &str = r#"[null,{"name":"mydex","fullName":"My Dex","deployer":"0x0000000000000000000000000000000000000001","deployerFeeScale":"0.0","feeRecipient":null,"oracleUpdater":null,"assetToStreamingOiCap":[],"lastDeployerFeeScaleChangeTime":"1970-01-01T00:00:00"}]"#;
+const MYDEX_META: &str = r#"{"universe":[{"name":"mydex:BTC","szDecimals":3,"maxLeverage":20}]}"#;
+
+/// Stub answering the metadata, mids and clearinghouse queries from fixtures and every
+/// `/exchange` post with `exchange_response`. Besides the default dex it lists the HIP-3 dex
+/// `mydex`, where the user is short 0.25 `mydex:BTC`.
+fn stub(exchange_response: &'static str) -> (ExchangeClient, StubServer) {
+    let server = StubServer::start(move |path, body| {
+        if path == "/exchange" {
+            return exchange_response.to_string();
+        }
+        let first = |name: &str| {
+            let pair: Value = serde_json::from_str(&fixture(name)).unwrap();
+            pair[0].to_string()
+        };
+        let dex = body.get("dex").and_then(Value::as_str);
+        match (body["type"].as_str().unwrap(), dex) {
+            ("meta", None) => first("info/meta_and_asset_ctxs.json"),
+            ("meta", Some("mydex")) => MYDEX_META.to_string(),
+            ("spotMeta", None) => first("info/spot_meta_and_asset_ctxs.json"),
+            ("perpDexs", None) => PERP_DEXS.to_string(),
+            ("allMids", None) => fixture("info/all_mids.json"),
+            ("l2Book", None) => fixture("info/l2_book.json"),
+            ("clearinghouseState", None) => fixture("info/clearinghouse_state.json"),
+            ("clearinghouseState", Some("mydex")) => fixture("info/clearinghouse_state.json")
+                .replace(r#""coin": "ETH""#, r#""coin": "mydex:BTC""#)
+                .replace(r#""szi": "0.5""#, r#""szi": "-0.25""#),
+            other => panic!("unexpected info request {other:?}"),
+        }
+    });
+
+    let client = ExchangeClient::new(server.base_url()).with_signer(PrivateKeySigner::random());
+    (client, server)
+}
+
+fn sent_order(server: &StubServer) -> Value {
+    let posts = server.bodies("/exchange");
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0]["action"]["type"], "order");
+    posts[0]["action"]["orders"][0].clone()
+}
+
+#[tokio::test]
+async fn market_open_sends_slippage_bounded_ioc() {
+    let (client, server) = stub(FILLED);
+    let fill = client
+        .market_open("ETH", true, dec!(0.12345), dec!(0.01))
+        .await
+        .unwrap();
+
+    // mid 3151.25 * 1.01 = 3182.7625, rounded down to 5 significant figures.
+    assert_eq!(
+        sent_order(&server),
+        json!({
+            "a": 1,
+            "b": true,
+            "p": "3182.7",
+            "s": "0.1234",
+            "r": false,
+            "t": {"limit": {"tif": "Ioc"}}
+        })
+    );
+    assert_eq!(fill.oid, 77738308);
+    assert_eq!(fill.total_sz, dec!(0.1234));
+    assert_eq!(fill.avg_px, dec!(3152.1));
+}
+
+#[tokio::test]
+async fn market_close_sends_reduce_only_opposite_side() {
+    let (client, server) = stub(FILLED);
+    client.market_close("ETH", None, dec!(0.01)).await.unwrap();
+
+    // Long 0.5 ETH: sell at mid * 0.99 = 3119.7375, rounded up.
+    let order = sent_order(&server);
+    assert_eq!(order["b"], false);
+    assert_eq!(order["p"], "3119.8");
+    assert_eq!(order["s"], "0.5");
+    assert_eq!(order["r"], true);
+}
+
+#[tokio::test]
+async fn market_close_caps_partial_size_at_position() {
+    let (client, server) = stub(FILLED);
+    client
+        .market_close("ETH", Some(dec!(2)), dec!(0.01))
+        .await
+        .unwrap();
+
+    assert_eq!(sent_order(&server)["s"], "0.5");
+}
+
+#[tokio::test]
+async fn market_close_reads_hip3_positions_from_their_dex() {
+    let (client, server) = stub(FILLED);
+    client
+        .market_close("mydex:BTC", None, dec!(0.01))
+        .await
+        .unwrap();
+
+    let states: Vec<_> = server
+        .bodies("/info")
+        .into_iter()
+        .filter(|body| body["type"] == "clearinghouseState")
+        .collect();
+    assert_eq!(states.len(), 1);
+    assert_eq!(states[0]["dex"], "mydex");
+
+    // Short 0.25 on the first dex's first asset: buy back at the l2 mid 113387 * 1.01
+    // = 114520.87, rounded down to 5 significant figures.
+    let order = sent_order(&server);
+    assert_eq!(order["a"], 110000);
+    assert_eq!(order["b"], true);
+    assert_eq!(order["p"], "114520");
+    assert_eq!(order["s"], "0.25");
+    assert_eq!(order["r"], true);
+}
+
+#[tokio::test]
+async fn market_close_without_position_fails() {
+    let (client, server) = stub(FILLED);
+    let err = client
+        .market_close("BTC", None, dec!(0.01))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::NoOpenPosition(coin) if coin == "BTC"));
+    assert!(server.bodies("/exchange").is_empty());
+}
+
+#[tokio::test]
+async fn market_open_surfaces_unfilled_ioc_as_error() {
+    let (client, _server) = stub(NO_MATCH);
+    let err = client
+        .market_open("ETH", false, dec!(1), dec!(0.01))
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, Error::Api(ApiError::IocNoMatch { asset: Some(1), .. })),
+        "{err:?}"
+    );
+}
@@ -0,0 +1,119 @@
+//! Offline tests for collecting multi-sig signatures and submitting `multiSig` actions.
+
+mod support;
+
+use alloy::primitives::{Address, Signature, B256};
+use alloy::signers::local::PrivateKeySigner;
+use hl_rs::{
+    BatchOrder, Error, ExchangeClient, ExchangeDataStatus, OrderWire, PartialSignature,
+    PreparedAction, SigningChain, Tif,
+};
+use rust_decimal_macros::dec;
+use serde_json::json;
+
+use crate::support::StubServer;
+
+const MULTI_SIG_USER: Address = Address::repeat_byte(0x11);
+const ORDER_RESTING: &str =
+    r#"{"status":"ok","response":{"type":"order","data":{"statuses":[{"resting":{"oid":7}}]}}}"#;
+
+fn wallet(byte: u8) -> PrivateKeySigner {
+    PrivateKeySigner::from_bytes(&B256::repeat_byte(byte)).unwrap()
+}
+
+fn server(signers: &[&PrivateKeySigner], threshold: u32) -> StubServer {
+    let config = json!({
+        "authorizedUsers": signers.iter().map(|w| w.address()).collect::<Vec<_>>(),
+        "threshold": threshold,
+    });
+    StubServer::start(move |path, body| match (path, body["type"].as_str()) {
+        ("/info", Some("userToMultiSigSigners")) => config.to_string(),
+        _ => ORDER_RESTING.to_string(),
+    })
+}
+
+#[tokio::test]
+async fn leader_collects_offline_signature_and_submits() {
+    let (leader, co_signer) = (wallet(1), wallet(2));
+    let server = server(&[&leader, &co_signer], 2);
+    let client = ExchangeClient::new(server.base_url()).with_signer(leader.clone());
+
+    let order = OrderWire::limit(0, true, dec!(100000), dec!(0.001), Tif::Gtc);
+    let mut builder = client
+        .multi_sig(MULTI_SIG_USER, BatchOrder::new(vec![order]))
+        .unwrap();
+    builder.sign(&leader).await.unwrap();
+
+    // The co-signer signs the same action elsewhere and sends back JSON.
+    let co_signer_json = builder
+        .sign_partial(&co_signer)
+        .await
+        .unwrap()
+        .to_json()
+        .unwrap();
+    builder
+        .add_signature(PartialSignature::from_json(&co_signer_json).unwrap())
+        .unwrap();
+
+    let config = client
+        .info()
+        .user_to_multi_sig_signers(&MULTI_SIG_USER)
+        .await
+        .unwrap()
+        .unwrap();
+    let nonce = builder.nonce();
+    let action = builder.build(&config).unwrap();
+    let response = client.send_action(action.clone()).await.unwrap();
+    assert!(matches!(
+        response.statuses[0],
+        ExchangeDataStatus::Resting(_)
+    ));
+
+    let body = &server.bodies("/exchange")[0];
+    assert_eq!(body["nonce"], json!(nonce));
+    assert_eq!(body["action"]["type"], "multiSig");
+    assert_eq!(body["action"]["signatures"].as_array().unwrap().len(), 2);
+    assert_eq!(
+        body["action"]["payload"]["outerSigner"],
+        json!(leader.address().to_string().to_lowercase())
+    );
+    assert_eq!(body["action"]["payload"]["action"]["type"], "order");
+
+    // The outer signature is the leader's, over the whole envelope.
+    let hash = PreparedAction::new(action, &SigningChain::Testnet, None, None)
+        .unwrap()
+        .signing_hash();
+    let signature: Signature = serde_json::from_value(body["signature"].clone()).unwrap();
+    assert_eq!(
+        signature.recover_address_from_prehash(&hash).unwrap(),
+        leader.address()
+    );
+}
+
+#[tokio::test]
+async fn threshold_is_checked_before_sending() {
+    let (leader, co_signer) = (wallet(1), wallet(2));
+    let server = server(&[&leader, &co_signer], 2);
+    let client = ExchangeClient::new(server.base_url()).with_signer(leader.clone());
+
+    let order = OrderWire::limit(0, true, dec!(100000), dec!(0.001), Tif::Gtc);
+    let mut builder = client
+        .multi_sig(MULTI_SIG_USER, BatchOrder::new(vec![order]))
+        .unwrap();
+    builder.sign(&leader).await.unwrap();
+
+    let config = client
+        .info()
+        .user_to_multi_sig_signers(&MULTI_SIG_USER)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        builder.build(&config),
+        Err(Error::MultiSigThresholdNotMet {
+            threshold: 2,
+            collected: 1
+        })
+    ));
+    assert!(server.bodies("/exchange").is_empty());
+}
@@ -0,0 +1,80 @@
+//! Process-wide nonce state: the [`nonce::set_shared`] source and the server clock
+//! calibrated from HTTP `Date` headers. Kept in its own test binary since both are global.
+
+mod support;
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use alloy::{primitives::Address, signers::local::PrivateKeySigner};
+use chrono::{Duration, Utc};
+use hl_rs::{
+    nonce::{self, InMemoryNonceSource, NonceSource},
+    BaseUrl, ClaimRewards, ExchangeClient, InfoClient, MultiSigBuilder, PreparedAction,
+    SigningChain,
+};
+
+use crate::support::{now_ms, StubServer};
+
+/// Hands out 1000, 1001, ...
+struct CountingSource(AtomicU64);
+
+impl NonceSource for CountingSource {
+    fn next_nonce(&self) -> hl_rs::Result<u64> {
+        Ok(self.0.fetch_add(1, Ordering::SeqCst))
+    }
+}
+
+#[tokio::test]
+async fn set_shared_replaces_the_source_everywhere() {
+    let wallet = PrivateKeySigner::random();
+    // Created before the source is replaced, and without a source of its own.
+    let client = ExchangeClient::new(BaseUrl::Testnet).with_signer(wallet.clone());
+
+    nonce::set_shared(Arc::new(CountingSource(AtomicU64::new(1000))));
+
+    let signed = PreparedAction::new(ClaimRewards::new(), &SigningChain::Testnet, None, None)
+        .unwrap()
+        .sign(&wallet)
+        .unwrap();
+    assert_eq!(signed.nonce, 1000);
+
+    let builder = MultiSigBuilder::new(
+        ClaimRewards::new(),
+        Address::repeat_byte(1),
+        wallet.address(),
+        &SigningChain::Testnet,
+        None,
+    )
+    .unwrap();
+    assert_eq!(builder.nonce(), 1001);
+
+    let signed = client
+        .sign_action(ClaimRewards::new(), &wallet)
+        .await
+        .unwrap();
+    assert_eq!(signed.nonce, 1002);
+}
+
+#[tokio::test]
+async fn date_headers_calibrate_the_server_clock() {
+    let ahead = Utc::now() + Duration::days(3);
+    let date = format!("date: {}\r\n", ahead.format("%a, %d %b %Y %H:%M:%S GMT"));
+    let server = StubServer::start_with_headers(date, |_, _| "{}".to_string());
+
+    let client = InfoClient::builder(server.base_url()).build().unwrap();
+    client.all_mids().await.unwrap();
+
+    let three_days_ms = 3 * 24 * 60 * 60 * 1000;
+    let offset = nonce::server_clock_offset_ms().expect("offset should be measured");
+    assert!((offset - three_days_ms).abs() < 2000, "offset {offset}");
+
+    // Local time is now more than 2 days behind the server, so nonces follow the server.
+    let local = now_ms();
+    assert!(nonce::check_nonce_window(local, nonce::server_time_ms()).is_err());
+    let next = InMemoryNonceSource::new().next_nonce().unwrap();
+    assert!(next >= local + three_days_ms as u64 - 2000);
+    nonce::check_nonce_window(next, nonce::server_time_ms()).unwrap();
+}
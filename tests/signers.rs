@@ -0,0 +1,66 @@
+//! Offline tests for plugging custom signers into `ExchangeClient`.
+
+mod support;
+
+use std::sync::Arc;
+
+use alloy::primitives::{Signature, B256};
+use hl_rs::{ActionSigner, Error, ExchangeClient, MockSigner, NoOp};
+
+use crate::support::StubServer;
+
+const OK_DEFAULT: &str = r#"{"status":"ok","response":{"type":"default"}}"#;
+
+#[tokio::test]
+async fn send_action_signs_with_custom_signer() {
+    let server = StubServer::start(|_, _| OK_DEFAULT.to_string());
+    let signer = MockSigner::new();
+    let client = ExchangeClient::new(server.base_url()).with_signer(signer.clone());
+
+    client.send_action(NoOp::invalidate_nonce(1)).await.unwrap();
+
+    let hashes = signer.signed_hashes();
+    assert_eq!(hashes.len(), 1);
+
+    let body = &server.bodies("/exchange")[0];
+    let signature: Signature = serde_json::from_value(body["signature"].clone()).unwrap();
+    assert_eq!(
+        signature.recover_address_from_prehash(&hashes[0]).unwrap(),
+        signer.address()
+    );
+}
+
+#[tokio::test]
+async fn shared_signer_matches_prepared_signing_hash() {
+    let server = StubServer::start(|_, _| OK_DEFAULT.to_string());
+    let signer = MockSigner::new();
+    let shared: Arc<dyn ActionSigner> = Arc::new(signer.clone());
+    let client = ExchangeClient::new(server.base_url()).with_shared_signer(shared.clone());
+
+    let expected: B256 = client
+        .prepare_action(NoOp::invalidate_nonce(7))
+        .unwrap()
+        .signing_hash();
+    let signed = client
+        .sign_action(NoOp::invalidate_nonce(7), &*shared)
+        .await
+        .unwrap();
+
+    assert_eq!(signer.signed_hashes(), vec![expected]);
+    assert_eq!(signed.nonce, 7);
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn signer_failure_is_reported_and_nothing_is_sent() {
+    let server = StubServer::start(|_, _| OK_DEFAULT.to_string());
+    let client = ExchangeClient::new(server.base_url()).with_signer(MockSigner::failing("denied"));
+
+    let err = client
+        .send_action(NoOp::invalidate_nonce(1))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::SignatureFailure(message) if message == "denied"));
+    assert!(server.requests().is_empty());
+}
@@ -0,0 +1,31 @@
+//! Clients for the in-process [`MockServer`].
+
+use alloy::signers::local::PrivateKeySigner;
+use hl_rs::{mock::MockServer, BatchOrder, ExchangeClient, ExchangeDataStatus, OrderWire, Tif};
+use rust_decimal_macros::dec;
+
+/// Asset index of ETH in the mock's markets.
+pub const ETH: u32 = 1;
+
+/// Client for a fresh wallet holding 10000 USDC on `server`.
+pub fn funded_client(server: &MockServer) -> (ExchangeClient, PrivateKeySigner) {
+    let wallet = PrivateKeySigner::random();
+    server.fund(wallet.address(), dec!(10000));
+    let client = ExchangeClient::new(server.base_url()).with_signer(wallet.clone());
+    (client, wallet)
+}
+
+/// [`funded_client`] with one resting ETH bid.
+pub async fn client_with_resting_bid(server: &MockServer) -> (ExchangeClient, PrivateKeySigner) {
+    let (client, wallet) = funded_client(server);
+    let bid = OrderWire::limit(ETH, true, dec!(2900), dec!(1), Tif::Gtc);
+    let mut response = client
+        .send_action(BatchOrder::new(vec![bid]))
+        .await
+        .unwrap();
+    assert!(matches!(
+        response.statuses.remove(0),
+        ExchangeDataStatus::Resting(_)
+    ));
+    (client, wallet)
+}
@@ -0,0 +1,147 @@
+//! Offline HTTP stub for exercising the clients without network access.
+//!
+//! Tests under `tests/integration/` include this module with
+//! `#[path = "../support/mod.rs"]`; with the `mock` feature it also carries helpers for the
+//! in-process [`MockServer`](hl_rs::mock::MockServer).
+
+#![allow(dead_code)]
+
+#[cfg(feature = "mock")]
+pub mod mock;
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hl_rs::{BaseUrl, SigningChain};
+use serde_json::Value;
+
+type Handler = dyn Fn(&str, &Value) -> String + Send + Sync;
+
+/// Local HTTP server answering every POST with `handler(path, json_body)`.
+///
+/// Requests are recorded in arrival order so tests can assert on what the client sent.
+pub struct StubServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<(String, Value)>>>,
+}
+
+impl StubServer {
+    pub fn start(handler: impl Fn(&str, &Value) -> String + Send + Sync + 'static) -> Self {
+        Self::start_with_headers("", handler)
+    }
+
+    /// Like [`start`](Self::start), adding `headers` (`"name: value\r\n"` lines) to every
+    /// response.
+    pub fn start_with_headers(
+        headers: impl Into<String>,
+        handler: impl Fn(&str, &Value) -> String + Send + Sync + 'static,
+    ) -> Self {
+        let headers: Arc<str> = headers.into().into();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { break };
+                let handler = handler.clone();
+                let recorded = recorded.clone();
+                let headers = headers.clone();
+                thread::spawn(move || serve_connection(stream, &headers, &*handler, &recorded));
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    pub fn base_url(&self) -> BaseUrl {
+        BaseUrl::Custom {
+            url: self.url.clone(),
+            signing_chain: SigningChain::Testnet,
+        }
+    }
+
+    /// All `(path, body)` pairs received so far.
+    pub fn requests(&self) -> Vec<(String, Value)> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Bodies received on `path`.
+    pub fn bodies(&self, path: &str) -> Vec<Value> {
+        self.requests()
+            .into_iter()
+            .filter(|(p, _)| p == path)
+            .map(|(_, body)| body)
+            .collect()
+    }
+}
+
+/// Local time in milliseconds.
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+/// Read an `/info`-style fixture from `tests/fixtures/`.
+pub fn fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("read {path}: {e}"))
+}
+
+fn serve_connection(
+    stream: TcpStream,
+    headers: &str,
+    handler: &Handler,
+    recorded: &Mutex<Vec<(String, Value)>>,
+) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut stream = stream;
+
+    // Keep-alive: serve requests until the client hangs up.
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+            return;
+        }
+        let path = request_line
+            .split_whitespace()
+            .nth(1)
+            .unwrap_or_default()
+            .to_string();
+
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+        let response = handler(&path, &body);
+        recorded.lock().unwrap().push((path, body));
+
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n{headers}content-length: {}\r\n\r\n{response}",
+            response.len()
+        )
+        .unwrap();
+    }
+}